@@ -3,7 +3,7 @@
 
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{EditOperation, IndexDocumentsMethod, VersionCondition};
 use meilisearch_types::settings::Unchecked;
 use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
 use meilisearch_types::InstanceUid;
@@ -96,6 +96,8 @@ pub enum KindDump {
         method: IndexDocumentsMethod,
         documents_count: u64,
         allow_index_creation: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version_condition: Option<VersionCondition>,
     },
     DocumentDeletion {
         documents_ids: Vec<String>,
@@ -104,6 +106,11 @@ pub enum KindDump {
     DocumentDeletionByFilter {
         filter: serde_json::Value,
     },
+    DocumentEdition {
+        filter: Option<serde_json::Value>,
+        documents_ids: Option<Vec<String>>,
+        operations: Vec<EditOperation>,
+    },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
         is_deletion: bool,
@@ -159,12 +166,14 @@ impl From<KindWithContent> for KindDump {
                 method,
                 documents_count,
                 allow_index_creation,
+                version_condition,
                 ..
             } => KindDump::DocumentImport {
                 primary_key,
                 method,
                 documents_count,
                 allow_index_creation,
+                version_condition,
             },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
@@ -172,6 +181,9 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 KindDump::DocumentDeletionByFilter { filter: filter_expr }
             }
+            KindWithContent::DocumentEdition { filter_expr, documents_ids, operations, .. } => {
+                KindDump::DocumentEdition { filter: filter_expr, documents_ids, operations }
+            }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -261,10 +273,18 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            expiry_field: Setting::NotSet,
+            version_field: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -282,11 +302,13 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: Some(S("bone")),
                         documents_count: 12,
+                        version_condition: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        skipped_documents: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -305,11 +327,13 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: None,
                         documents_count: 2,
+                        version_condition: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        skipped_documents: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
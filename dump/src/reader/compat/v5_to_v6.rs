@@ -103,6 +103,7 @@ impl CompatV5ToV6 {
                                 }
                             },
                             allow_index_creation,
+                            version_condition: None,
                         },
                         v5::tasks::TaskContent::DocumentDeletion { deletion, .. } => match deletion
                         {
@@ -132,6 +133,7 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                skipped_documents: None,
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -337,6 +339,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             },
             stop_words: settings.stop_words.into(),
             synonyms: settings.synonyms.into(),
+            synonym_sets: v6::Setting::NotSet,
             distinct_attribute: settings.distinct_attribute.into(),
             typo_tolerance: match settings.typo_tolerance {
                 v5::Setting::Set(typo) => v6::Setting::Set(v6::TypoTolerance {
@@ -369,6 +372,13 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            infix_searchable_attributes: v6::Setting::NotSet,
+            stemming_languages: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            document_schema: v6::Setting::NotSet,
+            ingest_pipeline: v6::Setting::NotSet,
+            expiry_field: v6::Setting::NotSet,
+            version_field: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
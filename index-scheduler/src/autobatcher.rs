@@ -26,6 +26,7 @@ enum AutobatchKind {
     },
     DocumentDeletion,
     DocumentDeletionByFilter,
+    DocumentEdition,
     DocumentClear,
     Settings {
         allow_index_creation: bool,
@@ -68,6 +69,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::DocumentDeletionByFilter { .. } => {
                 AutobatchKind::DocumentDeletionByFilter
             }
+            KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
                     allow_index_creation: allow_index_creation && !is_deletion,
@@ -104,6 +106,9 @@ pub enum BatchKind {
     DocumentDeletionByFilter {
         id: TaskId,
     },
+    DocumentEdition {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -205,6 +210,7 @@ impl BatchKind {
             K::DocumentDeletionByFilter => {
                 (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
             }
+            K::DocumentEdition => (Break(BatchKind::DocumentEdition { id: task_id }), false),
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -222,7 +228,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter | K::DocumentEdition) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -519,7 +525,8 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentDeletionByFilter { .. },
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentEdition { .. },
                 _,
             ) => {
                 unreachable!()
@@ -599,6 +606,7 @@ mod tests {
             content_file: Uuid::new_v4(),
             documents_count: 0,
             allow_index_creation,
+            version_condition: None,
         }
     }
 
@@ -29,8 +29,8 @@ use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
-    DeleteDocuments, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    DeleteDocuments, DocumentDeletionResult, EditDocuments, EditOperation, IndexDocumentsConfig,
+    IndexDocumentsMethod, IndexerConfig, Settings as MilliSettings, VersionCondition,
 };
 use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
@@ -42,7 +42,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
-use crate::utils::{self, swap_index_uid_in_task};
+use crate::utils::{self, parse_expired_documents_filter, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
 
 /// Represents a combination of tasks that can all be processed at the same time.
@@ -71,6 +71,10 @@ pub(crate) enum Batch {
         index_uid: String,
         task: Task,
     },
+    IndexDocumentEdition {
+        index_uid: String,
+        task: Task,
+    },
     IndexCreation {
         index_uid: String,
         primary_key: Option<String>,
@@ -93,7 +97,7 @@ pub(crate) enum Batch {
 
 #[derive(Debug)]
 pub(crate) enum DocumentOperation {
-    Add(Uuid),
+    Add(Uuid, Option<VersionCondition>),
     Delete(Vec<String>),
 }
 
@@ -156,6 +160,7 @@ impl Batch {
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
             | Batch::IndexDocumentDeletionByFilter { task, .. }
+            | Batch::IndexDocumentEdition { task, .. }
             | Batch::IndexUpdate { task, .. } => vec![task.uid],
             Batch::SnapshotCreation(tasks) | Batch::IndexDeletion { tasks, .. } => {
                 tasks.iter().map(|task| task.uid).collect()
@@ -195,7 +200,8 @@ impl Batch {
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
             | IndexDeletion { index_uid, .. }
-            | IndexDocumentDeletionByFilter { index_uid, .. } => Some(index_uid),
+            | IndexDocumentDeletionByFilter { index_uid, .. }
+            | IndexDocumentEdition { index_uid, .. } => Some(index_uid),
         }
     }
 }
@@ -247,6 +253,15 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::DocumentEdition { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                match &task.kind {
+                    KindWithContent::DocumentEdition { index_uid, .. } => {
+                        Ok(Some(Batch::IndexDocumentEdition { index_uid: index_uid.clone(), task }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentOperation { method, operation_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, operation_ids)?;
                 let primary_key = tasks
@@ -269,10 +284,12 @@ impl IndexScheduler {
                         KindWithContent::DocumentAdditionOrUpdate {
                             content_file,
                             documents_count,
+                            version_condition,
                             ..
                         } => {
                             documents_counts.push(documents_count);
-                            operations.push(DocumentOperation::Add(content_file));
+                            operations
+                                .push(DocumentOperation::Add(content_file, version_condition));
                         }
                         KindWithContent::DocumentDeletion { ref documents_ids, .. } => {
                             documents_counts.push(documents_ids.len() as u64);
@@ -900,7 +917,7 @@ impl IndexScheduler {
                     let rtxn = self.env.read_txn()?;
                     self.index_mapper.index(&rtxn, index_uid)?
                 };
-                let deleted_documents = delete_document_by_filter(filter, index);
+                let deleted_documents = delete_document_by_filter(filter, task.enqueued_at, index);
                 let original_filter = if let Some(Details::DocumentDeletionByFilter {
                     original_filter,
                     deleted_documents: _,
@@ -932,6 +949,63 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            Batch::IndexDocumentEdition { mut task, index_uid: _ } => {
+                let (index_uid, filter, documents_ids, operations) =
+                    if let KindWithContent::DocumentEdition {
+                        index_uid,
+                        filter_expr,
+                        documents_ids,
+                        operations,
+                    } = &task.kind
+                    {
+                        (index_uid, filter_expr, documents_ids, operations)
+                    } else {
+                        unreachable!()
+                    };
+                let index = {
+                    let rtxn = self.env.read_txn()?;
+                    self.index_mapper.index(&rtxn, index_uid)?
+                };
+                let must_stop_processing = self.must_stop_processing.clone();
+                let edited_documents = edit_documents(
+                    &index,
+                    self.index_mapper.indexer_config(),
+                    filter.as_ref(),
+                    documents_ids.as_deref(),
+                    operations.clone(),
+                    || must_stop_processing.get(),
+                );
+                let original_filter = if let Some(Details::DocumentEdition {
+                    original_filter,
+                    edited_documents: _,
+                }) = task.details
+                {
+                    original_filter
+                } else {
+                    // In the case of a `documentEdition` the details MUST be set
+                    unreachable!();
+                };
+
+                match edited_documents {
+                    Ok(edited_documents) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentEdition {
+                            original_filter,
+                            edited_documents: Some(edited_documents),
+                        });
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentEdition {
+                            original_filter,
+                            edited_documents: Some(0),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
             Batch::IndexCreation { index_uid, primary_key, task } => {
                 let wtxn = self.env.write_txn()?;
                 if self.index_mapper.exists(&wtxn, &index_uid)? {
@@ -1167,6 +1241,8 @@ impl IndexScheduler {
                     }
                 }
 
+                // The stale documents are skipped, and reported, when the documents are versioned.
+                let versioned_documents = index.version_field(index_wtxn)?.is_some();
                 let config = IndexDocumentsConfig { update_method: method, ..Default::default() };
 
                 let mut builder = milli::update::IndexDocuments::new(
@@ -1180,10 +1256,11 @@ impl IndexScheduler {
 
                 for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
                     match operation {
-                        DocumentOperation::Add(content_uuid) => {
+                        DocumentOperation::Add(content_uuid, version_condition) => {
                             let content_file = self.file_store.get_update(content_uuid)?;
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
+                            builder.set_version_condition(version_condition);
                             let (new_builder, user_result) = builder.add_documents(reader)?;
                             builder = new_builder;
 
@@ -1205,6 +1282,8 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        skipped_documents: versioned_documents
+                                            .then(|| received_documents.saturating_sub(count)),
                                     })
                                 }
                                 Err(e) => {
@@ -1212,6 +1291,7 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        skipped_documents: None,
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1487,24 +1567,86 @@ impl IndexScheduler {
     }
 }
 
-fn delete_document_by_filter(filter: &serde_json::Value, index: Index) -> Result<u64> {
-    let filter = Filter::from_json(filter)?;
-    Ok(if let Some(filter) = filter {
-        let mut wtxn = index.write_txn()?;
+fn delete_document_by_filter(
+    filter: &serde_json::Value,
+    enqueued_at: OffsetDateTime,
+    index: Index,
+) -> Result<u64> {
+    let mut wtxn = index.write_txn()?;
+
+    // The deletions of the documents that already expired when the task was enqueued, like
+    // the ones the scheduler registers, can use the expiry field even if it is not filterable.
+    let expired_at = index
+        .expiry_field(&wtxn)?
+        .zip(filter.as_str())
+        .and_then(|(expiry_field, filter)| parse_expired_documents_filter(expiry_field, filter))
+        .filter(|expired_at| *expired_at <= enqueued_at);
+
+    let candidates = match expired_at {
+        Some(expired_at) => index.expired_documents_ids(&wtxn, expired_at)?,
+        None => match Filter::from_json(filter)? {
+            Some(filter) => filter.evaluate(&wtxn, &index).map_err(|err| match err {
+                milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                    Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
+                }
+                e => e.into(),
+            })?,
+            None => return Ok(0),
+        },
+    };
+
+    let mut delete_operation = DeleteDocuments::new(&mut wtxn, &index)?;
+    delete_operation.delete_documents(&candidates);
+    let deleted_documents = delete_operation.execute().map(|result| result.deleted_documents)?;
+    wtxn.commit()?;
+    Ok(deleted_documents)
+}
+
+fn edit_documents(
+    index: &Index,
+    indexer_config: &IndexerConfig,
+    filter: Option<&serde_json::Value>,
+    documents_ids: Option<&[String]>,
+    operations: Vec<EditOperation>,
+    must_stop_processing: impl Fn() -> bool + Sync,
+) -> Result<u64> {
+    let mut wtxn = index.write_txn()?;
+
+    let filter = filter.map(Filter::from_json).transpose()?.flatten();
+    // An edition without any filter or ids never edits the whole index.
+    if filter.is_none() && documents_ids.is_none() {
+        return Ok(0);
+    }
 
-        let candidates = filter.evaluate(&wtxn, &index).map_err(|err| match err {
+    // The documents must match both the filter and the list of ids when they are specified.
+    let mut candidates = index.documents_ids(&wtxn)?;
+    if let Some(filter) = filter {
+        candidates &= filter.evaluate(&wtxn, index).map_err(|err| match err {
             milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
                 Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
             }
             e => e.into(),
         })?;
-        let mut delete_operation = DeleteDocuments::new(&mut wtxn, &index)?;
-        delete_operation.delete_documents(&candidates);
-        let deleted_documents =
-            delete_operation.execute().map(|result| result.deleted_documents)?;
-        wtxn.commit()?;
-        deleted_documents
-    } else {
-        0
-    })
+    }
+    if let Some(documents_ids) = documents_ids {
+        let external_documents_ids = index.external_documents_ids(&wtxn)?;
+        candidates &= documents_ids
+            .iter()
+            .filter_map(|id| external_documents_ids.get(id))
+            .collect::<RoaringBitmap>();
+    }
+
+    let mut builder = EditDocuments::new(
+        &mut wtxn,
+        index,
+        indexer_config,
+        operations,
+        |indexing_step| debug!("update: {:?}", indexing_step),
+        must_stop_processing,
+    )?;
+    builder.edit_documents(&candidates);
+    let edited_documents = builder.execute()?;
+    wtxn.commit()?;
+
+    Ok(edited_documents)
 }
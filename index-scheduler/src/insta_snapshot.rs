@@ -4,7 +4,7 @@ use std::fmt::Write;
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{Database, RoTxn};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Details, Task};
+use meilisearch_types::tasks::{Details, KindWithContent, Task};
 use roaring::RoaringBitmap;
 
 use crate::index_mapper::IndexMapper;
@@ -29,6 +29,8 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         finished_at,
         index_mapper,
         max_number_of_tasks: _,
+        documents_expiry_check_interval: _,
+        documents_expiry_checked_at: _,
         wake_up: _,
         dumps_path: _,
         snapshots_path: _,
@@ -160,20 +162,45 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(details) = details {
         snap.push_str(&format!("details: {}, ", &snapshot_details(details)));
     }
-    snap.push_str(&format!("kind: {kind:?}"));
+    snap.push_str(&format!("kind: {}", snapshot_kind(kind)));
 
     snap.push('}');
     snap
 }
 
+fn snapshot_kind(k: &KindWithContent) -> String {
+    match k {
+        KindWithContent::DocumentAdditionOrUpdate {
+            index_uid,
+            primary_key,
+            method,
+            content_file,
+            documents_count,
+            allow_index_creation,
+            version_condition: None,
+        } => {
+            format!("DocumentAdditionOrUpdate {{ index_uid: {index_uid:?}, primary_key: {primary_key:?}, method: {method:?}, content_file: {content_file:?}, documents_count: {documents_count}, allow_index_creation: {allow_index_creation} }}")
+        }
+        kind => format!("{kind:?}"),
+    }
+}
+
 fn snapshot_details(d: &Details) -> String {
     match d {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            skipped_documents: None,
         } => {
             format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
         }
+        Details::DocumentAdditionOrUpdate {
+            received_documents,
+            indexed_documents,
+            skipped_documents: Some(skipped_documents),
+        } => {
+            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}, skipped_documents: {skipped_documents} }}")
+        }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
         }
@@ -187,6 +214,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
+        Details::DocumentEdition { original_filter, edited_documents } => format!(
+           "{{ original_filter: {original_filter:?}, edited_documents: {edited_documents:?} }}"
+        ),
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
@@ -37,7 +37,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
@@ -57,7 +57,7 @@ use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, m
 use uuid::Uuid;
 
 use crate::index_mapper::IndexMapper;
-use crate::utils::{check_index_swap_validity, clamp_to_page_size};
+use crate::utils::{check_index_swap_validity, clamp_to_page_size, expired_documents_filter};
 
 pub(crate) type BEI128 =
     meilisearch_types::heed::zerocopy::I128<meilisearch_types::heed::byteorder::BE>;
@@ -247,6 +247,9 @@ pub struct IndexSchedulerOptions {
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
+    /// The interval at which the indexes are checked for expired documents to delete,
+    /// `None` to never delete the expired documents automatically.
+    pub documents_expiry_check_interval: Option<Duration>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -300,6 +303,12 @@ pub struct IndexScheduler {
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
 
+    /// The interval at which the expired documents deletions are scheduled.
+    pub(crate) documents_expiry_check_interval: Option<Duration>,
+
+    /// The last time the indexes were checked for expired documents.
+    pub(crate) documents_expiry_checked_at: Arc<RwLock<Option<Instant>>>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -350,6 +359,8 @@ impl IndexScheduler {
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
+            documents_expiry_check_interval: self.documents_expiry_check_interval,
+            documents_expiry_checked_at: self.documents_expiry_checked_at.clone(),
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -441,6 +452,8 @@ impl IndexScheduler {
             wake_up: Arc::new(SignalEvent::auto(true)),
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
+            documents_expiry_check_interval: options.documents_expiry_check_interval,
+            documents_expiry_checked_at: Arc::new(RwLock::new(None)),
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -555,7 +568,15 @@ impl IndexScheduler {
                 loop {
                     match run.tick() {
                         Ok(TickOutcome::TickAgain(_)) => (),
-                        Ok(TickOutcome::WaitForSignal) => run.wake_up.wait(),
+                        Ok(TickOutcome::WaitForSignal) => {
+                            // The expired documents must be checked even when no task is registered.
+                            match run.documents_expiry_check_interval {
+                                Some(interval) => {
+                                    run.wake_up.wait_timeout(interval);
+                                }
+                                None => run.wake_up.wait(),
+                            }
+                        }
                         Err(e) => {
                             log::error!("{}", e);
                             // Wait one second when an irrecoverable error occurs.
@@ -1020,6 +1041,7 @@ impl IndexScheduler {
         }
 
         self.cleanup_task_queue()?;
+        self.schedule_expired_documents_deletions()?;
 
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
         let batch =
@@ -1206,6 +1228,59 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Registers the deletion of the expired documents once every check interval.
+    fn schedule_expired_documents_deletions(&self) -> Result<()> {
+        let interval = match self.documents_expiry_check_interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+
+        let mut checked_at = self.documents_expiry_checked_at.write().unwrap();
+        if checked_at.map_or(false, |checked_at| checked_at.elapsed() < interval) {
+            return Ok(());
+        }
+        *checked_at = Some(Instant::now());
+        drop(checked_at);
+
+        self.register_expired_documents_deletions(OffsetDateTime::now_utc())
+    }
+
+    /// Registers a `DocumentDeletionByFilter` task for every index containing documents
+    /// that expired before `now`, unless a documents deletion is already enqueued for the index.
+    pub(crate) fn register_expired_documents_deletions(&self, now: OffsetDateTime) -> Result<()> {
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        let enqueued_deletions = self.get_status(&rtxn, Status::Enqueued)?
+            & self.get_kind(&rtxn, Kind::DocumentDeletion)?;
+
+        let mut deletions = Vec::new();
+        self.index_mapper.try_for_each_index::<(), ()>(&rtxn, |index_uid, index| {
+            let index_rtxn = index.read_txn()?;
+            let expiry_field = match index.expiry_field(&index_rtxn)? {
+                Some(expiry_field) => expiry_field,
+                None => return Ok(()),
+            };
+            if index.expired_documents_ids(&index_rtxn, now)?.is_empty()
+                || !self.index_tasks(&rtxn, index_uid)?.is_disjoint(&enqueued_deletions)
+            {
+                return Ok(());
+            }
+
+            deletions.push((index_uid.to_string(), expired_documents_filter(expiry_field, now)));
+            Ok(())
+        })?;
+        drop(rtxn);
+
+        for (index_uid, filter) in deletions {
+            log::info!("Deleting the expired documents of the `{index_uid}` index.");
+            self.register(KindWithContent::DocumentDeletionByFilter {
+                index_uid,
+                filter_expr: serde_json::Value::String(filter),
+            })?;
+        }
+
+        Ok(())
+    }
+
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
@@ -1309,6 +1384,7 @@ impl<'a> Dump<'a> {
                     method,
                     documents_count,
                     allow_index_creation,
+                    version_condition,
                 } => KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
@@ -1316,6 +1392,7 @@ impl<'a> Dump<'a> {
                     content_file: content_uuid.ok_or(Error::CorruptedDump)?,
                     documents_count,
                     allow_index_creation,
+                    version_condition,
                 },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
@@ -1327,6 +1404,14 @@ impl<'a> Dump<'a> {
                         index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     }
                 }
+                KindDump::DocumentEdition { filter, documents_ids, operations } => {
+                    KindWithContent::DocumentEdition {
+                        filter_expr: filter,
+                        documents_ids,
+                        operations,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
@@ -1534,6 +1619,7 @@ mod tests {
                 indexer_config,
                 autobatching_enabled: true,
                 max_number_of_tasks: 1_000_000,
+                documents_expiry_check_interval: None,
             };
             configuration(&mut options);
 
@@ -1600,6 +1686,7 @@ mod tests {
             content_file: Uuid::from_u128(content_file_uuid),
             documents_count,
             allow_index_creation: true,
+            version_condition: None,
         }
     }
 
@@ -2023,6 +2110,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_register");
@@ -2060,6 +2148,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
@@ -2096,6 +2185,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -2122,6 +2212,78 @@ mod tests {
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
 
+    #[test]
+    fn register_expired_documents_deletions() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let now = OffsetDateTime::now_utc();
+        let content = format!(
+            r#"[
+            {{ "id": 1, "doggo": "bob", "expires_at": {} }},
+            {{ "id": 2, "doggo": "jean", "expires_at": {} }},
+            {{ "id": 3, "doggo": "bork" }}
+        ]"#,
+            now.unix_timestamp() - 60,
+            now.unix_timestamp() + 3600
+        );
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+                version_condition: None,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder =
+            milli::update::Settings::new(&mut wtxn, &index, index_scheduler.indexer_config());
+        builder.set_expiry_field(S("expires_at"));
+        builder.execute(|_| (), || false).unwrap();
+        wtxn.commit().unwrap();
+
+        // A single deletion is enqueued even when the indexes are checked multiple times.
+        index_scheduler.register_expired_documents_deletions(now).unwrap();
+        index_scheduler.register_expired_documents_deletions(now).unwrap();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let deletions = index_scheduler.get_kind(&rtxn, Kind::DocumentDeletion).unwrap();
+        assert_eq!(deletions.len(), 1);
+        let task = index_scheduler.get_task(&rtxn, deletions.min().unwrap()).unwrap().unwrap();
+        assert_eq!(
+            task.kind,
+            KindWithContent::DocumentDeletionByFilter {
+                index_uid: S("doggos"),
+                filter_expr: serde_json::json!(format!(
+                    "\"expires_at\" <= {}",
+                    now.unix_timestamp()
+                )),
+            }
+        );
+        drop(rtxn);
+
+        handle.advance_one_successful_batch();
+
+        let rtxn = index.read_txn().unwrap();
+        let field_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let field_ids = field_ids_map.ids().collect::<Vec<_>>();
+        let documents = index
+            .all_documents(&rtxn)
+            .unwrap()
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|document| document["id"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(documents, vec![serde_json::json!(2), serde_json::json!(3)]);
+    }
+
     #[test]
     fn document_deletion_and_document_addition() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2150,6 +2312,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
@@ -2326,6 +2489,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         index_scheduler
@@ -2484,6 +2648,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2531,6 +2696,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2580,6 +2746,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2630,6 +2797,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2681,6 +2849,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3179,6 +3348,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -3217,6 +3387,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                version_condition: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -3273,6 +3444,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3321,6 +3493,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3375,6 +3548,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3434,6 +3608,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3498,6 +3673,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3551,6 +3727,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3603,6 +3780,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3664,6 +3842,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3721,6 +3900,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3802,6 +3982,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3885,6 +4066,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    version_condition: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
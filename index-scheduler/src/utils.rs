@@ -233,6 +233,21 @@ pub(crate) fn map_bound<T, U>(bound: Bound<T>, map: impl FnOnce(T) -> U) -> Boun
     }
 }
 
+/// Returns the filter selecting the documents that expired at the given date.
+pub(crate) fn expired_documents_filter(expiry_field: &str, date: OffsetDateTime) -> String {
+    format!("\"{}\" <= {}", expiry_field.replace('"', "\\\""), date.unix_timestamp())
+}
+
+/// Returns the date of a filter built by `expired_documents_filter` on the expiry field.
+pub(crate) fn parse_expired_documents_filter(
+    expiry_field: &str,
+    filter: &str,
+) -> Option<OffsetDateTime> {
+    let prefix = format!("\"{}\" <= ", expiry_field.replace('"', "\\\""));
+    let timestamp = filter.strip_prefix(&prefix)?.parse().ok()?;
+    OffsetDateTime::from_unix_timestamp(timestamp).ok()
+}
+
 pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     use KindWithContent as K;
     let mut index_uids = vec![];
@@ -240,6 +255,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
@@ -402,7 +418,11 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -488,6 +508,26 @@ impl IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentEdition { edited_documents, original_filter: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        let index_uid =
+                            if let KindWithContent::DocumentEdition { ref index_uid, .. } = kind {
+                                index_uid
+                            } else {
+                                unreachable!()
+                            };
+                        assert_eq!(&task_index_uid.unwrap(), index_uid);
+
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => {
+                                assert!(edited_documents.is_some());
+                            }
+                            Status::Failed | Status::Canceled => {
+                                assert!(edited_documents == Some(0));
+                            }
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
@@ -101,8 +101,14 @@ impl AuthController {
         let key_authorized_indexes = SearchRules::Set(key.indexes.into_iter().collect());
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
-
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        let allow_search_debug = self.is_key_authorized(uid, Action::SearchDebug, None)?;
+
+        Ok(AuthFilter {
+            search_rules,
+            key_authorized_indexes,
+            allow_index_creation,
+            allow_search_debug,
+        })
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
@@ -135,7 +141,31 @@ impl AuthController {
         action: Action,
         index: Option<&str>,
     ) -> Result<bool> {
-        match self
+        let expiration_date = match self.action_expiration_date(uid, action, index)? {
+            // the `*` action also grants the actions added after the creation of the key.
+            None if action != Action::All => {
+                self.action_expiration_date(uid, Action::All, index)?
+            }
+            expiration_date => expiration_date,
+        };
+
+        match expiration_date {
+            // check expiration date.
+            Some(Some(exp)) => Ok(OffsetDateTime::now_utc() < exp),
+            // no expiration date.
+            Some(None) => Ok(true),
+            // action or index forbidden.
+            None => Ok(false),
+        }
+    }
+
+    fn action_expiration_date(
+        &self,
+        uid: Uuid,
+        action: Action,
+        index: Option<&str>,
+    ) -> Result<Option<Option<OffsetDateTime>>> {
+        Ok(self
             .store
             // check if the key has access to all indexes.
             .get_expiration_date(uid, action, None)?
@@ -144,14 +174,7 @@ impl AuthController {
                 Some(index) => self.store.get_expiration_date(uid, action, Some(index))?,
                 // or to any index if no index has been requested.
                 None => self.store.prefix_first_expiration_date(uid, action)?,
-            }) {
-            // check expiration date.
-            Some(Some(exp)) => Ok(OffsetDateTime::now_utc() < exp),
-            // no expiration date.
-            Some(None) => Ok(true),
-            // action or index forbidden.
-            None => Ok(false),
-        }
+            }))
     }
 
     /// Delete all the keys in the DB.
@@ -170,6 +193,7 @@ pub struct AuthFilter {
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
     allow_index_creation: bool,
+    allow_search_debug: bool,
 }
 
 impl Default for AuthFilter {
@@ -178,6 +202,7 @@ impl Default for AuthFilter {
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
             allow_index_creation: true,
+            allow_search_debug: true,
         }
     }
 }
@@ -188,11 +213,17 @@ impl AuthFilter {
         self.allow_index_creation && self.is_index_authorized(index)
     }
 
+    #[inline]
+    pub fn allow_search_debug(&self, index: &str) -> bool {
+        self.allow_search_debug && self.is_index_authorized(index)
+    }
+
     pub fn with_allowed_indexes(allowed_indexes: HashSet<IndexUidPattern>) -> Self {
         Self {
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
             allow_index_creation: false,
+            allow_search_debug: false,
         }
     }
 
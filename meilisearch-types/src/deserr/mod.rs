@@ -9,9 +9,10 @@ use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, Value
 use crate::error::deserr_codes::*;
 use crate::error::{
     Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
-    ParseOffsetDateTimeError,
+    ParseOffsetDateTimeError, TooManyCropSnippetsError,
 };
 use crate::index_uid::IndexUidFormatError;
+use crate::locales::LocaleFormatError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
 
 pub mod query_params;
@@ -151,6 +152,11 @@ make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(
+    MissingDocumentEditionOperations,
+    missing_document_edition_operations
+);
+make_missing_field_convenience_builder!(MissingSimilarId, missing_similar_id);
 
 // Integrate a sub-error into a [`DeserrError`] by taking its error message but using
 // the default error code (C) from `Self`
@@ -184,3 +190,5 @@ merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
+merge_with_error_impl_take_error_message!(LocaleFormatError);
+merge_with_error_impl_take_error_message!(TooManyCropSnippetsError);
@@ -4,22 +4,47 @@ use std::fs::File;
 use std::io::{self, Seek, Write};
 use std::marker::PhantomData;
 
+#[cfg(feature = "columnar-payloads")]
+use arrow::array::{Array, AsArray, FixedSizeListArray};
+#[cfg(feature = "columnar-payloads")]
+use arrow::datatypes::{
+    DataType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type,
+    UInt32Type, UInt64Type, UInt8Type,
+};
+#[cfg(feature = "columnar-payloads")]
+use arrow::error::ArrowError;
+#[cfg(feature = "columnar-payloads")]
+use arrow::ipc::reader::StreamReader;
+#[cfg(feature = "columnar-payloads")]
+use arrow::record_batch::RecordBatch;
+#[cfg(feature = "columnar-payloads")]
+use arrow::util::display::array_value_to_string;
 use memmap2::MmapOptions;
-use milli::documents::{DocumentsBatchBuilder, Error};
+use milli::documents::{CsvEmptyCells, DocumentsBatchBuilder, Error};
 use milli::Object;
+#[cfg(feature = "columnar-payloads")]
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use serde::de::{SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 use serde_json::error::Category;
+#[cfg(feature = "columnar-payloads")]
+use serde_json::{Number, Value};
 
 use crate::error::{Code, ErrorCode};
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PayloadType {
     Ndjson,
     Json,
-    Csv { delimiter: u8 },
+    Csv {
+        delimiter: u8,
+    },
+    #[cfg(feature = "columnar-payloads")]
+    Parquet,
+    #[cfg(feature = "columnar-payloads")]
+    Arrow,
 }
 
 impl fmt::Display for PayloadType {
@@ -28,6 +53,10 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            #[cfg(feature = "columnar-payloads")]
+            PayloadType::Parquet => f.write_str("parquet"),
+            #[cfg(feature = "columnar-payloads")]
+            PayloadType::Arrow => f.write_str("arrow"),
         }
     }
 }
@@ -36,6 +65,8 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    #[cfg(feature = "columnar-payloads")]
+    MalformedColumnarPayload(ArrowError, PayloadType),
 }
 
 impl Display for DocumentFormatError {
@@ -74,6 +105,10 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            #[cfg(feature = "columnar-payloads")]
+            Self::MalformedColumnarPayload(e, b) => {
+                write!(f, "The `{}` payload provided is malformed: `{}`.", b, e)
+            }
         }
     }
 }
@@ -100,13 +135,23 @@ impl ErrorCode for DocumentFormatError {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
             DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            #[cfg(feature = "columnar-payloads")]
+            DocumentFormatError::MalformedColumnarPayload(_, _) => Code::MalformedPayload,
         }
     }
 }
 
 /// Reads CSV from input and write an obkv batch to writer.
-pub fn read_csv(file: &File, writer: impl Write + Seek, delimiter: u8) -> Result<u64> {
+pub fn read_csv(
+    file: &File,
+    writer: impl Write + Seek,
+    delimiter: u8,
+    empty_cells: CsvEmptyCells,
+    nested_headers: bool,
+) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(writer);
+    builder.set_csv_empty_cells(empty_cells);
+    builder.set_csv_nested_headers(nested_headers);
     let mmap = unsafe { MmapOptions::new().map(file)? };
     let csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(mmap.as_ref());
     builder.append_csv(csv).map_err(|e| (PayloadType::Csv { delimiter }, e))?;
@@ -167,6 +212,117 @@ pub fn read_ndjson(file: &File, writer: impl Write + Seek) -> Result<u64> {
     Ok(count as u64)
 }
 
+/// Reads Parquet from temporary file and write an obkv batch to writer.
+#[cfg(feature = "columnar-payloads")]
+pub fn read_parquet(file: &File, writer: impl Write + Seek) -> Result<u64> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file.try_clone()?)
+        .and_then(|builder| builder.build())
+        .map_err(|e| {
+            DocumentFormatError::MalformedColumnarPayload(e.into(), PayloadType::Parquet)
+        })?;
+
+    append_record_batches(DocumentsBatchBuilder::new(writer), reader, PayloadType::Parquet)
+}
+
+/// Reads an Arrow IPC stream from temporary file and write an obkv batch to writer.
+#[cfg(feature = "columnar-payloads")]
+pub fn read_arrow(file: &File, writer: impl Write + Seek) -> Result<u64> {
+    let mmap = unsafe { MmapOptions::new().map(file)? };
+    let reader = StreamReader::try_new(mmap.as_ref(), None)
+        .map_err(|e| DocumentFormatError::MalformedColumnarPayload(e, PayloadType::Arrow))?;
+
+    append_record_batches(DocumentsBatchBuilder::new(writer), reader, PayloadType::Arrow)
+}
+
+/// Converts the rows of the record batches into documents, the struct and list
+/// columns become JSON objects and arrays and the null values are skipped.
+#[cfg(feature = "columnar-payloads")]
+fn append_record_batches<W: Write + Seek>(
+    mut builder: DocumentsBatchBuilder<W>,
+    batches: impl Iterator<Item = std::result::Result<RecordBatch, ArrowError>>,
+    payload_type: PayloadType,
+) -> Result<u64> {
+    let malformed = |e: ArrowError| DocumentFormatError::MalformedColumnarPayload(e, payload_type);
+    let mut object = Object::new();
+    for batch in batches {
+        let batch = batch.map_err(malformed)?;
+        let schema = batch.schema();
+        for row in 0..batch.num_rows() {
+            object.clear();
+            for (field, column) in schema.fields().iter().zip(batch.columns()) {
+                if !column.is_null(row) {
+                    let value = arrow_value(column.as_ref(), row).map_err(malformed)?;
+                    object.insert(field.name().clone(), value);
+                }
+            }
+            builder.append_json_object(&object).map_err(DocumentFormatError::Io)?;
+        }
+    }
+
+    let count = builder.documents_count();
+    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
+
+    Ok(count as u64)
+}
+
+/// Converts the value at `row` of an Arrow array into a JSON value.
+#[cfg(feature = "columnar-payloads")]
+fn arrow_value(array: &dyn Array, row: usize) -> std::result::Result<Value, ArrowError> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Null => Value::Null,
+        DataType::Boolean => Value::Bool(array.as_boolean().value(row)),
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(row).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(row).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(row).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(row).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(row).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(row).into(),
+        DataType::Float32 => float_value(array.as_primitive::<Float32Type>().value(row) as f64),
+        DataType::Float64 => float_value(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => Value::String(array.as_string::<i32>().value(row).to_owned()),
+        DataType::LargeUtf8 => Value::String(array.as_string::<i64>().value(row).to_owned()),
+        DataType::List(_) => list_value(array.as_list::<i32>().value(row).as_ref())?,
+        DataType::LargeList(_) => list_value(array.as_list::<i64>().value(row).as_ref())?,
+        DataType::FixedSizeList(_, _) => {
+            let list = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            list_value(list.value(row).as_ref())?
+        }
+        DataType::Struct(fields) => {
+            let mut object = Object::new();
+            for (field, column) in fields.iter().zip(array.as_struct().columns()) {
+                if !column.is_null(row) {
+                    object.insert(field.name().clone(), arrow_value(column.as_ref(), row)?);
+                }
+            }
+            Value::Object(object)
+        }
+        // the dates, times, decimals and binaries are written as strings.
+        _ => Value::String(array_value_to_string(array, row)?),
+    };
+
+    Ok(value)
+}
+
+#[cfg(feature = "columnar-payloads")]
+fn list_value(array: &dyn Array) -> std::result::Result<Value, ArrowError> {
+    (0..array.len())
+        .map(|row| arrow_value(array, row))
+        .collect::<std::result::Result<_, _>>()
+        .map(Value::Array)
+}
+
+/// The infinite and NaN floats can't be represented by JSON numbers, they are written as `null`.
+#[cfg(feature = "columnar-payloads")]
+fn float_value(float: f64) -> Value {
+    Number::from_f64(float).map_or(Value::Null, Value::Number)
+}
+
 /// The actual handling of the deserialization process in serde
 /// avoids storing the deserialized object in memory.
 ///
@@ -212,3 +368,106 @@ where
     let visitor = SeqVisitor(f, PhantomData);
     deserializer.deserialize_seq(visitor)
 }
+
+#[cfg(all(test, feature = "columnar-payloads"))]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use arrow::array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, ListArray, StringArray, StructArray,
+    };
+    use arrow::datatypes::Int64Type;
+    use arrow::ipc::writer::StreamWriter;
+    use milli::documents::{obkv_to_object, DocumentsBatchReader};
+    use parquet::arrow::ArrowWriter;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn record_batch() -> RecordBatch {
+        let location = StructArray::try_from(vec![
+            ("lat", Arc::new(Float64Array::from(vec![48.86, 45.76])) as ArrayRef),
+            ("city", Arc::new(StringArray::from(vec!["Paris", "Lyon"])) as ArrayRef),
+        ])
+        .unwrap();
+        let tags = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]);
+
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            ("name", Arc::new(StringArray::from(vec![Some("kevin"), None])) as ArrayRef),
+            ("price", Arc::new(Float64Array::from(vec![12.5, 3.0])) as ArrayRef),
+            ("available", Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef),
+            ("location", Arc::new(location) as ArrayRef),
+            ("tags", Arc::new(tags) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    fn documents(batch: Vec<u8>) -> Vec<Value> {
+        let reader = DocumentsBatchReader::from_reader(Cursor::new(batch)).unwrap();
+        let (mut cursor, index) = reader.into_cursor_and_fields_index();
+        let mut documents = Vec::new();
+        while let Some(document) = cursor.next_document().unwrap() {
+            documents.push(Value::Object(obkv_to_object(&document, &index).unwrap()));
+        }
+        documents
+    }
+
+    fn expected_documents() -> Value {
+        json!([
+            {
+                "id": 1,
+                "name": "kevin",
+                "price": 12.5,
+                "available": true,
+                "location": { "lat": 48.86, "city": "Paris" },
+                "tags": [1, 2],
+            },
+            {
+                "id": 2,
+                "price": 3.0,
+                "available": false,
+                "location": { "lat": 45.76, "city": "Lyon" },
+            },
+        ])
+    }
+
+    #[test]
+    fn read_arrow_stream() {
+        let batch = record_batch();
+        let mut file = tempfile::tempfile().unwrap();
+        let mut writer = StreamWriter::try_new(&mut file, &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let mut output = Cursor::new(Vec::new());
+        let count = read_arrow(&file, &mut output).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(Value::Array(documents(output.into_inner())), expected_documents());
+
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"{ \"id\": 1 }").unwrap();
+        let error = read_arrow(&file, Cursor::new(Vec::new()));
+        assert!(matches!(error, Err(DocumentFormatError::MalformedColumnarPayload(_, _))));
+    }
+
+    #[test]
+    fn read_parquet_file() {
+        let batch = record_batch();
+        let file = tempfile::tempfile().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(file.try_clone().unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let mut output = Cursor::new(Vec::new());
+        let count = read_parquet(&file, &mut output).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(Value::Array(documents(output.into_inner())), expected_documents());
+    }
+}
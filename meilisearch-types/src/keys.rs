@@ -274,6 +274,9 @@ pub enum Action {
     #[serde(rename = "keys.delete")]
     #[deserr(rename = "keys.delete")]
     KeysDelete,
+    #[serde(rename = "search.debug")]
+    #[deserr(rename = "search.debug")]
+    SearchDebug,
 }
 
 impl Action {
@@ -310,6 +313,7 @@ impl Action {
             KEYS_GET => Some(Self::KeysGet),
             KEYS_UPDATE => Some(Self::KeysUpdate),
             KEYS_DELETE => Some(Self::KeysDelete),
+            SEARCH_DEBUG => Some(Self::SearchDebug),
             _otherwise => None,
         }
     }
@@ -352,4 +356,5 @@ pub mod actions {
     pub const KEYS_GET: u8 = KeysGet.repr();
     pub const KEYS_UPDATE: u8 = KeysUpdate.repr();
     pub const KEYS_DELETE: u8 = KeysDelete.repr();
+    pub const SEARCH_DEBUG: u8 = SearchDebug.repr();
 }
@@ -5,8 +5,10 @@ pub mod error;
 pub mod index_uid;
 pub mod index_uid_pattern;
 pub mod keys;
+pub mod locales;
 pub mod settings;
 pub mod star_or;
+pub mod synonyms;
 pub mod tasks;
 pub mod versioning;
 pub use milli::{heed, Index};
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use milli::tokenizer::Language;
+use milli::LocalizedAttributesRule;
+use serde::{Deserialize, Serialize};
+
+macro_rules! make_locale {
+    ($($locale:ident, $code:literal);* $(;)?) => {
+        /// A language, named by its ISO 639-3 code, that can be declared for
+        /// the attributes of an index or given along with a search query.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Deserr)]
+        #[serde(rename_all = "camelCase")]
+        #[deserr(rename_all = camelCase)]
+        pub enum Locale {
+            $($locale),*
+        }
+
+        impl Locale {
+            /// Returns the ISO 639-3 code of the locale.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Locale::$locale => $code),*
+                }
+            }
+
+            pub fn from_code(code: &str) -> Option<Locale> {
+                match code {
+                    $($code => Some(Locale::$locale),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+make_locale! {
+    Afr, "afr";
+    Aka, "aka";
+    Amh, "amh";
+    Ara, "ara";
+    Aze, "aze";
+    Bel, "bel";
+    Ben, "ben";
+    Bul, "bul";
+    Cat, "cat";
+    Ces, "ces";
+    Cmn, "cmn";
+    Dan, "dan";
+    Deu, "deu";
+    Ell, "ell";
+    Eng, "eng";
+    Epo, "epo";
+    Est, "est";
+    Fin, "fin";
+    Fra, "fra";
+    Guj, "guj";
+    Heb, "heb";
+    Hin, "hin";
+    Hrv, "hrv";
+    Hun, "hun";
+    Hye, "hye";
+    Ind, "ind";
+    Ita, "ita";
+    Jav, "jav";
+    Jpn, "jpn";
+    Kan, "kan";
+    Kat, "kat";
+    Khm, "khm";
+    Kor, "kor";
+    Lat, "lat";
+    Lav, "lav";
+    Lit, "lit";
+    Mal, "mal";
+    Mar, "mar";
+    Mkd, "mkd";
+    Mya, "mya";
+    Nep, "nep";
+    Nld, "nld";
+    Nob, "nob";
+    Ori, "ori";
+    Pan, "pan";
+    Pes, "pes";
+    Pol, "pol";
+    Por, "por";
+    Ron, "ron";
+    Rus, "rus";
+    Sin, "sin";
+    Slk, "slk";
+    Slv, "slv";
+    Sna, "sna";
+    Spa, "spa";
+    Srp, "srp";
+    Swe, "swe";
+    Tam, "tam";
+    Tel, "tel";
+    Tgl, "tgl";
+    Tha, "tha";
+    Tuk, "tuk";
+    Tur, "tur";
+    Ukr, "ukr";
+    Urd, "urd";
+    Uzb, "uzb";
+    Vie, "vie";
+    Yid, "yid";
+    Zul, "zul";
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::from_code(s.trim())
+            .ok_or_else(|| LocaleFormatError { invalid_locale: s.to_owned() })
+    }
+}
+
+impl From<Locale> for Language {
+    fn from(locale: Locale) -> Self {
+        Language::from_name(locale.code())
+    }
+}
+
+#[derive(Debug)]
+pub struct LocaleFormatError {
+    pub invalid_locale: String,
+}
+
+impl fmt::Display for LocaleFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unsupported locale `{}`, expected an ISO 639-3 code such as `eng`, `fra` or `jpn`.",
+            self.invalid_locale
+        )
+    }
+}
+
+impl std::error::Error for LocaleFormatError {}
+
+/// The user facing representation of a [`LocalizedAttributesRule`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRuleView {
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
+    fn from(view: LocalizedAttributesRuleView) -> Self {
+        LocalizedAttributesRule::new(
+            view.attribute_patterns,
+            view.locales.into_iter().map(Language::from).collect(),
+        )
+    }
+}
+
+impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(rule: LocalizedAttributesRule) -> Self {
+        LocalizedAttributesRuleView {
+            attribute_patterns: rule.attribute_patterns,
+            locales: rule
+                .locales
+                .iter()
+                .filter_map(|language| Locale::from_code(language.name()))
+                .collect(),
+        }
+    }
+}
@@ -9,11 +9,15 @@ use std::str::FromStr;
 use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, DocumentSchema, Index, IngestProcessor, RankingFunction,
+    StemmingLanguage, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
+use crate::locales::LocalizedAttributesRuleView;
 
 /// The maximimum number of results that the engine
 /// will be able to return in one search call.
@@ -127,6 +131,20 @@ impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRa
     }
 }
 
+impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSearchRankingRules> {
+    fn merge(
+        _self_: Option<Self>,
+        other: milli::CriterionError,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        Self::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg: other.to_string() },
+            merge_location,
+        )
+    }
+}
+
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -170,6 +188,9 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonymSets>)]
+    pub synonym_sets: Setting<BTreeMap<String, Setting<BTreeMap<String, Vec<String>>>>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -181,6 +202,27 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsInfixSearchableAttributes>)]
+    pub infix_searchable_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsStemmingLanguages>)]
+    pub stemming_languages: Setting<BTreeSet<StemmingLanguage>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentSchema>)]
+    pub document_schema: Setting<DocumentSchema>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIngestPipeline>)]
+    pub ingest_pipeline: Setting<Vec<IngestProcessor>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsExpiryField>)]
+    pub expiry_field: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVersionField>)]
+    pub version_field: Setting<String>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -197,10 +239,18 @@ impl Settings<Checked> {
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
+            synonym_sets: Setting::Reset,
             distinct_attribute: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            infix_searchable_attributes: Setting::Reset,
+            stemming_languages: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            document_schema: Setting::Reset,
+            ingest_pipeline: Setting::Reset,
+            expiry_field: Setting::Reset,
+            version_field: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -214,10 +264,18 @@ impl Settings<Checked> {
             ranking_rules,
             stop_words,
             synonyms,
+            synonym_sets,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            infix_searchable_attributes,
+            stemming_languages,
+            localized_attributes,
+            document_schema,
+            ingest_pipeline,
+            expiry_field,
+            version_field,
             ..
         } = self;
 
@@ -229,10 +287,18 @@ impl Settings<Checked> {
             ranking_rules,
             stop_words,
             synonyms,
+            synonym_sets,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            infix_searchable_attributes,
+            stemming_languages,
+            localized_attributes,
+            document_schema,
+            ingest_pipeline,
+            expiry_field,
+            version_field,
             _kind: PhantomData,
         }
     }
@@ -270,10 +336,18 @@ impl Settings<Unchecked> {
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
             synonyms: self.synonyms,
+            synonym_sets: self.synonym_sets,
             distinct_attribute: self.distinct_attribute,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            infix_searchable_attributes: self.infix_searchable_attributes,
+            stemming_languages: self.stemming_languages,
+            localized_attributes: self.localized_attributes,
+            document_schema: self.document_schema,
+            ingest_pipeline: self.ingest_pipeline,
+            expiry_field: self.expiry_field,
+            version_field: self.version_field,
             _kind: PhantomData,
         }
     }
@@ -337,6 +411,26 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.synonym_sets {
+        Setting::Set(ref synonym_sets) => builder.set_synonym_sets(
+            synonym_sets
+                .iter()
+                .map(|(name, synonyms)| {
+                    let synonyms = match synonyms {
+                        Setting::Set(synonyms) => {
+                            Setting::Set(synonyms.clone().into_iter().collect())
+                        }
+                        Setting::Reset => Setting::Reset,
+                        Setting::NotSet => Setting::NotSet,
+                    };
+                    (name.clone(), synonyms)
+                })
+                .collect(),
+        ),
+        Setting::Reset => builder.reset_synonym_sets(),
+        Setting::NotSet => (),
+    }
+
     match settings.distinct_attribute {
         Setting::Set(ref attr) => builder.set_distinct_field(attr.clone()),
         Setting::Reset => builder.reset_distinct_field(),
@@ -417,6 +511,52 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.infix_searchable_attributes {
+        Setting::Set(ref fields) => {
+            builder.set_infix_searchable_fields(fields.iter().cloned().collect())
+        }
+        Setting::Reset => builder.reset_infix_searchable_fields(),
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming_languages {
+        Setting::Set(ref languages) => builder.set_stemming_languages(languages.clone()),
+        Setting::Reset => builder.reset_stemming_languages(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => {
+            builder.set_localized_attributes_rules(rules.iter().cloned().map(Into::into).collect())
+        }
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.document_schema {
+        Setting::Set(ref schema) => builder.set_document_schema(schema.clone()),
+        Setting::Reset => builder.reset_document_schema(),
+        Setting::NotSet => (),
+    }
+
+    match settings.ingest_pipeline {
+        Setting::Set(ref processors) => builder.set_ingest_pipeline(processors.clone()),
+        Setting::Reset => builder.reset_ingest_pipeline(),
+        Setting::NotSet => (),
+    }
+
+    match settings.expiry_field {
+        Setting::Set(ref field) => builder.set_expiry_field(field.clone()),
+        Setting::Reset => builder.reset_expiry_field(),
+        Setting::NotSet => (),
+    }
+
+    match settings.version_field {
+        Setting::Set(ref field) => builder.set_version_field(field.clone()),
+        Setting::Reset => builder.reset_version_field(),
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -453,6 +593,20 @@ pub fn settings(
         .map(|(key, values)| (key.join(" "), values.iter().map(|value| value.join(" ")).collect()))
         .collect();
 
+    let synonym_sets = index
+        .synonym_sets(rtxn)?
+        .into_iter()
+        .map(|(name, synonyms)| {
+            let synonyms = synonyms
+                .iter()
+                .map(|(key, values)| {
+                    (key.join(" "), values.iter().map(|value| value.join(" ")).collect())
+                })
+                .collect();
+            (name, Setting::Set(synonyms))
+        })
+        .collect();
+
     let min_typo_word_len = MinWordSizeTyposSetting {
         one_typo: Setting::Set(index.min_word_len_one_typo(rtxn)?),
         two_typos: Setting::Set(index.min_word_len_two_typos(rtxn)?),
@@ -484,6 +638,23 @@ pub fn settings(
         ),
     };
 
+    let infix_searchable_attributes =
+        index.infix_searchable_fields(rtxn)?.into_iter().map(String::from).collect();
+
+    let stemming_languages = index.stemming_languages(rtxn)?.into_iter().collect();
+
+    let localized_attributes = index
+        .localized_attributes_rules(rtxn)?
+        .map(|rules| rules.into_iter().map(LocalizedAttributesRuleView::from).collect());
+
+    let document_schema = index.document_schema(rtxn)?;
+
+    let ingest_pipeline = index.ingest_pipeline(rtxn)?;
+
+    let expiry_field = index.expiry_field(rtxn)?.map(String::from);
+
+    let version_field = index.version_field(rtxn)?.map(String::from);
+
     Ok(Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -502,9 +673,29 @@ pub fn settings(
             None => Setting::Reset,
         },
         synonyms: Setting::Set(synonyms),
+        synonym_sets: Setting::Set(synonym_sets),
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        infix_searchable_attributes: Setting::Set(infix_searchable_attributes),
+        stemming_languages: Setting::Set(stemming_languages),
+        localized_attributes: match localized_attributes {
+            Some(rules) => Setting::Set(rules),
+            None => Setting::Reset,
+        },
+        document_schema: match document_schema {
+            Some(schema) => Setting::Set(schema),
+            None => Setting::Reset,
+        },
+        ingest_pipeline: Setting::Set(ingest_pipeline),
+        expiry_field: match expiry_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
+        version_field: match version_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
         _kind: PhantomData,
     })
 }
@@ -531,6 +722,8 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score of the function applied on the value of the field specified.
+    Function(String, RankingFunction),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -587,6 +780,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Function(x, function) => RankingRuleView::Function(x, function),
         }
     }
 }
@@ -601,6 +795,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Function(x, function) => Criterion::Function(x, function),
         }
     }
 }
@@ -620,10 +815,18 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            expiry_field: Setting::NotSet,
+            version_field: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -641,10 +844,18 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            expiry_field: Setting::NotSet,
+            version_field: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
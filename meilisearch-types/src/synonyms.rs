@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::{Code, ErrorCode};
+
+/// Parses a synonyms file in the Solr format into the synonyms of a synonym set.
+///
+/// Each line of the file is either:
+/// - a list of equivalent words separated by commas, e.g. `sofa, couch, settee`,
+///   where every word is a synonym of all the others,
+/// - an explicit mapping, e.g. `tv, television => telly`, where the words on the
+///   left are replaced by the words on the right but not the other way around.
+///
+/// The empty lines and the lines starting with a `#` are ignored.
+pub fn parse_solr_synonyms(text: &str) -> Result<BTreeMap<String, Vec<String>>, SolrSynonymsError> {
+    let mut synonyms: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |reason| SolrSynonymsError { line: index + 1, reason };
+        let mut sides = line.split("=>");
+        let left = parse_words(sides.next().unwrap_or_default());
+        match (sides.next(), sides.next()) {
+            (None, _) => {
+                if left.len() < 2 {
+                    return Err(error("expected at least two comma separated words"));
+                }
+                for word in &left {
+                    let others = left.iter().filter(|other| *other != word).cloned();
+                    synonyms.entry(word.clone()).or_default().extend(others);
+                }
+            }
+            (Some(right), None) => {
+                let right = parse_words(right);
+                if left.is_empty() || right.is_empty() {
+                    return Err(error("expected words on both sides of `=>`"));
+                }
+                for word in left {
+                    synonyms.entry(word).or_default().extend(right.iter().cloned());
+                }
+            }
+            (Some(_), Some(_)) => return Err(error("`=>` can only be used once per line")),
+        }
+    }
+
+    for words in synonyms.values_mut() {
+        words.sort_unstable();
+        words.dedup();
+    }
+
+    Ok(synonyms)
+}
+
+fn parse_words(text: &str) -> Vec<String> {
+    text.split(',').map(str::trim).filter(|word| !word.is_empty()).map(String::from).collect()
+}
+
+#[derive(Debug)]
+pub struct SolrSynonymsError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for SolrSynonymsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid synonyms file at line {}: {}.", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for SolrSynonymsError {}
+
+impl ErrorCode for SolrSynonymsError {
+    fn error_code(&self) -> Code {
+        Code::InvalidSettingsSynonymSets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_equivalent_and_explicit_synonyms() {
+        let text = "
+            # furniture
+            sofa, couch, settee
+            tv, television => telly
+
+            couch => divan
+        ";
+
+        let synonyms = parse_solr_synonyms(text).unwrap();
+        insta::assert_debug_snapshot!(synonyms, @r###"
+        {
+            "couch": [
+                "divan",
+                "settee",
+                "sofa",
+            ],
+            "settee": [
+                "couch",
+                "sofa",
+            ],
+            "sofa": [
+                "couch",
+                "settee",
+            ],
+            "television": [
+                "telly",
+            ],
+            "tv": [
+                "telly",
+            ],
+        }
+        "###);
+    }
+
+    #[test]
+    fn parse_invalid_synonyms() {
+        let error = parse_solr_synonyms("sofa, couch\nsofa").unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid synonyms file at line 2: expected at least two comma separated words.");
+
+        let error = parse_solr_synonyms("tv => television => telly").unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid synonyms file at line 1: `=>` can only be used once per line.");
+
+        let error = parse_solr_synonyms("tv, television =>").unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid synonyms file at line 1: expected words on both sides of `=>`.");
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -10,6 +10,7 @@ use byte_unit::Byte;
 use http::header::CONTENT_TYPE;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::locales::Locale;
 use meilisearch_types::InstanceUid;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -32,8 +33,9 @@ use crate::routes::indexes::documents::UpdateDocumentsQuery;
 use crate::routes::tasks::TasksFilterQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    SearchQuery, SearchQueryWithIndex, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    SearchQuery, SearchQueryWithIndex, SearchResult, SynonymsView, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_CROP_SNIPPETS, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
 };
 use crate::Opt;
 
@@ -245,6 +247,7 @@ struct Infos {
     db_path: bool,
     import_dump: bool,
     dump_dir: bool,
+    documents_expiry_check_interval: u64,
     ignore_missing_dump: bool,
     ignore_dump_if_db_exists: bool,
     import_snapshot: bool,
@@ -298,6 +301,7 @@ impl From<Opt> for Infos {
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dump_dir,
+            documents_expiry_check_interval,
             log_level,
             indexer_options,
             config_file_path,
@@ -322,6 +326,7 @@ impl From<Opt> for Infos {
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dump_dir: dump_dir != PathBuf::from("dumps/"),
+            documents_expiry_check_interval,
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             import_snapshot: import_snapshot.is_some(),
@@ -544,6 +549,22 @@ pub struct SearchAggregator {
     filter_total_number_of_criteria: usize,
     used_syntax: HashMap<String, usize>,
 
+    // boost
+    // the maximum number of boosts used in a single request
+    max_boosts_number: usize,
+
+    // ranking rules
+    // every time a request overrides the ranking rules of the index, this field must be incremented by one
+    total_ranking_rules_override: usize,
+
+    // random
+    // every time a request has a random seed, this field must be incremented by one
+    total_random_seed: usize,
+
+    // debug
+    // every time a request asks for the trace of the ranking rules, this field must be incremented by one
+    total_debug: usize,
+
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
@@ -551,10 +572,21 @@ pub struct SearchAggregator {
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
 
+    // locales
+    // the locales given with the requests
+    locales: BTreeSet<Locale>,
+
+    // synonyms
+    // the number of requests disabling the synonyms
+    total_synonyms_disabled: usize,
+    // the number of requests selecting some synonym sets only
+    total_synonym_sets_selected: usize,
+
     // pagination
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    search_after: bool,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -565,10 +597,15 @@ pub struct SearchAggregator {
     crop_marker: bool,
     show_matches_position: bool,
     crop_length: bool,
+    crop_snippets: bool,
 
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+
+    // group by
+    total_group_by: usize,
+    max_hits_per_group: usize,
 }
 
 impl SearchAggregator {
@@ -609,6 +646,22 @@ impl SearchAggregator {
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
+        if let Some(ref boost) = query.boost {
+            ret.max_boosts_number = boost.len();
+        }
+
+        if query.ranking_rules.is_some() {
+            ret.total_ranking_rules_override = 1;
+        }
+
+        if query.random_seed.is_some() {
+            ret.total_random_seed = 1;
+        }
+
+        if query.debug {
+            ret.total_debug = 1;
+        }
+
         if let Some(ref q) = query.q {
             ret.max_terms_number = q.split_whitespace().count();
         }
@@ -623,13 +676,30 @@ impl SearchAggregator {
             ret.max_offset = query.offset;
             ret.finite_pagination = 0;
         }
+        ret.search_after = query.search_after.is_some();
+
+        if query.group_by.is_some() {
+            ret.total_group_by = 1;
+            ret.max_hits_per_group = query.hits_per_group;
+        }
 
         ret.matching_strategy.insert(format!("{:?}", query.matching_strategy), 1);
 
+        if let Some(ref locales) = query.locales {
+            ret.locales = locales.iter().copied().collect();
+        }
+
+        match query.synonyms {
+            Some(SynonymsView::Enabled(false)) => ret.total_synonyms_disabled = 1,
+            Some(SynonymsView::Sets(_)) => ret.total_synonym_sets_selected = 1,
+            Some(SynonymsView::Enabled(true)) | None => (),
+        }
+
         ret.highlight_pre_tag = query.highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = query.highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
         ret.crop_marker = query.crop_marker != DEFAULT_CROP_MARKER();
         ret.crop_length = query.crop_length != DEFAULT_CROP_LENGTH();
+        ret.crop_snippets = query.crop_snippets != DEFAULT_CROP_SNIPPETS();
         ret.show_matches_position = query.show_matches_position;
 
         ret
@@ -675,6 +745,20 @@ impl SearchAggregator {
             let used_syntax = self.used_syntax.entry(key).or_insert(0);
             *used_syntax = used_syntax.saturating_add(value);
         }
+
+        // boost
+        self.max_boosts_number = self.max_boosts_number.max(other.max_boosts_number);
+
+        // ranking rules
+        self.total_ranking_rules_override =
+            self.total_ranking_rules_override.saturating_add(other.total_ranking_rules_override);
+
+        // random
+        self.total_random_seed = self.total_random_seed.saturating_add(other.total_random_seed);
+
+        // debug
+        self.total_debug = self.total_debug.saturating_add(other.total_debug);
+
         // q
         self.max_terms_number = self.max_terms_number.max(other.max_terms_number);
 
@@ -682,6 +766,7 @@ impl SearchAggregator {
         self.max_limit = self.max_limit.max(other.max_limit);
         self.max_offset = self.max_offset.max(other.max_offset);
         self.finite_pagination += other.finite_pagination;
+        self.search_after |= other.search_after;
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -694,6 +779,7 @@ impl SearchAggregator {
         self.crop_marker |= other.crop_marker;
         self.show_matches_position |= other.show_matches_position;
         self.crop_length |= other.crop_length;
+        self.crop_snippets |= other.crop_snippets;
 
         // facets
         self.facets_sum_of_terms =
@@ -701,11 +787,24 @@ impl SearchAggregator {
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(other.facets_total_number_of_facets);
 
+        // group by
+        self.total_group_by = self.total_group_by.saturating_add(other.total_group_by);
+        self.max_hits_per_group = self.max_hits_per_group.max(other.max_hits_per_group);
+
         // matching strategy
         for (key, value) in other.matching_strategy.into_iter() {
             let matching_strategy = self.matching_strategy.entry(key).or_insert(0);
             *matching_strategy = matching_strategy.saturating_add(value);
         }
+
+        // locales
+        self.locales.append(&mut other.locales);
+
+        // synonyms
+        self.total_synonyms_disabled =
+            self.total_synonyms_disabled.saturating_add(other.total_synonyms_disabled);
+        self.total_synonym_sets_selected =
+            self.total_synonym_sets_selected.saturating_add(other.total_synonym_sets_selected);
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -737,6 +836,18 @@ impl SearchAggregator {
                    "avg_criteria_number": format!("{:.2}", self.filter_sum_of_criteria_terms as f64 / self.filter_total_number_of_criteria as f64),
                    "most_used_syntax": self.used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
+                "boost": {
+                   "max_boosts_number": self.max_boosts_number,
+                },
+                "ranking_rules": {
+                   "total_override": self.total_ranking_rules_override,
+                },
+                "random": {
+                   "total_seeded": self.total_random_seed,
+                },
+                "debug": {
+                   "total_received": self.total_debug,
+                },
                 "q": {
                    "max_terms_number": self.max_terms_number,
                 },
@@ -744,6 +855,7 @@ impl SearchAggregator {
                    "max_limit": self.max_limit,
                    "max_offset": self.max_offset,
                    "most_used_navigation": if self.finite_pagination > (self.total_received / 2) { "exhaustive" } else { "estimated" },
+                   "with_search_after": self.search_after,
                 },
                 "formatting": {
                     "max_attributes_to_retrieve": self.max_attributes_to_retrieve,
@@ -754,13 +866,23 @@ impl SearchAggregator {
                     "crop_marker": self.crop_marker,
                     "show_matches_position": self.show_matches_position,
                     "crop_length": self.crop_length,
+                    "crop_snippets": self.crop_snippets,
                 },
                 "facets": {
                     "avg_facets_number": format!("{:.2}", self.facets_sum_of_terms as f64 / self.facets_total_number_of_facets as f64),
                 },
+                "group_by": {
+                    "total_group_by": self.total_group_by,
+                    "max_hits_per_group": self.max_hits_per_group,
+                },
                 "matching_strategy": {
                     "most_used_strategy": self.matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
-                }
+                },
+                "locales": self.locales,
+                "synonyms": {
+                    "total_disabled": self.total_synonyms_disabled,
+                    "total_sets_selected": self.total_synonym_sets_selected,
+                },
             });
 
             Some(Track {
@@ -14,6 +14,10 @@ pub enum MeilisearchHttpError {
     MissingContentType(Vec<String>),
     #[error("The Content-Type `{0}` does not support the use of a csv delimiter. The csv delimiter can only be used with the Content-Type `text/csv`.")]
     CsvDelimiterWithWrongContentType(String),
+    #[error("The Content-Type `{0}` does not support the use of the csv empty cells parameter. The csv empty cells parameter can only be used with the Content-Type `text/csv`.")]
+    CsvEmptyCellsWithWrongContentType(String),
+    #[error("The Content-Type `{0}` does not support the use of the csv nested headers parameter. The csv nested headers parameter can only be used with the Content-Type `text/csv`.")]
+    CsvNestedHeadersWithWrongContentType(String),
     #[error(
         "The Content-Type `{0}` is invalid. Accepted values for the Content-Type header are: {}",
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
@@ -23,6 +27,8 @@ pub enum MeilisearchHttpError {
     DocumentNotFound(String),
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
+    #[error("A document edition must select the documents with a `filter` or `ids`.")]
+    MissingDocumentEditionSelection,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidExpression(&'static [&'static str], Value),
     #[error("A {0} payload is missing.")]
@@ -44,6 +50,8 @@ pub enum MeilisearchHttpError {
     #[error(transparent)]
     Milli(#[from] meilisearch_types::milli::Error),
     #[error(transparent)]
+    InvalidSearchCursor(#[from] meilisearch_types::milli::InvalidSearchCursor),
+    #[error(transparent)]
     Payload(#[from] PayloadError),
     #[error(transparent)]
     FileStore(#[from] file_store::Error),
@@ -58,10 +66,15 @@ impl ErrorCode for MeilisearchHttpError {
         match self {
             MeilisearchHttpError::MissingContentType(_) => Code::MissingContentType,
             MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => Code::InvalidContentType,
+            MeilisearchHttpError::CsvEmptyCellsWithWrongContentType(_) => Code::InvalidContentType,
+            MeilisearchHttpError::CsvNestedHeadersWithWrongContentType(_) => {
+                Code::InvalidContentType
+            }
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
+            MeilisearchHttpError::MissingDocumentEditionSelection => Code::MissingDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
@@ -70,6 +83,7 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::HeedError(_) => Code::Internal,
             MeilisearchHttpError::IndexScheduler(e) => e.error_code(),
             MeilisearchHttpError::Milli(e) => e.error_code(),
+            MeilisearchHttpError::InvalidSearchCursor(_) => Code::InvalidSearchSearchAfter,
             MeilisearchHttpError::Payload(e) => e.error_code(),
             MeilisearchHttpError::FileStore(_) => Code::Internal,
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
@@ -6,6 +6,8 @@ pub enum AuthenticationError {
     MissingAuthorizationHeader,
     #[error("The provided API key is invalid.")]
     InvalidToken,
+    #[error("The provided API key is not allowed to perform the `{0}` action.")]
+    MissingAction(&'static str),
     // Triggered on configuration error.
     #[error("An internal error has occurred. `Irretrievable state`.")]
     IrretrievableState,
@@ -18,6 +20,7 @@ impl ErrorCode for AuthenticationError {
         match self {
             AuthenticationError::MissingAuthorizationHeader => Code::MissingAuthorizationHeader,
             AuthenticationError::InvalidToken => Code::InvalidApiKey,
+            AuthenticationError::MissingAction(_) => Code::MissingAction,
             AuthenticationError::IrretrievableState => Code::Internal,
             AuthenticationError::MissingMasterKey => Code::MissingMasterKey,
         }
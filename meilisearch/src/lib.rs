@@ -236,6 +236,10 @@ fn open_or_create_database_unchecked(
             indexer_config: (&opt.indexer_options).try_into()?,
             autobatching_enabled: true,
             max_number_of_tasks: 1_000_000,
+            documents_expiry_check_interval: match opt.documents_expiry_check_interval {
+                0 => None,
+                interval => Some(Duration::from_secs(interval)),
+            },
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().get_bytes() as usize,
             index_count: DEFAULT_INDEX_COUNT,
         })?)
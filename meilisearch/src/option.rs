@@ -47,6 +47,7 @@ const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
+const MEILI_DOCUMENTS_EXPIRY_CHECK_INTERVAL: &str = "MEILI_DOCUMENTS_EXPIRY_CHECK_INTERVAL";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
@@ -60,6 +61,7 @@ const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
+const DEFAULT_DOCUMENTS_EXPIRY_CHECK_INTERVAL_SEC: u64 = 60;
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
@@ -281,6 +283,12 @@ pub struct Opt {
     #[serde(default = "default_dump_dir")]
     pub dump_dir: PathBuf,
 
+    /// Sets the interval, in seconds, at which the indexes are checked for expired documents
+    /// to delete. The expired documents are never deleted automatically when set to 0.
+    #[clap(long, env = MEILI_DOCUMENTS_EXPIRY_CHECK_INTERVAL, default_value_t = default_documents_expiry_check_interval(), value_name = "EXPIRY_CHECK_INTERVAL_SEC")]
+    #[serde(default = "default_documents_expiry_check_interval")]
+    pub documents_expiry_check_interval: u64,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports six log levels, listed in order of increasing verbosity: OFF, ERROR, WARN, INFO, DEBUG, TRACE.
@@ -380,6 +388,7 @@ impl Opt {
             snapshot_dir,
             schedule_snapshot,
             dump_dir,
+            documents_expiry_check_interval,
             log_level,
             indexer_options,
             import_snapshot: _,
@@ -429,6 +438,10 @@ impl Opt {
         }
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
+        export_to_env_if_not_present(
+            MEILI_DOCUMENTS_EXPIRY_CHECK_INTERVAL,
+            documents_expiry_check_interval.to_string(),
+        );
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_ENABLE_METRICS,
@@ -734,6 +747,10 @@ fn default_dump_dir() -> PathBuf {
     PathBuf::from(DEFAULT_DUMP_DIR)
 }
 
+fn default_documents_expiry_check_interval() -> u64 {
+    DEFAULT_DOCUMENTS_EXPIRY_CHECK_INTERVAL_SEC
+}
+
 /// Indicates if a snapshot was scheduled, and if yes with which interval.
 #[derive(Debug, Default, Copy, Clone, Deserialize, Serialize)]
 pub enum ScheduleSnapshot {
@@ -11,12 +11,15 @@ use index_scheduler::IndexScheduler;
 use log::debug;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+#[cfg(feature = "columnar-payloads")]
+use meilisearch_types::document_formats::{read_arrow, read_parquet};
 use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::documents::CsvEmptyCells;
+use meilisearch_types::milli::update::{EditOperation, IndexDocumentsMethod, VersionCondition};
 use meilisearch_types::milli::DocumentId;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
@@ -24,7 +27,7 @@ use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tempfile::tempfile;
 use tokio::fs::File;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
@@ -40,7 +43,16 @@ use crate::routes::{PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT
 use crate::search::parse_filter;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    let mut content_types = vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+    ];
+    if cfg!(feature = "columnar-payloads") {
+        content_types.push("application/vnd.apache.parquet".to_string());
+        content_types.push("application/vnd.apache.arrow.stream".to_string());
+    }
+    content_types
 });
 
 /// Extracts the mime type from the content type and return
@@ -78,6 +90,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents_batch))),
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
     .service(
         web::resource("/{document_id}")
@@ -239,13 +252,19 @@ fn documents_by_query(
     Ok(HttpResponse::Ok().json(ret))
 }
 
-#[derive(Deserialize, Debug, Deserr)]
+#[derive(Debug, Deserr)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct UpdateDocumentsQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentCsvEmptyCells>)]
+    pub csv_empty_cells: Option<CsvEmptyCells>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentCsvNestedHeaders>)]
+    pub csv_nested_headers: Option<Param<bool>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentVersionCondition>)]
+    pub version_condition: Option<VersionCondition>,
 }
 
 fn from_char_csv_delimiter(
@@ -283,8 +302,11 @@ pub async fn replace_documents(
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.csv_empty_cells,
+        params.csv_nested_headers.map(|Param(nested)| nested),
         body,
         IndexDocumentsMethod::ReplaceDocuments,
+        params.version_condition,
         allow_index_creation,
     )
     .await?;
@@ -314,8 +336,11 @@ pub async fn update_documents(
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.csv_empty_cells,
+        params.csv_nested_headers.map(|Param(nested)| nested),
         body,
         IndexDocumentsMethod::UpdateDocuments,
+        params.version_condition,
         allow_index_creation,
     )
     .await?;
@@ -330,18 +355,40 @@ async fn document_addition(
     index_uid: IndexUid,
     primary_key: Option<String>,
     csv_delimiter: Option<u8>,
+    csv_empty_cells: Option<CsvEmptyCells>,
+    csv_nested_headers: Option<bool>,
     mut body: Payload,
     method: IndexDocumentsMethod,
+    version_condition: Option<VersionCondition>,
     allow_index_creation: bool,
 ) -> Result<SummarizedTaskView, MeilisearchHttpError> {
-    let format = match (
-        mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
-        csv_delimiter,
-    ) {
+    let mime_type = mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str()));
+    if let (Some((type_, subtype)), Some(_)) = (mime_type, csv_empty_cells) {
+        if (type_, subtype) != ("text", "csv") {
+            return Err(MeilisearchHttpError::CsvEmptyCellsWithWrongContentType(format!(
+                "{}/{}",
+                type_, subtype
+            )));
+        }
+    }
+    if let (Some((type_, subtype)), Some(_)) = (mime_type, csv_nested_headers) {
+        if (type_, subtype) != ("text", "csv") {
+            return Err(MeilisearchHttpError::CsvNestedHeadersWithWrongContentType(format!(
+                "{}/{}",
+                type_, subtype
+            )));
+        }
+    }
+
+    let format = match (mime_type, csv_delimiter) {
         (Some(("application", "json")), None) => PayloadType::Json,
         (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
         (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
         (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
+        #[cfg(feature = "columnar-payloads")]
+        (Some(("application", "vnd.apache.parquet")), None) => PayloadType::Parquet,
+        #[cfg(feature = "columnar-payloads")]
+        (Some(("application", "vnd.apache.arrow.stream")), None) => PayloadType::Arrow,
 
         (Some(("application", "json")), Some(_)) => {
             return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
@@ -353,6 +400,18 @@ async fn document_addition(
                 "application/x-ndjson",
             )))
         }
+        #[cfg(feature = "columnar-payloads")]
+        (Some(("application", "vnd.apache.parquet")), Some(_)) => {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/vnd.apache.parquet",
+            )))
+        }
+        #[cfg(feature = "columnar-payloads")]
+        (Some(("application", "vnd.apache.arrow.stream")), Some(_)) => {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/vnd.apache.arrow.stream",
+            )))
+        }
         (Some((type_, subtype)), _) => {
             return Err(MeilisearchHttpError::InvalidContentType(
                 format!("{}/{}", type_, subtype),
@@ -404,10 +463,18 @@ async fn document_addition(
     let documents_count = tokio::task::spawn_blocking(move || {
         let documents_count = match format {
             PayloadType::Json => read_json(&read_file, update_file.as_file_mut())?,
-            PayloadType::Csv { delimiter } => {
-                read_csv(&read_file, update_file.as_file_mut(), delimiter)?
-            }
+            PayloadType::Csv { delimiter } => read_csv(
+                &read_file,
+                update_file.as_file_mut(),
+                delimiter,
+                csv_empty_cells.unwrap_or_default(),
+                csv_nested_headers.unwrap_or_default(),
+            )?,
             PayloadType::Ndjson => read_ndjson(&read_file, update_file.as_file_mut())?,
+            #[cfg(feature = "columnar-payloads")]
+            PayloadType::Parquet => read_parquet(&read_file, update_file.as_file_mut())?,
+            #[cfg(feature = "columnar-payloads")]
+            PayloadType::Arrow => read_arrow(&read_file, update_file.as_file_mut())?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -441,6 +508,7 @@ async fn document_addition(
         documents_count,
         primary_key,
         allow_index_creation,
+        version_condition,
         index_uid: index_uid.to_string(),
     };
 
@@ -519,6 +587,86 @@ pub async fn delete_documents_by_filter(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentEdition {
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentId>)]
+    ids: Option<Vec<Value>>,
+    #[deserr(error = DeserrJsonError<InvalidDocumentEditionOperations>, missing_field_error = DeserrJsonError::missing_document_edition_operations)]
+    operations: Vec<Value>,
+}
+
+pub async fn edit_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<DocumentEdition, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let DocumentEdition { filter, ids, operations } = body.into_inner();
+
+    // we ensure the filter is well formed before enqueuing it
+    let has_filter = match &filter {
+        Some(filter) => crate::search::parse_filter(filter)
+            .map_err(|err| {
+                ResponseError::from_msg(
+                    ResponseError::from(err).message,
+                    Code::InvalidDocumentFilter,
+                )
+            })?
+            .is_some(),
+        None => false,
+    };
+    let documents_ids = ids.map(|ids| {
+        ids.iter().map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string())).collect()
+    });
+    let operations = operations
+        .into_iter()
+        .enumerate()
+        .map(|(i, operation)| {
+            serde_json::from_value::<EditOperation>(operation).map_err(|err| {
+                ResponseError::from_msg(
+                    format!("Invalid value at `.operations[{i}]`: {err}."),
+                    Code::InvalidDocumentEditionOperations,
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // editing all the documents of an index must be explicitly requested with a filter
+    if !has_filter && documents_ids.is_none() {
+        return Err(MeilisearchHttpError::MissingDocumentEditionSelection.into());
+    }
+
+    analytics.publish(
+        "Documents Edited".to_string(),
+        json!({
+            "per_filter": filter.is_some(),
+            "per_document_id": documents_ids.is_some(),
+            "operations": operations.len(),
+        }),
+        Some(&req),
+    );
+
+    let task = KindWithContent::DocumentEdition {
+        index_uid,
+        filter_expr: filter,
+        documents_ids,
+        operations,
+    };
+
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
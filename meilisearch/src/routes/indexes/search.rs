@@ -6,18 +6,20 @@ use log::debug;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{ResponseError, TooManyCropSnippetsError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::serde_cs::vec::CS;
 use serde_json::Value;
 
 use crate::analytics::{Analytics, SearchAggregator};
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::search::{
-    add_search_rules, perform_search, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    add_search_rules, parse_crop_snippets, perform_search, MatchingStrategy, SearchQuery,
+    SynonymsView, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_CROP_SNIPPETS,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_HITS_PER_GROUP,
     DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
@@ -42,18 +44,30 @@ pub struct SearchQueryGet {
     page: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchHitsPerPage>)]
     hits_per_page: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    search_after: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGroupBy>)]
+    group_by: Option<String>,
+    #[deserr(default = Param(DEFAULT_HITS_PER_GROUP()), error = DeserrQueryParamError<InvalidSearchHitsPerGroup>)]
+    hits_per_group: Param<usize>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToRetrieve>)]
     attributes_to_retrieve: Option<CS<String>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToCrop>)]
     attributes_to_crop: Option<CS<String>>,
     #[deserr(default = Param(DEFAULT_CROP_LENGTH()), error = DeserrQueryParamError<InvalidSearchCropLength>)]
     crop_length: Param<usize>,
+    #[deserr(default = Param(DEFAULT_CROP_SNIPPETS()), error = DeserrQueryParamError<InvalidSearchCropSnippets>, try_from(Param<usize>) = parse_crop_snippets_param -> TooManyCropSnippetsError)]
+    crop_snippets: Param<usize>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToHighlight>)]
     attributes_to_highlight: Option<CS<String>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFilter>)]
     filter: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSort>)]
     sort: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRandomSeed>)]
+    random_seed: Option<Param<u64>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDebug>)]
+    debug: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     show_matches_position: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
@@ -66,6 +80,10 @@ pub struct SearchQueryGet {
     crop_marker: String,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSynonyms>)]
+    synonyms: Option<Param<SynonymsView>>,
 }
 
 impl From<SearchQueryGet> for SearchQuery {
@@ -84,22 +102,38 @@ impl From<SearchQueryGet> for SearchQuery {
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
             hits_per_page: other.hits_per_page.as_deref().copied(),
+            search_after: other.search_after,
+            group_by: other.group_by,
+            hits_per_group: other.hits_per_group.0,
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
             crop_length: other.crop_length.0,
+            crop_snippets: other.crop_snippets.0,
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
+            boost: None,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            ranking_rules: None,
+            random_seed: other.random_seed.as_deref().copied(),
+            debug: other.debug.0,
             show_matches_position: other.show_matches_position.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            locales: other.locales.map(|o| o.into_iter().collect()),
+            synonyms: other.synonyms.map(|o| o.0),
         }
     }
 }
 
+fn parse_crop_snippets_param(
+    snippets: Param<usize>,
+) -> Result<Param<usize>, TooManyCropSnippetsError> {
+    parse_crop_snippets(snippets.0).map(Param)
+}
+
 // TODO: TAMO: split on :asc, and :desc, instead of doing some weird things
 
 /// Transform the sort query parameter into something that matches the post expected format.
@@ -142,6 +176,11 @@ pub async fn search_with_url_query(
         add_search_rules(&mut query, search_rules);
     }
 
+    // The trace of the ranking rules requires a dedicated action.
+    if query.debug && !index_scheduler.filters().allow_search_debug(&index_uid) {
+        return Err(AuthenticationError::MissingAction("search.debug").into());
+    }
+
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
@@ -174,6 +213,11 @@ pub async fn search_with_post(
         add_search_rules(&mut query, search_rules);
     }
 
+    // The trace of the ranking rules requires a dedicated action.
+    if query.debug && !index_scheduler.filters().allow_search_debug(&index_uid) {
+        return Err(AuthenticationError::MissingAction("search.debug").into());
+    }
+
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
@@ -187,6 +187,133 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/infix-searchable-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsInfixSearchableAttributes,
+    >,
+    infix_searchable_attributes,
+    "infixSearchableAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "InfixSearchableAttributes Updated".to_string(),
+            json!({
+                "infix_searchable_attributes": {
+                    "total": setting.as_ref().map(|infix| infix.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming-languages",
+    put,
+    std::collections::BTreeSet<meilisearch_types::milli::StemmingLanguage>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemmingLanguages,
+    >,
+    stemming_languages,
+    "stemmingLanguages",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<meilisearch_types::milli::StemmingLanguage>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "StemmingLanguages Updated".to_string(),
+            json!({
+                "stemming_languages": {
+                    "languages": setting.as_ref().map(|languages| languages.iter().collect::<Vec<_>>()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::locales::LocalizedAttributesRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::locales::LocalizedAttributesRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                    "locales": setting.as_ref().map(|rules| rules.iter().flat_map(|rule| rule.locales.iter()).collect::<std::collections::BTreeSet<_>>()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/document-schema",
+    put,
+    meilisearch_types::milli::DocumentSchema,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDocumentSchema,
+    >,
+    document_schema,
+    "documentSchema",
+    analytics,
+    |setting: &Option<meilisearch_types::milli::DocumentSchema>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DocumentSchema Updated".to_string(),
+            json!({
+                "document_schema": {
+                    "total": setting.as_ref().map(|schema| schema.fields.len()),
+                    "coerce_types": setting.as_ref().map(|schema| schema.coerce_types),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/ingest-pipeline",
+    put,
+    Vec<meilisearch_types::milli::IngestProcessor>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsIngestPipeline,
+    >,
+    ingest_pipeline,
+    "ingestPipeline",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::milli::IngestProcessor>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "IngestPipeline Updated".to_string(),
+            json!({
+                "ingest_pipeline": {
+                    "total": setting.as_ref().map(|processors| processors.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/displayed-attributes",
     put,
@@ -334,6 +461,132 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/synonym-sets",
+    patch,
+    std::collections::BTreeMap<
+        String,
+        meilisearch_types::milli::update::Setting<std::collections::BTreeMap<String, Vec<String>>>,
+    >,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSynonymSets,
+    >,
+    synonym_sets,
+    "synonymSets",
+    analytics,
+    |synonym_sets: &Option<std::collections::BTreeMap<String, meilisearch_types::milli::update::Setting<std::collections::BTreeMap<String, Vec<String>>>>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SynonymSets Updated".to_string(),
+            json!({
+                "synonym_sets": {
+                    "total": synonym_sets.as_ref().map(|sets| sets.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+/// Imports a single synonym set from a synonyms file in the Solr format,
+/// or removes it, without touching the other synonym sets of the index.
+pub mod synonym_set {
+    use std::collections::BTreeMap;
+
+    use actix_web::web::Data;
+    use actix_web::{web, HttpRequest, HttpResponse, Resource};
+    use index_scheduler::IndexScheduler;
+    use log::debug;
+    use meilisearch_types::error::ResponseError;
+    use meilisearch_types::index_uid::IndexUid;
+    use meilisearch_types::milli::update::Setting;
+    use meilisearch_types::settings::Settings;
+    use meilisearch_types::synonyms::parse_solr_synonyms;
+    use meilisearch_types::tasks::KindWithContent;
+    use serde_json::json;
+
+    use crate::analytics::Analytics;
+    use crate::extractors::authentication::policies::*;
+    use crate::extractors::authentication::GuardedData;
+    use crate::extractors::sequential_extractor::SeqHandler;
+    use crate::routes::SummarizedTaskView;
+
+    pub async fn update(
+        index_scheduler: GuardedData<
+            ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+            Data<IndexScheduler>,
+        >,
+        path: web::Path<(String, String)>,
+        body: String,
+        req: HttpRequest,
+        analytics: web::Data<dyn Analytics>,
+    ) -> Result<HttpResponse, ResponseError> {
+        let (index_uid, set_name) = path.into_inner();
+        let synonyms = parse_solr_synonyms(&body)?;
+
+        analytics.publish(
+            "SynonymSet Imported".to_string(),
+            json!({
+                "synonym_sets": {
+                    "total_synonyms": synonyms.len(),
+                },
+            }),
+            Some(&req),
+        );
+
+        register(index_scheduler, index_uid, set_name, Setting::Set(synonyms)).await
+    }
+
+    pub async fn delete(
+        index_scheduler: GuardedData<
+            ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+            Data<IndexScheduler>,
+        >,
+        path: web::Path<(String, String)>,
+    ) -> Result<HttpResponse, ResponseError> {
+        let (index_uid, set_name) = path.into_inner();
+        register(index_scheduler, index_uid, set_name, Setting::Reset).await
+    }
+
+    async fn register(
+        index_scheduler: GuardedData<
+            ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+            Data<IndexScheduler>,
+        >,
+        index_uid: String,
+        set_name: String,
+        synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    ) -> Result<HttpResponse, ResponseError> {
+        let index_uid = IndexUid::try_from(index_uid)?;
+
+        let new_settings = Settings {
+            synonym_sets: Setting::Set(BTreeMap::from([(set_name, synonyms)])),
+            ..Default::default()
+        };
+
+        let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+
+        let task = KindWithContent::SettingsUpdate {
+            index_uid: index_uid.to_string(),
+            new_settings: Box::new(new_settings),
+            is_deletion: false,
+            allow_index_creation,
+        };
+        let task: SummarizedTaskView =
+            tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+        debug!("returns: {:?}", task);
+        Ok(HttpResponse::Accepted().json(task))
+    }
+
+    pub fn resources() -> Resource {
+        Resource::new("/synonym-sets/{set_name}")
+            .route(web::put().to(SeqHandler(update)))
+            .route(web::delete().to(SeqHandler(delete)))
+    }
+}
+
 make_setting_route!(
     "/distinct-attribute",
     put,
@@ -358,6 +611,54 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/expiry-field",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsExpiryField,
+    >,
+    expiry_field,
+    "expiryField",
+    analytics,
+    |expiry_field: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "ExpiryField Updated".to_string(),
+            json!({
+                "expiry_field": {
+                    "set": expiry_field.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/version-field",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVersionField,
+    >,
+    version_field,
+    "versionField",
+    analytics,
+    |version_field: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "VersionField Updated".to_string(),
+            json!({
+                "version_field": {
+                    "set": version_field.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/ranking-rules",
     put,
@@ -382,6 +683,7 @@ make_setting_route!(
                     "sort_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Sort))),
                     "exactness_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Exactness))),
                     "values": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Asc(_) | meilisearch_types::settings::RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
+                    "functions": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Function(..))).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
                 }
             }),
             Some(req),
@@ -461,10 +763,19 @@ generate_configure!(
     distinct_attribute,
     stop_words,
     synonyms,
+    synonym_sets,
+    synonym_set,
     ranking_rules,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    infix_searchable_attributes,
+    stemming_languages,
+    localized_attributes,
+    document_schema,
+    ingest_pipeline,
+    expiry_field,
+    version_field
 );
 
 pub async fn update_all(
@@ -489,6 +800,7 @@ pub async fn update_all(
                 "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
                 "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
                 "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
+                "functions": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| matches!(s, RankingRuleView::Function(..))).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
             },
             "searchable_attributes": {
                 "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
@@ -558,6 +870,31 @@ pub async fn update_all(
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
+            "synonym_sets": {
+                "total": new_settings.synonym_sets.as_ref().set().map(|sets| sets.len()),
+            },
+            "infix_searchable_attributes": {
+                "total": new_settings.infix_searchable_attributes.as_ref().set().map(|infix| infix.len()),
+            },
+            "stemming_languages": {
+                "languages": new_settings.stemming_languages.as_ref().set().map(|languages| languages.iter().collect::<Vec<_>>()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
+            "document_schema": {
+                "total": new_settings.document_schema.as_ref().set().map(|schema| schema.fields.len()),
+                "coerce_types": new_settings.document_schema.as_ref().set().map(|schema| schema.coerce_types),
+            },
+            "ingest_pipeline": {
+                "total": new_settings.ingest_pipeline.as_ref().set().map(|processors| processors.len()),
+            },
+            "expiry_field": {
+                "set": new_settings.expiry_field.as_ref().set().is_some(),
+            },
+            "version_field": {
+                "set": new_settings.version_field.as_ref().set().is_some(),
+            },
         }),
         Some(&req),
     );
@@ -0,0 +1,54 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{add_similar_search_rules, perform_similar, SimilarQuery};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(similar_with_post))));
+}
+
+pub async fn similar_with_post(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SimilarQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!("similar called with params: {:?}", query);
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_similar_search_rules(&mut query, search_rules);
+    }
+
+    analytics.publish(
+        "Similar POST".to_string(),
+        json!({
+            "filtered": query.filter.is_some(),
+            "limit": query.limit,
+            "offset": query.offset,
+        }),
+        Some(&req),
+    );
+
+    let index = index_scheduler.index(&index_uid)?;
+    let similar_result =
+        tokio::task::spawn_blocking(move || perform_similar(&index, query)).await??;
+
+    debug!("returns: {:?}", similar_result);
+    Ok(HttpResponse::Ok().json(similar_result))
+}
@@ -63,6 +63,11 @@ pub async fn multi_search_with_post(
                 {
                     add_search_rules(&mut query, search_rules);
                 }
+                // The trace of the ranking rules requires a dedicated action
+                if query.debug && !index_scheduler.filters().allow_search_debug(&index_uid) {
+                    return Err(AuthenticationError::MissingAction("search.debug"))
+                        .with_index(query_index);
+                }
 
                 let index = index_scheduler
                     .index(&index_uid)
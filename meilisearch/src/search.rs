@@ -1,20 +1,26 @@
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::str::FromStr;
 use std::time::Instant;
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, ErrorKind, IntoValue, ValueKind, ValuePointerRef};
 use either::Either;
 use meilisearch_auth::IndexSearchRules;
+use meilisearch_types::deserr::query_params::FromQueryParameter;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError, TooManyCropSnippetsError};
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::locales::Locale;
+use meilisearch_types::settings::{RankingRuleView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::heed::RoTxn;
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    Boost, Criterion, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds,
+    MatcherBuilder, SearchTrace, Similar, SortCriterion, SortError, SynonymsSelection,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
@@ -28,10 +34,22 @@ pub const DEFAULT_SEARCH_OFFSET: fn() -> usize = || 0;
 pub const DEFAULT_SEARCH_LIMIT: fn() -> usize = || 20;
 pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
+pub const DEFAULT_CROP_SNIPPETS: fn() -> usize = || 1;
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
+pub const DEFAULT_HITS_PER_GROUP: fn() -> usize = || 1;
+/// The maximum number of crop windows a search can return for each cropped attribute.
+pub const MAX_CROP_SNIPPETS: usize = 10;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Deserr)]
+pub fn parse_crop_snippets(snippets: usize) -> Result<usize, TooManyCropSnippetsError> {
+    if snippets > MAX_CROP_SNIPPETS {
+        Err(TooManyCropSnippetsError { snippets, max: MAX_CROP_SNIPPETS })
+    } else {
+        Ok(snippets)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
@@ -44,20 +62,36 @@ pub struct SearchQuery {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToCrop>)]
     pub attributes_to_crop: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropLength>, default = DEFAULT_CROP_LENGTH())]
     pub crop_length: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropSnippets>, default = DEFAULT_CROP_SNIPPETS(), try_from(usize) = parse_crop_snippets -> TooManyCropSnippetsError)]
+    pub crop_snippets: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToHighlight>)]
     pub attributes_to_highlight: Option<HashSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<Vec<SearchBoost>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRandomSeed>)]
+    pub random_seed: Option<u64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDebug>, default)]
+    pub debug: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -68,6 +102,10 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSynonyms>)]
+    pub synonyms: Option<SynonymsView>,
 }
 
 impl SearchQuery {
@@ -80,7 +118,7 @@ impl SearchQuery {
 // This struct contains the fields of `SearchQuery` inline.
 // This is because neither deserr nor serde support `flatten` when using `deny_unknown_fields.
 // The `From<SearchQueryWithIndex>` implementation ensures both structs remain up to date.
-#[derive(Debug, Clone, PartialEq, Eq, Deserr)]
+#[derive(Debug, Clone, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQueryWithIndex {
     #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
@@ -95,20 +133,36 @@ pub struct SearchQueryWithIndex {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToCrop>)]
     pub attributes_to_crop: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropLength>, default = DEFAULT_CROP_LENGTH())]
     pub crop_length: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropSnippets>, default = DEFAULT_CROP_SNIPPETS(), try_from(usize) = parse_crop_snippets -> TooManyCropSnippetsError)]
+    pub crop_snippets: usize,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToHighlight>)]
     pub attributes_to_highlight: Option<HashSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<Vec<SearchBoost>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRandomSeed>)]
+    pub random_seed: Option<u64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDebug>, default)]
+    pub debug: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -119,6 +173,18 @@ pub struct SearchQueryWithIndex {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSynonyms>)]
+    pub synonyms: Option<SynonymsView>,
+}
+
+/// A filter promoting the documents matching it by the given weight.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchBoost {
+    pub filter: Value,
+    pub weight: f64,
 }
 
 impl SearchQueryWithIndex {
@@ -130,18 +196,28 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
+            group_by,
+            hits_per_group,
             attributes_to_retrieve,
             attributes_to_crop,
             crop_length,
+            crop_snippets,
             attributes_to_highlight,
             show_matches_position,
             filter,
+            boost,
             sort,
+            ranking_rules,
+            random_seed,
+            debug,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            locales,
+            synonyms,
         } = self;
         (
             index_uid,
@@ -151,18 +227,28 @@ impl SearchQueryWithIndex {
                 limit,
                 page,
                 hits_per_page,
+                search_after,
+                group_by,
+                hits_per_group,
                 attributes_to_retrieve,
                 attributes_to_crop,
                 crop_length,
+                crop_snippets,
                 attributes_to_highlight,
                 show_matches_position,
                 filter,
+                boost,
                 sort,
+                ranking_rules,
+                random_seed,
+                debug,
                 facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
+                locales,
+                synonyms,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -170,6 +256,21 @@ impl SearchQueryWithIndex {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SimilarQuery {
+    #[deserr(error = DeserrJsonError<InvalidSimilarId>, missing_field_error = DeserrJsonError::missing_similar_id)]
+    pub id: Value,
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSimilarOffset>)]
+    pub offset: usize,
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSimilarLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarAttributesToRetrieve>)]
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum MatchingStrategy {
@@ -194,6 +295,63 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+/// Either enables or disables all the synonyms, or only enables the given synonym sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SynonymsView {
+    Enabled(bool),
+    Sets(Vec<String>),
+}
+
+impl<E: DeserializeError> Deserr<E> for SynonymsView {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Boolean(enabled) => Ok(SynonymsView::Enabled(enabled)),
+            deserr::Value::Sequence(_) => {
+                Vec::<String>::deserialize_from_value(value, location).map(SynonymsView::Sets)
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::Boolean, ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl FromQueryParameter for SynonymsView {
+    type Err = Infallible;
+
+    fn from_query_param(p: &str) -> Result<Self, Self::Err> {
+        match p {
+            "true" => Ok(SynonymsView::Enabled(true)),
+            "false" => Ok(SynonymsView::Enabled(false)),
+            sets => Ok(SynonymsView::Sets(
+                sets.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl From<SynonymsView> for SynonymsSelection {
+    fn from(other: SynonymsView) -> Self {
+        match other {
+            SynonymsView::Enabled(true) => Self::All,
+            SynonymsView::Enabled(false) => Self::None,
+            SynonymsView::Sets(sets) => Self::Sets(sets),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct SearchHit {
     #[serde(flatten)]
@@ -212,10 +370,27 @@ pub struct SearchResult {
     pub processing_time_ms: u128,
     #[serde(flatten)]
     pub hits_info: HitsInfo,
+    /// The position of the last hit, only returned when more hits may follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// The groups of hits, when the hits are grouped by the values of a field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<SearchGroup>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_distribution: Option<BTreeMap<String, BTreeMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    /// The trace of the ranking rules, only returned when the search is debugged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug: Option<SearchTrace>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchGroup {
+    pub value: Value,
+    pub hits: Vec<SearchHit>,
+    pub total_hits: u64,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -226,6 +401,16 @@ pub struct SearchResultWithIndex {
     pub result: SearchResult,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarResult {
+    pub hits: Vec<SearchHit>,
+    pub id: String,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum HitsInfo {
@@ -243,7 +428,16 @@ pub struct FacetStats {
 
 /// Incorporate search rules in search query
 pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
-    query.filter = match (query.filter.take(), rules.filter) {
+    query.filter = fuse_filters(query.filter.take(), rules.filter);
+}
+
+/// Incorporate search rules in similar query
+pub fn add_similar_search_rules(query: &mut SimilarQuery, rules: IndexSearchRules) {
+    query.filter = fuse_filters(query.filter.take(), rules.filter);
+}
+
+fn fuse_filters(filter: Option<Value>, rules_filter: Option<Value>) -> Option<Value> {
+    match (filter, rules_filter) {
         (None, rules_filter) => rules_filter,
         (filter, None) => filter,
         (Some(filter), Some(rules_filter)) => {
@@ -303,59 +497,79 @@ pub fn perform_search(
     search.offset(offset);
     search.limit(limit);
 
+    // The offset and the limit are relative to the cursor,
+    // which allows to paginate further than the hard limit.
+    if let Some(ref search_after) = query.search_after {
+        search.search_after(search_after.parse()?);
+    }
+
     if let Some(ref filter) = query.filter {
         if let Some(facets) = parse_filter(filter)? {
             search.filter(facets);
         }
     }
 
+    if let Some(ref boosts) = query.boost {
+        let mut search_boosts = Vec::with_capacity(boosts.len());
+        for SearchBoost { filter, weight } in boosts {
+            let filter = parse_filter(filter).map_err(|err| {
+                milli::Error::from(milli::UserError::InvalidBoost(err.to_string()))
+            })?;
+            if let Some(filter) = filter {
+                search_boosts.push(Boost { filter, weight: *weight });
+            }
+        }
+        search.boosts(search_boosts);
+    }
+
+    // The offset and the limit then apply to the groups.
+    if let Some(ref group_by) = query.group_by {
+        search.group_by(group_by.clone(), query.hits_per_group);
+    }
+
     if let Some(ref sort) = query.sort {
-        let sort = match sort.iter().map(|s| AscDesc::from_str(s)).collect() {
+        let sort = match sort.iter().map(|s| SortCriterion::from_str(s)).collect() {
             Ok(sorts) => sorts,
             Err(asc_desc_error) => {
                 return Err(milli::Error::from(SortError::from(asc_desc_error)).into())
             }
         };
 
-        search.sort_criteria(sort);
+        search.sort_criteria_with_options(sort);
     }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, .. } = search.execute()?;
+    if let Some(ref ranking_rules) = query.ranking_rules {
+        search.ranking_rules(ranking_rules.iter().cloned().map(Criterion::from).collect());
+    }
 
-    let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+    if let Some(seed) = query.random_seed {
+        search.random_seed(seed);
+    }
 
-    let displayed_ids = index
-        .displayed_fields_ids(&rtxn)?
-        .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
-        .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.iter().copied().map(Language::from).collect());
+    }
 
-    let fids = |attrs: &BTreeSet<String>| {
-        let mut ids = BTreeSet::new();
-        for attr in attrs {
-            if attr == "*" {
-                ids = displayed_ids.clone();
-                break;
-            }
+    if let Some(ref synonyms) = query.synonyms {
+        search.synonyms(synonyms.clone().into());
+    }
 
-            if let Some(id) = fields_ids_map.id(attr) {
-                ids.insert(id);
-            }
-        }
-        ids
-    };
+    search.debug(query.debug);
 
-    // The attributes to retrieve are the ones explicitly marked as to retrieve (all by default),
-    // but these attributes must be also be present
-    // - in the fields_ids_map
-    // - in the the displayed attributes
-    let to_retrieve_ids: BTreeSet<_> = query
-        .attributes_to_retrieve
-        .as_ref()
-        .map(fids)
-        .unwrap_or_else(|| displayed_ids.clone())
-        .intersection(&displayed_ids)
-        .cloned()
-        .collect();
+    let milli::SearchResult { documents_ids, matching_words, candidates, cursor, groups, trace } =
+        search.execute()?;
+    // A full page means that there may be more documents to return after the last one.
+    let cursor =
+        cursor.filter(|_| limit != 0 && documents_ids.len() == limit).map(|c| c.to_string());
+
+    let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+
+    let (displayed_ids, to_retrieve_ids) = compute_retrieved_ids(
+        index,
+        &rtxn,
+        &fields_ids_map,
+        query.attributes_to_retrieve.as_ref(),
+    )?;
 
     let attr_to_highlight = query.attributes_to_highlight.unwrap_or_default();
 
@@ -385,6 +599,7 @@ pub fn perform_search(
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
     formatter_builder.crop_marker(query.crop_marker);
+    formatter_builder.crop_snippets(query.crop_snippets);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
 
@@ -420,6 +635,19 @@ pub fn perform_search(
         documents.push(hit);
     }
 
+    // The documents of the groups follow each other, in the order of the groups.
+    let groups = groups.map(|groups| {
+        let mut hits = std::mem::take(&mut documents).into_iter();
+        groups
+            .into_iter()
+            .map(|group| SearchGroup {
+                value: group.value,
+                hits: hits.by_ref().take(group.documents_ids.len()).collect(),
+                total_hits: group.total,
+            })
+            .collect()
+    });
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -465,14 +693,143 @@ pub fn perform_search(
     let result = SearchResult {
         hits: documents,
         hits_info,
+        cursor,
+        groups,
         query: query.q.clone().unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        debug: trace,
     };
     Ok(result)
 }
 
+pub fn perform_similar(index: &Index, query: SimilarQuery) -> Result<SimilarResult, ResponseError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let id = match &query.id {
+        Value::String(id) => id.clone(),
+        Value::Number(id) if id.is_u64() || id.is_i64() => id.to_string(),
+        id => {
+            return Err(ResponseError::from_msg(
+                format!(
+                    "Invalid value at `.id`: `{id}` is not a valid document id. A document id must be an integer or a string."
+                ),
+                Code::InvalidSimilarId,
+            ))
+        }
+    };
+
+    let internal_id = index
+        .external_documents_ids(&rtxn)?
+        .get(id.as_bytes())
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(id.clone()))?;
+
+    let mut similar = Similar::new(internal_id, &rtxn, index);
+
+    let max_total_hits = index
+        .pagination_max_total_hits(&rtxn)
+        .map_err(milli::Error::from)?
+        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+
+    // Make sure that a user can't get more documents than the hard limit,
+    // we align that on the offset too.
+    let offset = min(query.offset, max_total_hits);
+    let limit = min(query.limit, max_total_hits.saturating_sub(offset));
+
+    similar.offset(offset);
+    similar.limit(limit);
+
+    if let Some(ref filter) = query.filter {
+        let filter = parse_filter(filter)
+            .map_err(|err| ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter))?;
+        if let Some(facets) = filter {
+            similar.filter(facets);
+        }
+    }
+
+    let milli::SearchResult { documents_ids, candidates, .. } =
+        similar.execute().map_err(|err| match err {
+            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
+            }
+            e => e.into(),
+        })?;
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let (displayed_ids, to_retrieve_ids) = compute_retrieved_ids(
+        index,
+        &rtxn,
+        &fields_ids_map,
+        query.attributes_to_retrieve.as_ref(),
+    )?;
+
+    let mut hits = Vec::new();
+    for (_id, obkv) in index.documents(&rtxn, documents_ids)? {
+        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+
+        // select the attributes to retrieve
+        let attributes_to_retrieve = to_retrieve_ids
+            .iter()
+            .map(|&fid| fields_ids_map.name(fid).expect("Missing field name"));
+        let document =
+            permissive_json_pointer::select_values(&displayed_document, attributes_to_retrieve);
+
+        hits.push(SearchHit { document, formatted: Document::new(), matches_position: None });
+    }
+
+    let estimated_total_hits = min(candidates.len() as usize, max_total_hits);
+
+    Ok(SimilarResult {
+        hits,
+        id,
+        processing_time_ms: before_search.elapsed().as_millis(),
+        hits_info: HitsInfo::OffsetLimit { limit: query.limit, offset, estimated_total_hits },
+    })
+}
+
+/// Returns the displayed fields ids along with the ids of the fields to retrieve.
+fn compute_retrieved_ids(
+    index: &Index,
+    rtxn: &RoTxn,
+    fields_ids_map: &FieldsIdsMap,
+    attributes_to_retrieve: Option<&BTreeSet<String>>,
+) -> Result<(BTreeSet<FieldId>, BTreeSet<FieldId>), MeilisearchHttpError> {
+    let displayed_ids = index
+        .displayed_fields_ids(rtxn)?
+        .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
+        .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+
+    let fids = |attrs: &BTreeSet<String>| {
+        let mut ids = BTreeSet::new();
+        for attr in attrs {
+            if attr == "*" {
+                ids = displayed_ids.clone();
+                break;
+            }
+
+            if let Some(id) = fields_ids_map.id(attr) {
+                ids.insert(id);
+            }
+        }
+        ids
+    };
+
+    // The attributes to retrieve are the ones explicitly marked as to retrieve (all by default),
+    // but these attributes must be also be present
+    // - in the fields_ids_map
+    // - in the the displayed attributes
+    let to_retrieve_ids: BTreeSet<_> = attributes_to_retrieve
+        .map(fids)
+        .unwrap_or_else(|| displayed_ids.clone())
+        .intersection(&displayed_ids)
+        .cloned()
+        .collect();
+
+    Ok((displayed_ids, to_retrieve_ids))
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
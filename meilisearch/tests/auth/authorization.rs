@@ -21,6 +21,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete-batch") =>         hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete") =>               hashset!{"documents.delete", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/edit") =>                 hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/tasks") =>                                           hashset!{"tasks.get", "tasks.*", "*"},
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
@@ -1026,3 +1027,53 @@ async fn error_creating_index_without_index() {
     let (response, code) = index.create(None).await;
     assert_eq!(403, code, "{:?}", &response);
 }
+
+#[actix_rt::test]
+async fn error_search_debug_without_action() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key(MASTER_KEY).await;
+
+    let index = server.index("products");
+    let documents = json!([{ "id": 1, "content": "foo" }]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(202, code, "{:?}", &response);
+    let task_id = response["taskUid"].as_u64().unwrap();
+    index.wait_task(task_id).await;
+
+    for (actions, expected_code) in
+        [(json!(["search"]), 403), (json!(["search", "search.debug"]), 200), (json!(["*"]), 200)]
+    {
+        server.use_api_key(MASTER_KEY);
+        let content = json!({
+            "indexes": ["products"],
+            "actions": actions,
+            "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+        });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+
+        // use created key.
+        let key = response["key"].as_str().unwrap();
+        server.use_api_key(key);
+
+        let index = server.index("products");
+        let (response, code) = index.search_post(json!({ "q": "foo" })).await;
+        assert_eq!(200, code, "{:?}", &response);
+
+        let (response, code) = index.search_post(json!({ "q": "foo", "debug": true })).await;
+        assert_eq!(expected_code, code, "{:?}", &response);
+        if expected_code == 403 {
+            assert_eq!(
+                response,
+                json!({
+                    "message": "The provided API key is not allowed to perform the `search.debug` action.",
+                    "code": "missing_action",
+                    "type": "auth",
+                    "link": "https://docs.meilisearch.com/errors#missing_action"
+                })
+            );
+        } else {
+            assert_eq!(response["debug"]["universeLen"], json!(1));
+        }
+    }
+}
@@ -418,6 +418,90 @@ async fn add_csv_document_with_custom_delimiter() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_csv_document_with_typed_columns() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = r#"#id:number;name;tags:array;owner.name;details:json
+0;jean;dog, big;Kevin;{"age": 4}
+1;;;Tamo;"#;
+
+    let (response, code) = index
+        .raw_update_documents(
+            document,
+            Some("text/csv"),
+            "?csvDelimiter=;&csvEmptyCells=empty&csvNestedHeaders=true",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "#id": 0,
+          "name": "jean",
+          "tags": [
+            "dog",
+            "big"
+          ],
+          "owner": {
+            "name": "Kevin"
+          },
+          "details": {
+            "age": 4
+          }
+        },
+        {
+          "#id": 1,
+          "name": "",
+          "tags": [],
+          "owner": {
+            "name": "Tamo"
+          },
+          "details": null
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_csv_document_with_dotted_headers() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = r#"#id,owner.name
+0,Kevin"#;
+
+    let (response, code) = index.raw_update_documents(document, Some("text/csv"), "").await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "#id": "0",
+          "owner.name": "Kevin"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 1
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types_error() {
     let server = Server::new().await;
@@ -453,6 +537,7 @@ async fn add_csv_document_with_types_error() {
 }
 
 /// any other content-type is must be refused
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn error_add_documents_test_bad_content_types() {
     let document = json!([
@@ -479,7 +564,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -500,7 +585,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -509,6 +594,7 @@ async fn error_add_documents_test_bad_content_types() {
 }
 
 /// missing content-type must be refused
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn error_add_documents_test_no_content_type() {
     let document = json!([
@@ -534,7 +620,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -554,7 +640,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -1762,6 +1848,136 @@ async fn add_documents_invalid_geo_field() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_documents_invalid_document_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("id")).await;
+    index
+        .update_settings(json!({
+            "documentSchema": {
+                "fields": {
+                    "title": { "type": "string", "required": true, "maxLength": 10 },
+                    "price": { "type": "number" }
+                },
+                "coerceTypes": true
+            }
+        }))
+        .await;
+
+    let documents = json!([
+        { "id": 1, "title": "Carol", "price": "12.50" },
+        { "id": 2, "title": "Alice in Wonderland", "price": 10 }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(2).await;
+    let (response, code) = index.get_task(2).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }),
+        @r###"
+    {
+      "uid": 2,
+      "indexUid": "test",
+      "status": "failed",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 2,
+        "indexedDocuments": 0
+      },
+      "error": {
+        "message": "Document `2` does not respect the document schema: the field `title` contains `Alice in Wonderland` which is longer than 10 characters.",
+        "code": "invalid_document_schema",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_document_schema"
+      },
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // The declared types are coerced when possible.
+    let documents = json!([{ "id": 1, "title": "Carol", "price": "12.50" }]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(3).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "title": "Carol",
+      "price": 12.5
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_ingest_pipeline() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("id")).await;
+    index
+        .update_settings(json!({
+            "ingestPipeline": [
+                { "type": "concatenate", "fields": ["firstName", "lastName"], "target": "fullName" },
+                { "type": "split", "field": "categoryPath", "separator": ">" },
+                { "type": "dateParts", "field": "releasedAt", "target": "released" },
+                { "type": "default", "field": "stock", "value": 0 }
+            ]
+        }))
+        .await;
+
+    let documents = json!([{
+        "id": 1,
+        "firstName": "Kevin",
+        "lastName": "Durant",
+        "categoryPath": "Sports > Shoes",
+        "releasedAt": "2022-10-07"
+    }]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "firstName": "Kevin",
+      "lastName": "Durant",
+      "categoryPath": [
+        "Sports",
+        "Shoes"
+      ],
+      "releasedAt": "2022-10-07",
+      "fullName": "Kevin Durant",
+      "released": {
+        "year": 2022,
+        "month": 10,
+        "day": 7,
+        "hour": 0,
+        "minute": 0,
+        "weekday": "friday"
+      },
+      "stock": 0
+    }
+    "###);
+
+    let documents = json!([{ "id": 2, "firstName": "Kevina", "releasedAt": "tomorrow" }]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(3).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Could not process the document `2` with the ingest pipeline: cannot extract the date parts of the `releasedAt` field: `\"tomorrow\"`.",
+      "code": "invalid_document_ingest_pipeline",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_ingest_pipeline"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn error_add_documents_payload_size() {
     let server = Server::new().await;
@@ -1982,3 +2198,138 @@ async fn batch_several_documents_addition() {
     assert_eq!(code, 200, "failed with `{}`", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 120);
 }
+
+#[actix_rt::test]
+async fn add_documents_with_composite_primary_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("tenant_id,sku")).await;
+
+    let documents = json!([
+        { "tenant_id": "acme", "sku": "KD-35", "title": "Acme shoe" },
+        { "tenant_id": "globex", "sku": "KD-35", "title": "Globex shoe" }
+    ]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(1).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.service.get("/indexes/test/documents/acme:KD-35").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "tenant_id": "acme",
+      "sku": "KD-35",
+      "title": "Acme shoe"
+    }
+    "###);
+
+    index.delete_batch_raw(json!(["globex:KD-35"])).await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["details"]["deletedDocuments"], @"1");
+
+    let (response, code) = index.service.get("/indexes/test/documents/globex:KD-35").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""document_not_found""###);
+
+    // A document missing one of the fields of the primary key is rejected.
+    index.add_documents(json!([{ "tenant_id": "acme", "title": "Unknown shoe" }]), None).await;
+    let response = index.wait_task(3).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""missing_document_id""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_version_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("id")).await;
+    index.update_settings(json!({ "versionField": "version" })).await;
+
+    index.add_documents(json!([{ "id": 1, "name": "kevin", "version": 2 }]), None).await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    // The document older than the indexed one is skipped.
+    let documents = json!([
+        { "id": 1, "name": "old kevin", "version": 1 },
+        { "id": 2, "name": "kevina", "version": 1 }
+    ]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(3).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2,
+      "indexedDocuments": 1,
+      "skippedDocuments": 1
+    }
+    "###);
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "kevin",
+      "version": 2
+    }
+    "###);
+
+    index.add_documents(json!([{ "id": 1, "name": "kevin", "version": "two" }]), None).await;
+    let response = index.wait_task(4).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_version""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_version_condition() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("id")).await;
+
+    let documents = r#"[{ "id": 1, "name": "kevin" }]"#;
+    let (_, code) = index
+        .raw_add_documents(documents, Some("application/json"), "?versionCondition=ifVersion")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(1).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_version_condition""###);
+
+    index.update_settings(json!({ "versionField": "version" })).await;
+    let (_, code) = index
+        .raw_add_documents(documents, Some("application/json"), "?versionCondition=ifVersion")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(3).await;
+
+    // The document is only written when it sends the indexed version.
+    let documents = r#"[{ "id": 1, "name": "stale kevin", "version": 0 }]"#;
+    index
+        .raw_add_documents(documents, Some("application/json"), "?versionCondition=ifVersion")
+        .await;
+    let response = index.wait_task(4).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["skippedDocuments"], @"1");
+
+    let documents = r#"[{ "id": 1, "name": "new kevin", "version": 1 }]"#;
+    index
+        .raw_add_documents(documents, Some("application/json"), "?versionCondition=ifVersion")
+        .await;
+    index.wait_task(5).await;
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "name": "new kevin",
+      "version": 2
+    }
+    "###);
+
+    let (response, code) = index
+        .raw_add_documents(documents, Some("application/json"), "?versionCondition=ifOlder")
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_version_condition""###);
+}
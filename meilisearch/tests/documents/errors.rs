@@ -282,6 +282,8 @@ async fn update_documents_missing_payload() {
     "###);
 }
 
+// The columnar payloads, enabled by default, extend the accepted content types.
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn replace_documents_missing_content_type() {
     let server = Server::new().await;
@@ -291,7 +293,7 @@ async fn replace_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -303,7 +305,7 @@ async fn replace_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -311,6 +313,24 @@ async fn replace_documents_missing_content_type() {
     "###);
 }
 
+#[cfg(feature = "columnar-payloads")]
+#[actix_rt::test]
+async fn malformed_columnar_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.raw_add_documents("doggo", Some("application/vnd.apache.parquet"), "").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+
+    let (response, code) =
+        index.raw_add_documents("doggo", Some("application/vnd.apache.arrow.stream"), "").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+}
+
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn update_documents_missing_content_type() {
     let server = Server::new().await;
@@ -320,7 +340,7 @@ async fn update_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -332,7 +352,7 @@ async fn update_documents_missing_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -340,6 +360,7 @@ async fn update_documents_missing_content_type() {
     "###);
 }
 
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn replace_documents_bad_content_type() {
     let server = Server::new().await;
@@ -349,7 +370,7 @@ async fn replace_documents_bad_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -357,6 +378,7 @@ async fn replace_documents_bad_content_type() {
     "###);
 }
 
+#[cfg(feature = "columnar-payloads")]
 #[actix_rt::test]
 async fn update_documents_bad_content_type() {
     let server = Server::new().await;
@@ -366,7 +388,7 @@ async fn update_documents_bad_content_type() {
     snapshot!(code, @"415 Unsupported Media Type");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `doggo` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -524,6 +546,66 @@ async fn update_documents_csv_delimiter_with_bad_content_type() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_documents_bad_csv_empty_cells() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.raw_add_documents("", Some("text/csv"), "?csvEmptyCells=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `csvEmptyCells`: expected one of `null`, `empty`",
+      "code": "invalid_document_csv_empty_cells",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_csv_empty_cells"
+    }
+    "###);
+
+    let (response, code) =
+        index.raw_update_documents("", Some("application/json"), "?csvEmptyCells=empty").await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The Content-Type `application/json` does not support the use of the csv empty cells parameter. The csv empty cells parameter can only be used with the Content-Type `text/csv`.",
+      "code": "invalid_content_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_content_type"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_bad_csv_nested_headers() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.raw_add_documents("", Some("text/csv"), "?csvNestedHeaders=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `csvNestedHeaders`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_document_csv_nested_headers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_csv_nested_headers"
+    }
+    "###);
+
+    let (response, code) =
+        index.raw_update_documents("", Some("application/json"), "?csvNestedHeaders=true").await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The Content-Type `application/json` does not support the use of the csv nested headers parameter. The csv nested headers parameter can only be used with the Content-Type `text/csv`.",
+      "code": "invalid_content_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_content_type"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn delete_document_by_filter() {
     let server = Server::new().await;
@@ -688,6 +770,85 @@ async fn delete_document_by_filter() {
     "###);
 }
 
+#[actix_rt::test]
+async fn edit_documents() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    // do not send any operations
+    let (response, code) = index.edit_documents(json!({ "filter": "doggo = bernese" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `operations`",
+      "code": "missing_document_edition_operations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_document_edition_operations"
+    }
+    "###);
+
+    // send an unknown operation
+    let (response, code) = index
+        .edit_documents(json!({ "operations": [{ "op": "multiply", "field": "views" }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.operations[0]`: unknown variant `multiply`, expected one of `set`, `unset`, `increment`, `push`, `remove`.",
+      "code": "invalid_document_edition_operations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition_operations"
+    }
+    "###);
+
+    // send bad filter
+    let (response, code) = index
+        .edit_documents(
+            json!({ "filter": "hello", "operations": [{ "op": "unset", "field": "views" }] }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `hello`.\n1:6 hello",
+      "code": "invalid_document_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_filter"
+    }
+    "###);
+
+    // select neither a filter nor ids
+    let (response, code) =
+        index.edit_documents(json!({ "operations": [{ "op": "unset", "field": "views" }] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A document edition must select the documents with a `filter` or `ids`.",
+      "code": "missing_document_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_document_filter"
+    }
+    "###);
+
+    // the operation cannot be applied on the document
+    index.add_documents(json!([{ "id": 1, "name": "kevin" }]), Some("id")).await;
+    let (response, code) = index
+        .edit_documents(
+            json!({ "ids": [1], "operations": [{ "op": "increment", "field": "name" }] }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Could not edit the document `1`: cannot increment the non-numeric `name` field: `\"kevin\"`.",
+      "code": "invalid_document_edition_operations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition_operations"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn fetch_document_by_filter() {
     let server = Server::new().await;
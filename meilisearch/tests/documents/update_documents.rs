@@ -1,3 +1,4 @@
+use meili_snap::{json_string, snapshot};
 use serde_json::json;
 
 use crate::common::encoder::Encoder;
@@ -196,3 +197,101 @@ async fn error_update_documents_missing_document_id() {
         "https://docs.meilisearch.com/errors#missing_document_id"
     );
 }
+
+#[actix_rt::test]
+async fn edit_documents_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "color": "red", "views": 1 },
+                { "id": 1, "color": "blue", "views": 3, "tags": ["old"] },
+                { "id": 2, "color": "blue" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents(json!({
+            "filter": "color = blue",
+            "operations": [
+                { "op": "increment", "field": "views", "by": 2 },
+                { "op": "push", "field": "tags", "value": "new" },
+                { "op": "remove", "field": "tags", "value": "old" },
+            ],
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
+    {
+      "taskUid": 2,
+      "indexUid": "doggo",
+      "status": "enqueued",
+      "type": "documentEdition",
+      "enqueuedAt": "[date]"
+    }
+    "###);
+
+    let response = index.wait_task(2).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 2,
+      "indexUid": "doggo",
+      "status": "succeeded",
+      "type": "documentEdition",
+      "canceledBy": null,
+      "details": {
+        "editedDocuments": 2,
+        "originalFilter": "\"color = blue\""
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // the filter and the ids are intersected
+    let (response, code) = index
+        .edit_documents(json!({
+            "filter": "color = blue",
+            "ids": [0, 1],
+            "operations": [{ "op": "unset", "field": "tags" }],
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "editedDocuments": 1,
+      "originalFilter": "\"color = blue\""
+    }
+    "###);
+
+    let (document, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "color": "blue",
+      "views": 5
+    }
+    "###);
+    let (document, code) = index.get_document(2, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 2,
+      "color": "blue",
+      "views": 2,
+      "tags": [
+        "new"
+      ]
+    }
+    "###);
+}
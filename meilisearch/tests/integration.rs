@@ -6,6 +6,7 @@ mod dumps;
 mod index;
 mod search;
 mod settings;
+mod similar;
 mod snapshot;
 mod stats;
 mod swap_indexes;
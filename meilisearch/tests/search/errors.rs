@@ -167,6 +167,229 @@ async fn search_bad_hits_per_page() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_boost() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"boost": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.boost`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"boost": [{"filter": "doggo = 1"}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `weight` inside `.boost[0]`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{"filter": "doggo = 1", "weight": "doggo"}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.boost[0].weight`: expected a number, but found a string: `\"doggo\"`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{"filter": "doggo = 1", "weight": 1}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `doggo` is not filterable. This index does not have configured filterable attributes.\n1:6 doggo = 1",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{"filter": "doggo =", "weight": 1}]})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_boost");
+
+    let boosts: Vec<_> = (0..11).map(|_| json!({"filter": "doggo = 1", "weight": 1})).collect();
+    let (response, code) = index.search_post(json!({ "boost": boosts })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A search cannot have more than 10 boosts, but 11 boosts were given.",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"rankingRules": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rankingRules`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `doggo` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["doggo:asc"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `doggo` cannot be used in the ranking rules of the search as it is not filterable, sortable or used in the ranking rules of the index. This index does not have any attribute that can be used in the ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_random_seed() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"randomSeed": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.randomSeed`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_random_seed",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_random_seed"
+    }
+    "###);
+
+    let (response, code) = index.search_get("randomSeed=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `randomSeed`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_random_seed",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_random_seed"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_group_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"groupBy": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.groupBy`: expected a string, but found an array: `[\"doggo\"]`",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"groupBy": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `doggo` is not filterable and thus cannot be used to group the search results. This index does not have configured filterable attributes.",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"groupBy": "doggo", "hitsPerGroup": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.hitsPerGroup`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_hits_per_group",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hits_per_group"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_search_after() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"searchAfter": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchAfter`: expected a string, but found an array: `[\"doggo\"]`",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"searchAfter": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`doggo` is not a valid search cursor.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_get("searchAfter=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`doggo` is not a valid search cursor.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_attributes_to_crop() {
     let server = Server::new().await;
@@ -213,6 +436,84 @@ async fn search_bad_crop_length() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_crop_snippets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"cropSnippets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.cropSnippets`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_crop_snippets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_snippets"
+    }
+    "###);
+
+    let (response, code) = index.search_get("cropSnippets=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `cropSnippets`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_crop_snippets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_snippets"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"cropSnippets": 11})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.cropSnippets`: a search cannot return more than 10 crop snippets, but 11 were requested",
+      "code": "invalid_search_crop_snippets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_snippets"
+    }
+    "###);
+
+    let (response, code) = index.search_get("cropSnippets=11").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `cropSnippets`: a search cannot return more than 10 crop snippets, but 11 were requested",
+      "code": "invalid_search_crop_snippets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_crop_snippets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"locales": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.locales`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_get("locales=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `locales`: Unsupported locale `doggo`, expected an ISO 639-3 code such as `eng`, `fra` or `jpn`.",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_attributes_to_highlight() {
     let server = Server::new().await;
@@ -273,6 +574,32 @@ async fn search_bad_sort() {
     }
     "###);
     // Can't make the `sort` fail with a get search since it'll accept anything as a strings.
+
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"sort": ["doggo:asc:missing:middle"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid sort option in `doggo:asc:missing:middle`: the supported options are `missing:first`, `missing:last`, `collation:caseInsensitive` and `collation:natural`.",
+      "code": "invalid_search_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"sort": ["_geoPoint(1, 2):asc:missing:first"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The sort options can't be used to sort on the `_geo` field, found `_geoPoint(1, 2):asc:missing:first`.",
+      "code": "invalid_search_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+    }
+    "###);
 }
 
 #[actix_rt::test]
@@ -303,6 +630,34 @@ async fn search_bad_show_matches_position() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_debug() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"debug": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.debug`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_debug",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_debug"
+    }
+    "###);
+
+    let (response, code) = index.search_get("debug=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `debug`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_debug",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_debug"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_facets() {
     let server = Server::new().await;
@@ -963,3 +1318,45 @@ async fn sort_unset_ranking_rule() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_bad_synonyms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"synonyms": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.synonyms`: expected a boolean or an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_synonyms"
+    }
+    "###);
+
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"synonyms": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Synonym set `doggo` does not exist and thus cannot be used to expand the search query. This index does not have any synonym set.",
+      "code": "invalid_search_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_synonyms"
+    }
+    "###);
+
+    let (response, code) = index.search_get("synonyms=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Synonym set `doggo` does not exist and thus cannot be used to expand the search query. This index does not have any synonym set.",
+      "code": "invalid_search_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_synonyms"
+    }
+    "###);
+}
@@ -25,7 +25,7 @@ async fn formatted_contain_wildcard() {
                         "id": "852",
                         "cattos": "<em>pésti</em>",
                     },
-                    "_matchesPosition": {"cattos": [{"start": 0, "length": 5}]},
+                    "_matchesPosition": {"cattos": [{"start": 0, "length": 5, "queryTermIndex": 0, "kind": "exact"}]},
                 })
             );
         }
@@ -59,7 +59,7 @@ async fn formatted_contain_wildcard() {
                             "id": "852",
                             "cattos": "pésti",
                         },
-                        "_matchesPosition": {"cattos": [{"start": 0, "length": 5}]},
+                        "_matchesPosition": {"cattos": [{"start": 0, "length": 5, "queryTermIndex": 0, "kind": "exact"}]},
                     })
                 );
             }
@@ -172,7 +172,7 @@ async fn format_nested() {
                                 "name": "buddy",
                             },
                         ],
-                        "_matchesPosition": {"doggos.name": [{"start": 0, "length": 5}]},
+                        "_matchesPosition": {"doggos.name": [{"start": 0, "length": 5, "queryTermIndex": 0, "kind": "exact"}]},
                     })
                 );
             }
@@ -476,3 +476,93 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn format_crop_snippets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "void world void void void void void void void split the world void void" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(
+            json!({ "q": "split the world", "attributesToCrop": ["title"], "cropLength": 3, "cropSnippets": 2 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(
+                    response["hits"][0]["_formatted"]["title"],
+                    json!("void world void…split the world…")
+                );
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "q": "split the world", "attributesToCrop": ["title"], "cropLength": 3 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"][0]["_formatted"]["title"], json!("…split the world…"));
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn matches_position_kind() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "quack brow", "showMatchesPosition": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(
+                response["hits"][0]["_matchesPosition"],
+                json!({
+                    "title": [
+                        { "start": 4, "length": 5, "queryTermIndex": 0, "kind": "typo" },
+                        { "start": 10, "length": 4, "queryTermIndex": 1, "kind": "prefix" },
+                    ]
+                })
+            );
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn matches_position_infix_kind() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "infixSearchableAttributes": ["title"] })).await;
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "uic brown", "showMatchesPosition": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(
+                response["hits"][0]["_matchesPosition"],
+                json!({
+                    "title": [
+                        { "start": 4, "length": 5, "queryTermIndex": 0, "kind": "infix" },
+                        { "start": 10, "length": 5, "queryTermIndex": 1, "kind": "exact" },
+                    ]
+                })
+            );
+        })
+        .await;
+}
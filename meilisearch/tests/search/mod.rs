@@ -6,6 +6,7 @@ mod formatted;
 mod multi;
 mod pagination;
 
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 
@@ -192,6 +193,122 @@ async fn test_kanji_language_detection() {
         .await;
 }
 
+#[cfg(feature = "default")]
+#[actix_rt::test]
+async fn test_kanji_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["title_ja"], "locales": ["jpn"] }]
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "title": "The quick (\"brown\") fox can't jump 32.3 feet, right? Brr, it's 29.3°F!" },
+        { "id": 1, "title_ja": "東京" },
+        { "id": 2, "title_ja": "東京のお寿司。" }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "東京", "locales": ["jpn"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_synonym_sets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "a sofa" },
+        { "id": 1, "title": "a couch" },
+        { "id": 2, "title": "a red chair" }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (_, code) = server
+        .service
+        .put_str(
+            "/indexes/test/settings/synonym-sets/furniture",
+            "# furniture\nsofa, couch\n",
+            Vec::new(),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    let (_, code) =
+        index.update_settings(json!({ "synonymSets": { "colors": { "scarlet": ["red"] } } })).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    meili_snap::snapshot!(meili_snap::json_string!(response["synonymSets"]), @r###"
+    {
+      "colors": {
+        "scarlet": [
+          "red"
+        ]
+      },
+      "furniture": {
+        "couch": [
+          "sofa"
+        ],
+        "sofa": [
+          "couch"
+        ]
+      }
+    }
+    "###);
+
+    index
+        .search(json!({"q": "sofa"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "sofa", "synonyms": false}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "sofa", "synonyms": ["colors"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    let (_, code) = server.service.delete("/indexes/test/settings/synonym-sets/furniture").await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(3).await;
+
+    index
+        .search(json!({"q": "scarlet"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "sofa"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+}
+
 #[cfg(feature = "default")]
 #[actix_rt::test]
 async fn test_thai_language() {
@@ -750,3 +867,367 @@ async fn faceting_max_values_per_facet() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_group_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(
+            json!({ "filterableAttributes": ["mother"], "sortableAttributes": ["id"] }),
+        )
+        .await;
+
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "groupBy": "mother",
+                "sort": ["id:desc"],
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                assert!(response["hits"].as_array().unwrap().is_empty());
+                snapshot!(json_string!(response["groups"]), @r###"
+                [
+                  {
+                    "value": "sophie",
+                    "hits": [
+                      {
+                        "id": 951
+                      }
+                    ],
+                    "totalHits": 1
+                  },
+                  {
+                    "value": "michelle",
+                    "hits": [
+                      {
+                        "id": 852
+                      }
+                    ],
+                    "totalHits": 2
+                  },
+                  {
+                    "value": "sabine",
+                    "hits": [
+                      {
+                        "id": 654
+                      }
+                    ],
+                    "totalHits": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "groupBy": "mother",
+                "hitsPerGroup": 2,
+                "offset": 1,
+                "limit": 1,
+                "sort": ["id:desc"],
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["groups"]), @r###"
+                [
+                  {
+                    "value": "michelle",
+                    "hits": [
+                      {
+                        "id": 852
+                      },
+                      {
+                        "id": 750
+                      }
+                    ],
+                    "totalHits": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_boost() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["brand", "in_stock"] })).await;
+
+    let documents = json!([
+        { "id": 1, "brand": "acme", "in_stock": false },
+        { "id": 2, "brand": "globex", "in_stock": true },
+        { "id": 3, "brand": "acme", "in_stock": true },
+        { "id": 4, "brand": "initech", "in_stock": false },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // the boosts are only available in POST searches
+    let (response, code) = index
+        .search_post(json!({
+            "boost": [
+                { "filter": "in_stock = true", "weight": 1 },
+                { "filter": ["brand = acme"], "weight": 2.5 }
+            ],
+            "attributesToRetrieve": ["id"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "sortableAttributes": ["date"] })).await;
+
+    let documents = json!([
+        { "id": 1, "date": 2 },
+        { "id": 2, "date": 3 },
+        { "id": 3, "date": 1 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // the ranking rules are only available in POST searches
+    let (response, code) = index
+        .search_post(json!({ "rankingRules": ["date:desc"], "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+
+    // the sort parameter requires the sort ranking rule
+    let (response, code) =
+        index.search_post(json!({ "rankingRules": ["words"], "sort": ["date:asc"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_search_sort""###);
+
+    // the ranking rules of the index are left untouched
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["rankingRules"]), @r###"
+    [
+      "words",
+      "typo",
+      "proximity",
+      "attribute",
+      "sort",
+      "exactness"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_random_seed() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0 },
+        { "id": 1 },
+        { "id": 2 },
+        { "id": 3 },
+        { "id": 4 },
+        { "id": 5 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "randomSeed": 42, "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 5
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 4
+              },
+              {
+                "id": 0
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the pages of the same seed follow each other
+    index
+        .search(
+            json!({ "randomSeed": 42, "offset": 2, "limit": 2, "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 4
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_debug() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "text": "red shoes" },
+        { "id": 2, "text": "red" },
+        { "id": 3, "text": "blue" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "red" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["debug"]), @"null");
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "red", "debug": true }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["debug"]["universeLen"]), @"2");
+            snapshot!(json_string!(response["debug"]["droppedEvents"]), @"0");
+            snapshot!(json_string!(response["debug"]["rankingRules"][0]), @r###""words""###);
+            snapshot!(json_string!(response["debug"]["events"][0]), @r###"
+            {
+              "event": "startIteration",
+              "rankingRule": 0,
+              "universeLen": 2
+            }
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_sort_options() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "sortableAttributes": ["name", "price"] })).await;
+
+    let documents = json!([
+        { "id": 1, "name": "item10", "price": 3 },
+        { "id": 2, "name": "Item2" },
+        { "id": 3, "name": "item1", "price": 1 },
+        { "id": 4, "price": 2 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "sort": ["name:asc:collation:natural:missing:first"],
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 4
+                  },
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 2
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "sort": ["price:desc:missing:first"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  },
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 4
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
@@ -164,3 +164,62 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn search_after_cursor() {
+    let server = Server::new().await;
+    let index = server.index("basic");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "limit": 100 })).await;
+    assert_eq!(code, 200, "{}", response);
+    let expected = response["hits"].as_array().unwrap().clone();
+    // the page is not full, there is no more hits to return
+    assert!(response.get("cursor").is_none());
+
+    let mut hits = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = json!({ "limit": 2 });
+        if let Some(cursor) = cursor.take() {
+            query["searchAfter"] = json!(cursor);
+        }
+        let (response, code) = index.search_post(query).await;
+        assert_eq!(code, 200, "{}", response);
+
+        let page = response["hits"].as_array().unwrap();
+        assert!(page.len() <= 2);
+        hits.extend(page.iter().cloned());
+        match response["cursor"].as_str() {
+            Some(next_cursor) => cursor = Some(next_cursor.to_string()),
+            None => break,
+        }
+    }
+
+    assert_eq!(hits, expected);
+}
+
+#[actix_rt::test]
+async fn search_after_cursor_with_get() {
+    let server = Server::new().await;
+    let index = server.index("basic");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_get("limit=3").await;
+    assert_eq!(code, 200, "{}", response);
+    let cursor = response["cursor"].as_str().unwrap().to_string();
+
+    let (response, code) = index.search_get(&format!("limit=100&searchAfter={cursor}")).await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("cursor").is_none());
+
+    let (all, code) = index.search_get("limit=100").await;
+    assert_eq!(code, 200, "{}", all);
+    assert_eq!(response["hits"].as_array().unwrap()[..], all["hits"].as_array().unwrap()[3..]);
+}
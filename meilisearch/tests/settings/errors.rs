@@ -337,3 +337,145 @@ async fn settings_bad_pagination() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_stemming_languages() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "stemmingLanguages": ["klingon"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `klingon` at `.stemmingLanguages[0]`: expected one of `arabic`, `danish`, `dutch`, `english`, `finnish`, `french`, `german`, `greek`, `hungarian`, `italian`, `norwegian`, `portuguese`, `romanian`, `russian`, `spanish`, `swedish`, `tamil`, `turkish`",
+      "code": "invalid_settings_stemming_languages",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_stemming_languages"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["title"], "locales": "doggo" }]
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.localizedAttributes[0].locales`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_document_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "documentSchema": { "fields": { "price": { "type": "date" } } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `date` at `.documentSchema.fields.price.type`: expected one of `string`, `number`, `boolean`, `array`, `object`",
+      "code": "invalid_settings_document_schema",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_schema"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_ingest_pipeline() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "ingestPipeline": [{ "type": "uppercase", "field": "title" }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `uppercase` at `.ingestPipeline[0].type`: expected one of `concatenate`, `lowercase`, `split`, `copy`, `rename`, `drop`, `dateParts`, `default`",
+      "code": "invalid_settings_ingest_pipeline",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ingest_pipeline"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_expiry_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "expiryField": ["expires_at"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.expiryField`: expected a string, but found an array: `[\"expires_at\"]`",
+      "code": "invalid_settings_expiry_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_expiry_field"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_version_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "versionField": ["version"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.versionField`: expected a string, but found an array: `[\"version\"]`",
+      "code": "invalid_settings_version_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_version_field"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_synonym_sets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "synonymSets": { "furniture": ["sofa", "couch"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.synonymSets.furniture`: expected an object, but found an array: `[\"sofa\",\"couch\"]`",
+      "code": "invalid_settings_synonym_sets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_sets"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .put_str("/indexes/test/settings/synonym-sets/furniture", "sofa, couch\nsofa", Vec::new())
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid synonyms file at line 2: expected at least two comma separated words.",
+      "code": "invalid_settings_synonym_sets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_sets"
+    }
+    "###);
+}
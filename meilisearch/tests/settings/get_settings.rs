@@ -17,6 +17,7 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     );
     map.insert("stop_words", json!([]));
     map.insert("synonyms", json!({}));
+    map.insert("synonym_sets", json!({}));
     map.insert(
         "faceting",
         json!({
@@ -29,6 +30,13 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
             "maxTotalHits": json!(1000),
         }),
     );
+    map.insert("infix_searchable_attributes", json!([]));
+    map.insert("stemming_languages", json!([]));
+    map.insert("localized_attributes", json!(null));
+    map.insert("document_schema", json!(null));
+    map.insert("ingest_pipeline", json!([]));
+    map.insert("expiry_field", json!(null));
+    map.insert("version_field", json!(null));
     map
 });
 
@@ -48,7 +56,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 11);
+    assert_eq!(settings.keys().len(), 19);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -59,6 +67,7 @@ async fn get_settings() {
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
     assert_eq!(settings["stopWords"], json!([]));
+    assert_eq!(settings["synonymSets"], json!({}));
     assert_eq!(
         settings["faceting"],
         json!({
@@ -71,6 +80,13 @@ async fn get_settings() {
             "maxTotalHits": 1000,
         })
     );
+    assert_eq!(settings["infixSearchableAttributes"], json!([]));
+    assert_eq!(settings["stemmingLanguages"], json!([]));
+    assert_eq!(settings["localizedAttributes"], json!(null));
+    assert_eq!(settings["documentSchema"], json!(null));
+    assert_eq!(settings["ingestPipeline"], json!([]));
+    assert_eq!(settings["expiryField"], json!(null));
+    assert_eq!(settings["versionField"], json!(null));
 }
 
 #[actix_rt::test]
@@ -268,8 +284,16 @@ test_setting_routes!(
     stop_words put,
     ranking_rules put,
     synonyms put,
+    synonym_sets patch,
     pagination patch,
-    faceting patch
+    faceting patch,
+    infix_searchable_attributes put,
+    stemming_languages put,
+    localized_attributes put,
+    document_schema put,
+    ingest_pipeline put,
+    expiry_field put,
+    version_field put
 );
 
 #[actix_rt::test]
@@ -290,6 +314,37 @@ async fn error_set_invalid_ranking_rules() {
     "###);
 }
 
+#[actix_rt::test]
+async fn set_ranking_function() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let ranking_rules = json!(["words", "published_at:gauss(now, 86400)", "popularity:log"]);
+    let (_response, _code) = index.update_settings(json!({ "rankingRules": ranking_rules })).await;
+    index.wait_task(0).await;
+
+    let (response, _) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["rankingRules"]), @r###"
+    [
+      "words",
+      "published_at:gauss(now, 86400)",
+      "popularity:log"
+    ]
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "rankingRules": ["popularity:gauss(now, 0)"] })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `popularity:gauss(now, 0)` ranking rule is invalid. Ranking functions must be written `field:log` or `field:<function>(<origin>, <scale>)`, with a function among gauss, exp and linear, an origin being a number or `now` and a scale being a strictly positive number.",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn set_and_reset_distinct_attribute_with_dedicated_route() {
     let server = Server::new().await;
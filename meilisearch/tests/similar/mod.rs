@@ -0,0 +1,128 @@
+use meili_snap::*;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+pub(self) static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "the red dragon of the north" },
+        { "id": 1, "title": "the blue dragon of the south" },
+        { "id": 2, "title": "a red apple" },
+        { "id": 3, "title": "the story of the north" },
+        { "id": 4, "title": "epilogue" },
+    ])
+});
+
+#[actix_rt::test]
+async fn similar_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.similar_post(json!({ "id": 0 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "hits": [
+        {
+          "id": 1,
+          "title": "the blue dragon of the south"
+        }
+      ],
+      "id": "0",
+      "processingTimeMs": "[time]",
+      "limit": 20,
+      "offset": 0,
+      "estimatedTotalHits": 1
+    }
+    "###);
+
+    // the id can also be given as a string
+    let (response, code) = index.similar_post(json!({ "id": "0" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "title": "the blue dragon of the south" }]));
+
+    // no word of this document is shared with another one
+    let (response, code) = index.similar_post(json!({ "id": 4 })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([]));
+    assert_eq!(response["estimatedTotalHits"], 0);
+}
+
+#[actix_rt::test]
+async fn similar_documents_with_filter_and_attributes_to_retrieve() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({ "filterableAttributes": ["id"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.similar_post(json!({ "id": 0, "attributesToRetrieve": ["title"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "title": "the blue dragon of the south" }]));
+
+    // the only other document containing `dragon`, the most discriminative word, is filtered out
+    let (response, code) = index.similar_post(json!({ "id": 0, "filter": "id != 1" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([]));
+}
+
+#[actix_rt::test]
+async fn similar_unexisting_document() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.similar_post(json!({ "id": 42 })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Document `42` not found.",
+      "code": "document_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn similar_bad_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.similar_post(json!({})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "missing_similar_id");
+
+    let (response, code) = index.similar_post(json!({ "id": [0] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_similar_id");
+
+    let (response, code) = index.similar_post(json!({ "id": 0, "offset": "doggo" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_similar_offset");
+
+    let (response, code) = index.similar_post(json!({ "id": 0, "limit": "doggo" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_similar_limit");
+
+    let (response, code) = index.similar_post(json!({ "id": 0, "filter": "title = dragon" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_similar_filter");
+
+    let (response, code) =
+        index.similar_post(json!({ "id": 0, "attributesToRetrieve": "title" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_similar_attributes_to_retrieve");
+}
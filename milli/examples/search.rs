@@ -5,8 +5,8 @@ use std::time::Instant;
 
 use heed::EnvOpenOptions;
 use milli::{
-    execute_search, DefaultSearchLogger, GeoSortStrategy, Index, SearchContext, SearchLogger,
-    TermsMatchingStrategy,
+    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, Index, SearchContext,
+    SearchLogger, TermsMatchingStrategy,
 };
 
 #[global_allocator]
@@ -49,17 +49,23 @@ fn main() -> Result<(), Box<dyn Error>> {
             let start = Instant::now();
 
             let mut ctx = SearchContext::new(&index, &txn);
+            let universe = filtered_universe(&ctx, &None)?;
             let docs = execute_search(
                 &mut ctx,
                 &(!query.trim().is_empty()).then(|| query.trim().to_owned()),
                 TermsMatchingStrategy::Last,
                 false,
+                universe,
                 &None,
                 &None,
                 GeoSortStrategy::default(),
+                &[],
+                None,
                 0,
                 20,
                 None,
+                None,
+                None,
                 &mut DefaultSearchLogger,
                 logger,
             )?;
@@ -20,6 +20,10 @@ pub enum AscDescError {
     InvalidSyntax { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a asc/desc rule.")]
     ReservedKeyword { name: String },
+    #[error("Invalid sort option in `{name}`.")]
+    InvalidOption { name: String },
+    #[error("The sort options can't be used with `_geoPoint`, found `{name}`.")]
+    GeoPointOptions { name: String },
 }
 
 impl From<BadGeoError> for AscDescError {
@@ -34,7 +38,9 @@ impl From<AscDescError> for CriterionError {
             AscDescError::GeoError(_) => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
-            AscDescError::InvalidSyntax { name } => CriterionError::InvalidName { name },
+            AscDescError::InvalidSyntax { name }
+            | AscDescError::InvalidOption { name }
+            | AscDescError::GeoPointOptions { name } => CriterionError::InvalidName { name },
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
@@ -148,6 +154,83 @@ impl FromStr for AscDesc {
     }
 }
 
+/// Where the documents without any value for the sorted field are ranked.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPosition {
+    First,
+    #[default]
+    Last,
+}
+
+/// How the string values of the sorted field are compared.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    /// The values are compared character by character, ignoring their case and accents.
+    #[default]
+    CaseInsensitive,
+    /// Like `CaseInsensitive`, but the sequences of digits are compared by their numeric
+    /// value, so that `item2` comes before `item10`.
+    Natural,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SortOptions {
+    pub missing: MissingPosition,
+    pub collation: Collation,
+}
+
+/// An [`AscDesc`] sort expression along with the options changing how it ranks the documents.
+///
+/// The options are written after the expression, e.g. `title:asc:collation:natural:missing:first`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SortCriterion {
+    pub asc_desc: AscDesc,
+    pub options: SortOptions,
+}
+
+impl From<AscDesc> for SortCriterion {
+    fn from(asc_desc: AscDesc) -> Self {
+        SortCriterion { asc_desc, options: SortOptions::default() }
+    }
+}
+
+impl FromStr for SortCriterion {
+    type Err = AscDescError;
+
+    fn from_str(text: &str) -> Result<SortCriterion, Self::Err> {
+        let mut options = SortOptions::default();
+        let mut has_options = false;
+        let mut expression = text;
+        // The options are pairs of a name and a value, read from the end of the text.
+        while let Some((rest, value)) = expression.rsplit_once(':') {
+            let Some((rest, name)) = rest.rsplit_once(':') else { break };
+            match (name, value) {
+                ("missing", "first") => options.missing = MissingPosition::First,
+                ("missing", "last") => options.missing = MissingPosition::Last,
+                ("collation", "caseInsensitive") => options.collation = Collation::CaseInsensitive,
+                ("collation", "natural") => options.collation = Collation::Natural,
+                ("missing" | "collation", value) if value != "asc" && value != "desc" => {
+                    return Err(AscDescError::InvalidOption { name: text.to_string() })
+                }
+                _ => break,
+            }
+            has_options = true;
+            expression = rest;
+        }
+
+        let asc_desc: AscDesc = expression.parse().map_err(|error| match error {
+            AscDescError::InvalidSyntax { .. } => {
+                AscDescError::InvalidSyntax { name: text.to_string() }
+            }
+            error => error,
+        })?;
+        if has_options && asc_desc.member().geo_point().is_some() {
+            return Err(AscDescError::GeoPointOptions { name: text.to_string() });
+        }
+        Ok(SortCriterion { asc_desc, options })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum SortError {
     #[error(transparent)]
@@ -157,6 +240,11 @@ pub enum SortError {
     BadGeoPointUsage { name: String },
     #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `{name}`.")]
     InvalidName { name: String },
+    #[error("Invalid sort option in `{name}`: the supported options are `missing:first`, `missing:last`, \
+                    `collation:caseInsensitive` and `collation:natural`.")]
+    InvalidOption { name: String },
+    #[error("The sort options can't be used to sort on the `_geo` field, found `{name}`.")]
+    GeoPointOptions { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression.")]
     ReservedName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression. \
@@ -172,6 +260,8 @@ impl From<AscDescError> for SortError {
         match error {
             AscDescError::GeoError(error) => SortError::ParseGeoError { error },
             AscDescError::InvalidSyntax { name } => SortError::InvalidName { name },
+            AscDescError::InvalidOption { name } => SortError::InvalidOption { name },
+            AscDescError::GeoPointOptions { name } => SortError::GeoPointOptions { name },
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 SortError::BadGeoPointUsage { name }
             }
@@ -295,4 +385,67 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parse_sort_criterion() {
+        let valid_req = [
+            ("truc:asc", Asc(Field(S("truc"))), MissingPosition::Last, Collation::CaseInsensitive),
+            (
+                "truc:asc:missing:first",
+                Asc(Field(S("truc"))),
+                MissingPosition::First,
+                Collation::CaseInsensitive,
+            ),
+            (
+                "truc:desc:missing:last",
+                Desc(Field(S("truc"))),
+                MissingPosition::Last,
+                Collation::CaseInsensitive,
+            ),
+            (
+                "truc:desc:collation:natural",
+                Desc(Field(S("truc"))),
+                MissingPosition::Last,
+                Collation::Natural,
+            ),
+            (
+                "a:b:asc:collation:natural:missing:first",
+                Asc(Field(S("a:b"))),
+                MissingPosition::First,
+                Collation::Natural,
+            ),
+            (
+                "a:missing:asc",
+                Asc(Field(S("a:missing"))),
+                MissingPosition::Last,
+                Collation::CaseInsensitive,
+            ),
+            (
+                "_geoPoint(42, 59):asc",
+                Asc(Geo([42., 59.])),
+                MissingPosition::Last,
+                Collation::CaseInsensitive,
+            ),
+        ];
+
+        for (req, asc_desc, missing, collation) in valid_req {
+            let expected = SortCriterion { asc_desc, options: SortOptions { missing, collation } };
+            assert_eq!(req.parse::<SortCriterion>().unwrap(), expected, "for input {req}");
+        }
+
+        let invalid_req = [
+            ("truc:asc:missing:middle", InvalidOption { name: S("truc:asc:missing:middle") }),
+            ("truc:asc:collation:binary", InvalidOption { name: S("truc:asc:collation:binary") }),
+            ("truc:missing:first", InvalidSyntax { name: S("truc:missing:first") }),
+            (
+                "_geoPoint(42, 59):asc:missing:first",
+                GeoPointOptions { name: S("_geoPoint(42, 59):asc:missing:first") },
+            ),
+        ];
+
+        for (req, expected_error) in invalid_req {
+            let error = req.parse::<SortCriterion>().unwrap_err();
+            assert_eq!(error.to_string(), expected_error.to_string(), "for input {req}");
+        }
+    }
 }
@@ -22,6 +22,12 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. Ranking functions must be written \
+`field:log` or `field:<function>(<origin>, <scale>)`, with a function among gauss, exp and linear, \
+an origin being a number or `now` and a scale being a strictly positive number."
+    )]
+    InvalidRankingFunction { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,18 +51,137 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score of the function applied on the value of the field specified.
+    Function(String, RankingFunction),
 }
 
 impl Criterion {
     /// Returns the field name parameter of this criterion.
     pub fn field_name(&self) -> Option<&str> {
         match self {
-            Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Asc(name) | Criterion::Desc(name) | Criterion::Function(name, _) => {
+                Some(name)
+            }
             _otherwise => None,
         }
     }
 }
 
+/// A function scoring the documents according to the numeric value of a field.
+///
+/// The decay functions score a document according to the distance between its value and
+/// the origin. The score is `1` at the origin and is halved when the distance reaches the scale.
+/// Dates must be stored as unix timestamps, in seconds, to be decayed from `now`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RankingFunction {
+    /// The score follows a normal distribution around the origin.
+    Gauss { origin: DecayOrigin, scale: FiniteNumber },
+    /// The score decreases exponentially with the distance.
+    Exp { origin: DecayOrigin, scale: FiniteNumber },
+    /// The score decreases linearly with the distance, down to `0` at twice the scale.
+    Linear { origin: DecayOrigin, scale: FiniteNumber },
+    /// The score is the natural logarithm of the value.
+    Log,
+}
+
+/// The value from which the distance of the documents is computed by the decay functions.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayOrigin {
+    /// The current unix timestamp, in seconds, at search time.
+    Now,
+    Value(FiniteNumber),
+}
+
+/// A number that is neither infinite nor NaN, used as a parameter of the ranking functions.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct FiniteNumber(f64);
+
+// A finite number is never NaN and is therefore always equal to itself.
+impl Eq for FiniteNumber {}
+
+impl FiniteNumber {
+    pub fn new(number: f64) -> Option<FiniteNumber> {
+        number.is_finite().then_some(FiniteNumber(number))
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for FiniteNumber {
+    type Error = &'static str;
+
+    fn try_from(number: f64) -> Result<Self, Self::Error> {
+        FiniteNumber::new(number).ok_or("the number must be finite")
+    }
+}
+
+impl From<FiniteNumber> for f64 {
+    fn from(number: FiniteNumber) -> f64 {
+        number.0
+    }
+}
+
+impl fmt::Display for FiniteNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for RankingFunction {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<RankingFunction, Self::Err> {
+        if text == "log" {
+            return Ok(RankingFunction::Log);
+        }
+
+        let (name, parameters) = text.strip_suffix(')').and_then(|s| s.split_once('(')).ok_or(())?;
+        let (origin, scale) = parameters.split_once(',').ok_or(())?;
+        let origin = match origin.trim() {
+            "now" => DecayOrigin::Now,
+            origin => {
+                DecayOrigin::Value(origin.parse().ok().and_then(FiniteNumber::new).ok_or(())?)
+            }
+        };
+        let scale = scale.trim().parse().ok().and_then(FiniteNumber::new).ok_or(())?;
+        if scale.get() <= 0.0 {
+            return Err(());
+        }
+
+        match name {
+            "gauss" => Ok(RankingFunction::Gauss { origin, scale }),
+            "exp" => Ok(RankingFunction::Exp { origin, scale }),
+            "linear" => Ok(RankingFunction::Linear { origin, scale }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for RankingFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, origin, scale) = match self {
+            RankingFunction::Gauss { origin, scale } => ("gauss", origin, scale),
+            RankingFunction::Exp { origin, scale } => ("exp", origin, scale),
+            RankingFunction::Linear { origin, scale } => ("linear", origin, scale),
+            RankingFunction::Log => return f.write_str("log"),
+        };
+        match origin {
+            DecayOrigin::Now => write!(f, "{name}(now, {scale})"),
+            DecayOrigin::Value(origin) => write!(f, "{name}({origin}, {scale})"),
+        }
+    }
+}
+
+/// Whether the text looks like a ranking function, even an invalid one.
+fn is_ranking_function(text: &str) -> bool {
+    text == "log"
+        || (["gauss(", "exp(", "linear("].iter().any(|name| text.starts_with(name))
+            && text.ends_with(')'))
+}
+
 impl FromStr for Criterion {
     type Err = CriterionError;
 
@@ -68,13 +193,35 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
-            text => match AscDesc::from_str(text)? {
-                AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
-                AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
-                    Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
+            text => {
+                if let Some((field, function)) = text.rsplit_once(':') {
+                    if is_ranking_function(function) {
+                        return ranking_function_criterion(text, field, function);
+                    }
                 }
-            },
+                match AscDesc::from_str(text)? {
+                    AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
+                    AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
+                    AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+                        Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn ranking_function_criterion(
+    text: &str,
+    field: &str,
+    function: &str,
+) -> Result<Criterion, CriterionError> {
+    let function = RankingFunction::from_str(function)
+        .map_err(|_| CriterionError::InvalidRankingFunction { name: text.to_string() })?;
+    match Member::from_str(field)? {
+        Member::Field(field) => Ok(Criterion::Function(field, function)),
+        Member::Geo(_) => {
+            Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })
         }
     }
 }
@@ -103,6 +250,7 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Function(attr, function) => write!(f, "{}:{}", attr, function),
         }
     }
 }
@@ -114,6 +262,10 @@ mod tests {
 
     use super::*;
 
+    fn finite(number: f64) -> FiniteNumber {
+        FiniteNumber::new(number).unwrap()
+    }
+
     #[test]
     fn parse_criterion() {
         let valid_criteria = [
@@ -129,6 +281,34 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            ("popularity:log", Criterion::Function(S("popularity"), RankingFunction::Log)),
+            (
+                "published_at:gauss(now, 86400)",
+                Criterion::Function(
+                    S("published_at"),
+                    RankingFunction::Gauss { origin: DecayOrigin::Now, scale: finite(86400.0) },
+                ),
+            ),
+            (
+                "price:exp(-12.5,3)",
+                Criterion::Function(
+                    S("price"),
+                    RankingFunction::Exp {
+                        origin: DecayOrigin::Value(finite(-12.5)),
+                        scale: finite(3.0),
+                    },
+                ),
+            ),
+            (
+                "price:asc:linear( 10 , 0.5 )",
+                Criterion::Function(
+                    S("price:asc"),
+                    RankingFunction::Linear {
+                        origin: DecayOrigin::Value(finite(10.0)),
+                        scale: finite(0.5),
+                    },
+                ),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -140,7 +320,10 @@ mod tests {
                 expected,
                 res
             );
-            assert_eq!(res.unwrap(), expected);
+            let res = res.unwrap();
+            assert_eq!(res, expected);
+            // the criteria must be displayed in a way they can be parsed back
+            assert_eq!(res.to_string().parse::<Criterion>().unwrap(), expected);
         }
 
         let invalid_criteria = [
@@ -153,6 +336,13 @@ mod tests {
             ("price:aasc", InvalidName { name: S("price:aasc") }),
             ("price:asc and desc", InvalidName { name: S("price:asc and desc") }),
             ("price:asc:truc", InvalidName { name: S("price:asc:truc") }),
+            ("price:lg", InvalidName { name: S("price:lg") }),
+            ("price:gauss(now)", InvalidRankingFunction { name: S("price:gauss(now)") }),
+            ("price:gauss(now, 0)", InvalidRankingFunction { name: S("price:gauss(now, 0)") }),
+            ("price:exp(today, 1)", InvalidRankingFunction { name: S("price:exp(today, 1)") }),
+            ("price:linear(1, -1)", InvalidRankingFunction { name: S("price:linear(1, -1)") }),
+            ("price:exp(inf, 1)", InvalidRankingFunction { name: S("price:exp(inf, 1)") }),
+            ("_geo:log", ReservedName { name: S("_geo") }),
             ("_geo:asc", ReservedName { name: S("_geo") }),
             ("_geoDistance:asc", ReservedName { name: S("_geoDistance") }),
             ("_geoPoint:asc", ReservedNameForSort { name: S("_geoPoint") }),
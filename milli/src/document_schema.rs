@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::result::Result as StdResult;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use crate::Object;
+
+/// The constraints the documents of an index must respect to be indexed.
+///
+/// Only the top-level fields of the documents can be declared.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentSchema {
+    #[serde(default)]
+    #[deserr(default)]
+    pub fields: BTreeMap<String, FieldSchema>,
+    /// The maximum number of nested objects levels, the document itself being the first one.
+    #[serde(default)]
+    #[deserr(default)]
+    pub max_depth: Option<usize>,
+    /// Whether the values that are not of the declared type are converted when possible.
+    #[serde(default)]
+    #[deserr(default)]
+    pub coerce_types: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct FieldSchema {
+    #[serde(default, rename = "type")]
+    #[deserr(default, rename = "type")]
+    pub field_type: Option<FieldType>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub required: bool,
+    /// The maximum number of characters of the string values, including the ones in arrays.
+    #[serde(default)]
+    #[deserr(default)]
+    pub max_length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FieldType {
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl FieldType {
+    fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "a string",
+            FieldType::Number => "a number",
+            FieldType::Boolean => "a boolean",
+            FieldType::Array => "an array",
+            FieldType::Object => "an object",
+        }
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (FieldType::String, Value::String(_))
+                | (FieldType::Number, Value::Number(_))
+                | (FieldType::Boolean, Value::Bool(_))
+                | (FieldType::Array, Value::Array(_))
+                | (FieldType::Object, Value::Object(_))
+        )
+    }
+
+    /// Converts the value into this type, returns `None` if it is not possible.
+    fn coerce(&self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (FieldType::String, Value::Number(number)) => Some(Value::String(number.to_string())),
+            (FieldType::String, Value::Bool(boolean)) => Some(Value::String(boolean.to_string())),
+            (FieldType::Number, Value::String(string)) => {
+                let string = string.trim();
+                string
+                    .parse::<i64>()
+                    .map(Number::from)
+                    .ok()
+                    .or_else(|| string.parse::<f64>().ok().and_then(Number::from_f64))
+                    .map(Value::Number)
+            }
+            (FieldType::Boolean, Value::String(string)) => match string.trim() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl DocumentSchema {
+    /// Returns the value of the field converted into its declared type, `None` when
+    /// the types are not coerced, the field has no declared type or it already matches.
+    pub fn coerce(&self, field: &str, value: &Value) -> Option<Value> {
+        if !self.coerce_types {
+            return None;
+        }
+        let field_type = self.fields.get(field)?.field_type?;
+        if field_type.matches(value) {
+            None
+        } else {
+            field_type.coerce(value)
+        }
+    }
+
+    /// Checks that the document respects the schema, once coerced, and returns
+    /// the reason why it does not otherwise.
+    pub fn validate(&self, document: &Object) -> StdResult<(), String> {
+        for (name, field) in &self.fields {
+            let value = match document.get(name) {
+                None | Some(Value::Null) if field.required => {
+                    return Err(format!("the required field `{name}` is missing"))
+                }
+                None | Some(Value::Null) => continue,
+                Some(value) => value,
+            };
+
+            let coerced = self.coerce(name, value);
+            let value = coerced.as_ref().unwrap_or(value);
+            if let Some(field_type) = field.field_type {
+                if !field_type.matches(value) {
+                    return Err(format!(
+                        "the field `{name}` must be {} but found `{value}`",
+                        field_type.name()
+                    ));
+                }
+            }
+
+            if let Some(max_length) = field.max_length {
+                let strings = match value {
+                    Value::String(string) => vec![string],
+                    Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+                    _ => Vec::new(),
+                };
+                let too_long = strings.into_iter().find(|s| s.chars().count() > max_length);
+                if let Some(string) = too_long {
+                    return Err(format!(
+                        "the field `{name}` contains `{string}` which is longer than {max_length} characters"
+                    ));
+                }
+            }
+        }
+
+        if let Some(max_depth) = self.max_depth {
+            for (name, value) in document {
+                if 1 + objects_depth(value) > max_depth {
+                    return Err(format!(
+                        "the field `{name}` contains objects nested deeper than {max_depth} levels"
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the number of objects levels contained in the value.
+fn objects_depth(value: &Value) -> usize {
+    match value {
+        Value::Object(object) => 1 + object.values().map(objects_depth).max().unwrap_or(0),
+        Value::Array(values) => values.iter().map(objects_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema(schema: Value) -> DocumentSchema {
+        serde_json::from_value(schema).unwrap()
+    }
+
+    fn validate(schema: &DocumentSchema, document: Value) -> StdResult<(), String> {
+        schema.validate(document.as_object().unwrap())
+    }
+
+    #[test]
+    fn validate_documents() {
+        let schema = schema(json!({
+            "fields": {
+                "title": { "type": "string", "required": true, "maxLength": 5 },
+                "price": { "type": "number" },
+                "tags": { "maxLength": 3 },
+            },
+            "maxDepth": 2,
+        }));
+
+        assert_eq!(validate(&schema, json!({ "title": "hello", "price": 12.5 })), Ok(()));
+        assert_eq!(
+            validate(&schema, json!({ "title": "hello", "price": "12.50" })),
+            Err(String::from("the field `price` must be a number but found `\"12.50\"`"))
+        );
+        assert_eq!(
+            validate(&schema, json!({ "price": 12 })),
+            Err(String::from("the required field `title` is missing"))
+        );
+        assert_eq!(
+            validate(&schema, json!({ "title": "hello world" })),
+            Err(String::from(
+                "the field `title` contains `hello world` which is longer than 5 characters"
+            ))
+        );
+        assert_eq!(
+            validate(&schema, json!({ "title": "hello", "tags": ["red", "blue"] })),
+            Err(String::from("the field `tags` contains `blue` which is longer than 3 characters"))
+        );
+        assert_eq!(
+            validate(&schema, json!({ "title": "hello", "author": { "name": "kevin" } })),
+            Ok(())
+        );
+        assert_eq!(
+            validate(
+                &schema,
+                json!({ "title": "hello", "author": [{ "address": { "city": "Paris" } }] })
+            ),
+            Err(String::from("the field `author` contains objects nested deeper than 2 levels"))
+        );
+    }
+
+    #[test]
+    fn coerce_declared_types() {
+        let schema = schema(json!({
+            "fields": {
+                "title": { "type": "string" },
+                "price": { "type": "number" },
+                "available": { "type": "boolean" },
+            },
+            "coerceTypes": true,
+        }));
+
+        assert_eq!(schema.coerce("price", &json!("12.50")), Some(json!(12.5)));
+        assert_eq!(schema.coerce("price", &json!(" 12 ")), Some(json!(12)));
+        assert_eq!(schema.coerce("price", &json!(12)), None);
+        assert_eq!(schema.coerce("price", &json!("twelve")), None);
+        assert_eq!(schema.coerce("title", &json!(42)), Some(json!("42")));
+        assert_eq!(schema.coerce("available", &json!("true")), Some(json!(true)));
+        assert_eq!(schema.coerce("undeclared", &json!("12")), None);
+
+        assert_eq!(validate(&schema, json!({ "price": "12.50", "available": "false" })), Ok(()));
+        assert_eq!(
+            validate(&schema, json!({ "price": "twelve" })),
+            Err(String::from("the field `price` must be a number but found `\"twelve\"`"))
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use std::io::{self, Write};
 
+use deserr::Deserr;
 use grenad::{CompressionType, WriterBuilder};
 use serde::de::Deserializer;
+use serde::Deserialize;
 use serde_json::{to_writer, Value};
 
 use super::{DocumentsBatchIndex, Error, DOCUMENTS_BATCH_INDEX_KEY};
 use crate::documents::serde_impl::DocumentVisitor;
-use crate::Object;
+use crate::{FieldId, Object};
 
 /// The `DocumentsBatchBuilder` provides a way to build a documents batch in the intermediary
 /// format used by milli.
@@ -39,6 +41,10 @@ pub struct DocumentsBatchBuilder<W> {
     /// A buffer to serialize the values and avoid reallocating,
     /// serialized values are stored in an obkv.
     value_buffer: Vec<u8>,
+    /// How the empty cells of the CSV files are read.
+    csv_empty_cells: CsvEmptyCells,
+    /// Whether the dotted headers of the CSV files are read as nested objects.
+    csv_nested_headers: bool,
 }
 
 impl<W: Write> DocumentsBatchBuilder<W> {
@@ -49,9 +55,22 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             documents_count: 0,
             obkv_buffer: Vec::new(),
             value_buffer: Vec::new(),
+            csv_empty_cells: CsvEmptyCells::default(),
+            csv_nested_headers: false,
         }
     }
 
+    /// Sets how the empty cells of the next appended CSV files are read.
+    pub fn set_csv_empty_cells(&mut self, empty_cells: CsvEmptyCells) {
+        self.csv_empty_cells = empty_cells;
+    }
+
+    /// Sets whether the dotted headers of the next appended CSV files, like `address.city`,
+    /// are stored as nested objects instead of flat fields.
+    pub fn set_csv_nested_headers(&mut self, nested_headers: bool) {
+        self.csv_nested_headers = nested_headers;
+    }
+
     /// Returns the number of documents inserted into this builder.
     pub fn documents_count(&self) -> u32 {
         self.documents_count
@@ -88,17 +107,26 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 
     /// Appends a new CSV file into the batch and updates the `DocumentsBatchIndex` accordingly.
+    ///
+    /// The dotted header names, like `address.city`, are stored as nested objects
+    /// only when it was enabled with [`Self::set_csv_nested_headers`].
     pub fn append_csv<R: io::Read>(&mut self, mut reader: csv::Reader<R>) -> Result<(), Error> {
+        let headers = reader.headers()?.clone();
+        let columns: Vec<_> =
+            headers.iter().map(|h| parse_csv_header(h, self.csv_nested_headers)).collect();
+        check_csv_columns_paths(&columns)?;
+
+        // We group the columns by the top-level field they are stored in.
+        let mut typed_fields_ids: Vec<(FieldId, Vec<(usize, &CsvColumn)>)> = Vec::new();
+        for (i, column) in columns.iter().enumerate() {
+            let field_id = self.fields_index.insert(column.path[0]);
+            match typed_fields_ids.iter_mut().find(|(fid, _)| *fid == field_id) {
+                Some((_, columns)) => columns.push((i, column)),
+                None => typed_fields_ids.push((field_id, vec![(i, column)])),
+            }
+        }
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
-        let mut typed_fields_ids: Vec<_> = reader
-            .headers()?
-            .into_iter()
-            .map(parse_csv_header)
-            .map(|(k, t)| (self.fields_index.insert(k), t))
-            .enumerate()
-            .collect();
-        // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
-        typed_fields_ids.sort_unstable_by_key(|(_, (fid, _))| *fid);
+        typed_fields_ids.sort_unstable_by_key(|(fid, _)| *fid);
 
         let mut record = csv::StringRecord::new();
         let mut line = 0;
@@ -110,58 +138,28 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             self.obkv_buffer.clear();
             let mut writer = obkv::KvWriter::new(&mut self.obkv_buffer);
 
-            for (i, (field_id, type_)) in typed_fields_ids.iter() {
+            for (field_id, columns) in typed_fields_ids.iter() {
                 self.value_buffer.clear();
 
-                let value = &record[*i];
-                let trimmed_value = value.trim();
-                match type_ {
-                    AllowedType::Number => {
-                        if trimmed_value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else if let Ok(integer) = trimmed_value.parse::<i64>() {
-                            to_writer(&mut self.value_buffer, &integer)?;
-                        } else {
-                            match trimmed_value.parse::<f64>() {
-                                Ok(float) => {
-                                    to_writer(&mut self.value_buffer, &float)?;
-                                }
-                                Err(error) => {
-                                    return Err(Error::ParseFloat {
-                                        error,
-                                        line,
-                                        value: value.to_string(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    AllowedType::Boolean => {
-                        if trimmed_value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else {
-                            match trimmed_value.parse::<bool>() {
-                                Ok(bool) => {
-                                    to_writer(&mut self.value_buffer, &bool)?;
-                                }
-                                Err(error) => {
-                                    return Err(Error::ParseBool {
-                                        error,
-                                        line,
-                                        value: value.to_string(),
-                                    });
-                                }
-                            }
-                        }
+                let value = match columns.as_slice() {
+                    [(i, column)] if column.path.len() == 1 => {
+                        parse_csv_value(&record[*i], &column.type_, self.csv_empty_cells, line)?
                     }
-                    AllowedType::String => {
-                        if value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else {
-                            to_writer(&mut self.value_buffer, value)?;
+                    columns => {
+                        let mut object = Value::Object(Object::new());
+                        for (i, column) in columns {
+                            let value = parse_csv_value(
+                                &record[*i],
+                                &column.type_,
+                                self.csv_empty_cells,
+                                line,
+                            )?;
+                            insert_nested_value(&mut object, &column.path[1..], value);
                         }
+                        object
                     }
-                }
+                };
+                to_writer(&mut self.value_buffer, &value)?;
 
                 // We insert into the obkv writer the value buffer that has been filled just above.
                 writer.insert(*field_id, &self.value_buffer)?;
@@ -189,20 +187,73 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 }
 
+/// How the empty cells of the string and array columns of a CSV are read,
+/// the empty cells of the other columns are always read as `null`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum CsvEmptyCells {
+    /// The empty cells are read as `null`.
+    #[default]
+    Null,
+    /// The empty cells are read as an empty string or an empty array.
+    Empty,
+}
+
+/// The separator used between the values of an `:array` column when none is specified.
+const DEFAULT_CSV_ARRAY_SEPARATOR: &str = ",";
+
 #[derive(Debug)]
 enum AllowedType {
     String,
     Boolean,
     Number,
+    Array { separator: String },
+    Json,
+}
+
+#[derive(Debug)]
+struct CsvColumn<'a> {
+    /// The path of the field, the dotted header names are split into several parts.
+    path: Vec<&'a str>,
+    type_: AllowedType,
 }
 
-fn parse_csv_header(header: &str) -> (&str, AllowedType) {
+fn parse_csv_header(header: &str, nested: bool) -> CsvColumn {
+    let (field_name, type_) = parse_csv_header_type(header);
+    if !nested {
+        return CsvColumn { path: vec![field_name], type_ };
+    }
+    let path: Vec<_> = field_name.split('.').collect();
+    // we only create nested objects when none of the parts of the name are empty.
+    if path.iter().any(|part| part.is_empty()) {
+        CsvColumn { path: vec![field_name], type_ }
+    } else {
+        CsvColumn { path, type_ }
+    }
+}
+
+fn parse_csv_header_type(header: &str) -> (&str, AllowedType) {
+    // the separator of an array can be specified between parentheses, e.g. `tags:array(|)`.
+    if let Some((field_name, separator)) =
+        header.strip_suffix(')').and_then(|header| header.rsplit_once(":array("))
+    {
+        if !separator.is_empty() {
+            return (field_name, AllowedType::Array { separator: separator.to_string() });
+        }
+    }
+
     // if there are several separators we only split on the last one.
     match header.rsplit_once(':') {
         Some((field_name, field_type)) => match field_type {
             "string" => (field_name, AllowedType::String),
             "boolean" => (field_name, AllowedType::Boolean),
             "number" => (field_name, AllowedType::Number),
+            "array" => (
+                field_name,
+                AllowedType::Array { separator: DEFAULT_CSV_ARRAY_SEPARATOR.to_string() },
+            ),
+            "json" => (field_name, AllowedType::Json),
             // if the pattern isn't reconized, we keep the whole field.
             _otherwise => (header, AllowedType::String),
         },
@@ -210,6 +261,102 @@ fn parse_csv_header(header: &str) -> (&str, AllowedType) {
     }
 }
 
+/// Returns an error when a column is stored inside the value of another column,
+/// like the `address` and `address.city` columns, or when two columns share the same path.
+fn check_csv_columns_paths(columns: &[CsvColumn]) -> Result<(), Error> {
+    for (i, parent) in columns.iter().enumerate() {
+        if columns[..i].iter().any(|column| column.path == parent.path) {
+            return Err(Error::DuplicateCsvHeader { header: parent.path.join(".") });
+        }
+        for child in columns {
+            if child.path.len() > parent.path.len() && child.path.starts_with(&parent.path) {
+                return Err(Error::ConflictingCsvHeaders {
+                    parent: parent.path.join("."),
+                    child: child.path.join("."),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_csv_value(
+    value: &str,
+    type_: &AllowedType,
+    empty_cells: CsvEmptyCells,
+    line: usize,
+) -> Result<Value, Error> {
+    let trimmed_value = value.trim();
+    match type_ {
+        AllowedType::Number => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else if let Ok(integer) = trimmed_value.parse::<i64>() {
+                Ok(Value::from(integer))
+            } else {
+                match trimmed_value.parse::<f64>() {
+                    Ok(float) => Ok(Value::from(float)),
+                    Err(error) => Err(Error::ParseFloat { error, line, value: value.to_string() }),
+                }
+            }
+        }
+        AllowedType::Boolean => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else {
+                match trimmed_value.parse::<bool>() {
+                    Ok(bool) => Ok(Value::from(bool)),
+                    Err(error) => Err(Error::ParseBool { error, line, value: value.to_string() }),
+                }
+            }
+        }
+        AllowedType::String => {
+            if value.is_empty() && empty_cells == CsvEmptyCells::Null {
+                Ok(Value::Null)
+            } else {
+                Ok(Value::from(value))
+            }
+        }
+        AllowedType::Array { separator } => {
+            if trimmed_value.is_empty() && empty_cells == CsvEmptyCells::Null {
+                Ok(Value::Null)
+            } else {
+                Ok(trimmed_value
+                    .split(separator.as_str())
+                    .map(str::trim)
+                    .filter(|part| !part.is_empty())
+                    .map(Value::from)
+                    .collect())
+            }
+        }
+        AllowedType::Json => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else {
+                serde_json::from_str(trimmed_value).map_err(|error| Error::ParseJson {
+                    error,
+                    line,
+                    value: value.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Inserts the value in the object following the path and creates the missing objects.
+fn insert_nested_value(object: &mut Value, path: &[&str], value: Value) {
+    match path {
+        [] => *object = value,
+        [key, rest @ ..] => {
+            if let Value::Object(map) = object {
+                let entry = map.entry(*key).or_insert_with(|| Value::Object(Object::new()));
+                insert_nested_value(entry, rest, value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Cursor;
@@ -577,6 +724,163 @@ mod test {
         assert!(builder.append_csv(csv).is_err());
     }
 
+    #[test]
+    fn array_and_json_in_fields() {
+        let csv_content = r#"id:number,tags:array,colors:array(|),available:boolean,meta:json
+1,"red shoes, sport ,","blue|green",true,"{""size"": 42, ""new"": false}"
+2,,,,"#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_csv(csv).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "id": 1,
+                "tags": ["red shoes", "sport"],
+                "colors": ["blue", "green"],
+                "available": true,
+                "meta": { "size": 42, "new": false },
+            })
+        );
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({ "id": 2, "tags": null, "colors": null, "available": null, "meta": null })
+        );
+    }
+
+    #[test]
+    fn empty_cells_as_empty_values() {
+        let csv_content = "id:number,name,tags:array,meta:json\n1,,,";
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.set_csv_empty_cells(CsvEmptyCells::Empty);
+        builder.append_csv(csv).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(val, json!({ "id": 1, "name": "", "tags": [], "meta": null }));
+    }
+
+    #[test]
+    fn dotted_header_as_nested_object() {
+        let csv_content = r#"id,address.city,address.geo.lat:number,.hidden,name.
+1,"Boston",42.36,a,b"#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.set_csv_nested_headers(true);
+        builder.append_csv(csv).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+        assert_eq!(index.len(), 4);
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "id": "1",
+                "address": { "city": "Boston", "geo": { "lat": 42.36 } },
+                ".hidden": "a",
+                "name.": "b",
+            })
+        );
+    }
+
+    #[test]
+    fn dotted_header_as_flat_field_by_default() {
+        let csv_content = r#"id,address,address.city,address.geo.lat:number
+1,"Boston","Boston",42.36"#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_csv(csv).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+        assert_eq!(index.len(), 4);
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "id": "1",
+                "address": "Boston",
+                "address.city": "Boston",
+                "address.geo.lat": 42.36,
+            })
+        );
+    }
+
+    #[test]
+    fn conflicting_dotted_headers() {
+        let csv_content = r#"id,address,address.city
+1,"Boston","Boston""#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.set_csv_nested_headers(true);
+        let error = builder.append_csv(csv).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The CSV header `address` conflicts with the nested `address.city` header."
+        );
+    }
+
+    #[test]
+    fn duplicate_headers() {
+        let csv_content = r#"id,address.city,address.city:string
+1,"Boston","Cambridge""#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.set_csv_nested_headers(true);
+        let error = builder.append_csv(csv).unwrap_err();
+        assert_eq!(error.to_string(), "The CSV header `address.city` is defined more than once.");
+
+        let csv_content = r#"id,address,address
+1,"Boston","Cambridge""#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        let error = builder.append_csv(csv).unwrap_err();
+        assert_eq!(error.to_string(), "The CSV header `address` is defined more than once.");
+    }
+
+    #[test]
+    fn bad_json_in_field() {
+        let csv_content = r#"id,meta:json
+1,"{""size"": }""#;
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        assert!(matches!(builder.append_csv(csv), Err(Error::ParseJson { line: 1, .. })));
+    }
+
     #[test]
     fn bad_column_count1() {
         let csv_content = r#"city,country,pop
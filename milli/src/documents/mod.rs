@@ -8,7 +8,7 @@ use std::io;
 use std::str::Utf8Error;
 
 use bimap::BiHashMap;
-pub use builder::DocumentsBatchBuilder;
+pub use builder::{CsvEmptyCells, DocumentsBatchBuilder};
 pub use enriched::{EnrichedDocument, EnrichedDocumentsBatchCursor, EnrichedDocumentsBatchReader};
 use obkv::KvReader;
 pub use reader::{DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchReader};
@@ -93,6 +93,12 @@ pub enum Error {
     ParseFloat { error: std::num::ParseFloatError, line: usize, value: String },
     #[error("Error parsing boolean {value:?} at line {line}: {error}")]
     ParseBool { error: std::str::ParseBoolError, line: usize, value: String },
+    #[error("Error parsing JSON {value:?} at line {line}: {error}")]
+    ParseJson { error: serde_json::Error, line: usize, value: String },
+    #[error("The CSV header `{parent}` conflicts with the nested `{child}` header.")]
+    ConflictingCsvHeaders { parent: String, child: String },
+    #[error("The CSV header `{header}` is defined more than once.")]
+    DuplicateCsvHeader { header: String },
     #[error("Invalid document addition format, missing the documents batch index.")]
     InvalidDocumentFormat,
     #[error("Invalid enriched data.")]
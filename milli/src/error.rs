@@ -103,6 +103,20 @@ A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("Could not edit the document `{}`: {reason}.", .document_id.to_string())]
+    InvalidDocumentEdition { document_id: Value, reason: String },
+    #[error("{}", format_invalid_document_schema(.documents))]
+    InvalidDocumentSchema { documents: Vec<(Value, String)> },
+    #[error("Could not process the document `{}` with the ingest pipeline: {reason}.", .document_id.to_string())]
+    InvalidIngestPipeline { document_id: Value, reason: String },
+    #[error("Document `{}` has an invalid version: expected {expected}, but found `{}`.", .document_id.to_string(), .version.to_string())]
+    InvalidDocumentVersion { document_id: Value, version: Value, expected: &'static str },
+    #[error(
+        "The `versionCondition` parameter can only be used when the index has a version field."
+    )]
+    MissingVersionField,
+    #[error("{0}")]
+    InvalidBoost(String),
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
@@ -114,6 +128,26 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidFilterExpression(&'static [&'static str], Value),
+    #[error("Attribute `{}` is not filterable and thus cannot be used to group the search results. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+                ),
+        }
+    )]
+    InvalidGroupByAttribute { field: String, valid_fields: BTreeSet<String> },
+    #[error("Attribute `{}` cannot be used in the ranking rules of the search as it is not filterable, sortable or used in the ranking rules of the index. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have any attribute that can be used in the ranking rules.".to_string(),
+            false => format!("Available attributes are: `{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+                ),
+        }
+    )]
+    InvalidRankingRuleAttribute { field: String, valid_fields: BTreeSet<String> },
     #[error("Attribute `{}` is not sortable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -124,6 +158,16 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String> },
+    #[error("Synonym set `{}` does not exist and thus cannot be used to expand the search query. {}",
+        .set_name,
+        match .valid_sets.is_empty() {
+            true => "This index does not have any synonym set.".to_string(),
+            false => format!("Available synonym sets are: `{}`.",
+                    valid_sets.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+                ),
+        }
+    )]
+    InvalidSynonymSet { set_name: String, valid_sets: BTreeSet<String> },
     #[error("{}", HeedError::BadOpenOptions)]
     InvalidLmdbOpenOptions,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
@@ -136,6 +180,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     MissingDocumentId { primary_key: String, document: Object },
     #[error("Document have too many matching `{}` attribute: `{}`.", .primary_key, serde_json::to_string(.document).unwrap())]
     TooManyDocumentIds { primary_key: String, document: Object },
+    #[error("A search cannot have more than {1} boosts, but {0} boosts were given.")]
+    TooManyBoosts(usize, usize),
     #[error("The primary key inference failed as the engine did not find any field ending with `id` in its name. Please specify the primary key manually using the `primaryKey` query parameter.")]
     NoPrimaryKeyCandidateFound,
     #[error("The primary key inference failed as the engine found {} fields ending with `id` in their names: '{}' and '{}'. Please specify the primary key manually using the `primaryKey` query parameter.", .candidates.len(), .candidates.get(0).unwrap(), .candidates.get(1).unwrap())]
@@ -144,6 +190,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     NoSpaceLeftOnDevice,
     #[error("Index already has a primary key: `{0}`.")]
     PrimaryKeyCannotBeChanged(String),
+    #[error("The primary key `{0}` cannot be modified by a document edition.")]
+    PrimaryKeyCannotBeEdited(String),
     #[error(transparent)]
     SerdeJson(serde_json::Error),
     #[error(transparent)]
@@ -174,6 +222,35 @@ pub enum GeoError {
     BadLongitude { document_id: Value, value: Value },
 }
 
+fn format_invalid_document_schema(documents: &[(Value, String)]) -> String {
+    // Only the first invalid documents are listed to keep the message readable.
+    const MAX_LISTED_DOCUMENTS: usize = 10;
+
+    match documents {
+        [(document_id, reason)] => {
+            format!("Document `{document_id}` does not respect the document schema: {reason}.")
+        }
+        documents => {
+            let mut result =
+                format!("{} documents do not respect the document schema: ", documents.len());
+            for (i, (document_id, reason)) in
+                documents.iter().take(MAX_LISTED_DOCUMENTS).enumerate()
+            {
+                if i != 0 {
+                    result.push_str("; ");
+                }
+                write!(result, "document `{document_id}`: {reason}").unwrap();
+            }
+            if documents.len() > MAX_LISTED_DOCUMENTS {
+                let others = documents.len() - MAX_LISTED_DOCUMENTS;
+                write!(result, "; and {others} other documents").unwrap();
+            }
+            result.push('.');
+            result
+        }
+    }
+}
+
 fn format_invalid_filter_distribution(
     invalid_facets_name: &BTreeSet<String>,
     valid_facets_name: &BTreeSet<String>,
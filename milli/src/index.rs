@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::mem::size_of;
 use std::path::Path;
@@ -21,9 +21,11 @@ use crate::heed_codec::facet::{
 };
 use crate::heed_codec::{ScriptLanguageCodec, StrBEU16Codec, StrRefCodec};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec, GeoPoint, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, BEU16, BEU32,
+    default_criteria, CboRoaringBitmapCodec, Condition, Criterion, DocumentId, DocumentSchema,
+    ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec,
+    Filter, FilterCondition, GeoPoint, IngestProcessor, LocalizedAttributesRule, LocalizedFieldIds,
+    ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, StemmingLanguage,
+    SynonymsSelection, Token, U8StrStrCodec, BEU16, BEU32,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -51,8 +53,10 @@ pub mod main_key {
     pub const STOP_WORDS_KEY: &str = "stop-words";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
     pub const SYNONYMS_KEY: &str = "synonyms";
+    pub const SYNONYM_SETS_KEY: &str = "synonym-sets";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
+    pub const WORDS_INFIXES_FST_KEY: &str = "words-infixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
@@ -60,6 +64,13 @@ pub mod main_key {
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const INFIX_SEARCHABLE_FIELDS_KEY: &str = "infix-searchable-fields";
+    pub const STEMMING_LANGUAGES_KEY: &str = "stemming-languages";
+    pub const LOCALIZED_ATTRIBUTES_RULES_KEY: &str = "localized-attributes-rules";
+    pub const DOCUMENT_SCHEMA_KEY: &str = "document-schema";
+    pub const INGEST_PIPELINE_KEY: &str = "ingest-pipeline";
+    pub const EXPIRY_FIELD_KEY: &str = "expiry-field";
+    pub const VERSION_FIELD_KEY: &str = "version-field";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
 }
@@ -785,14 +796,18 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct,
+    /// expiry and Asc/Desc/ranking function fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
         let distinct_field = self.distinct_field(rtxn)?;
+        let expiry_field = self.expiry_field(rtxn)?;
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Asc(field) | Criterion::Desc(field) | Criterion::Function(field, _) => {
+                    Some(field)
+                }
                 _otherwise => None,
             });
 
@@ -802,6 +817,9 @@ impl Index {
         if let Some(field) = distinct_field {
             faceted_fields.insert(field.to_owned());
         }
+        if let Some(field) = expiry_field {
+            faceted_fields.insert(field.to_owned());
+        }
 
         Ok(faceted_fields)
     }
@@ -915,6 +933,66 @@ impl Index {
         self.main.delete::<_, Str>(wtxn, main_key::DISTINCT_FIELD_KEY)
     }
 
+    /* expiry field */
+
+    pub(crate) fn put_expiry_field(
+        &self,
+        wtxn: &mut RwTxn,
+        expiry_field: &str,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, Str>(wtxn, main_key::EXPIRY_FIELD_KEY, expiry_field)
+    }
+
+    /// Returns the field containing the Unix timestamp, in seconds, after which a document expires.
+    pub fn expiry_field<'a>(&self, rtxn: &'a RoTxn) -> heed::Result<Option<&'a str>> {
+        self.main.get::<_, Str, Str>(rtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    pub(crate) fn delete_expiry_field(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    /// Returns the documents whose expiry date is before or at the given date.
+    pub fn expired_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<RoaringBitmap> {
+        match self.expiry_field(rtxn)? {
+            Some(expiry_field) => {
+                let now = now.unix_timestamp().to_string();
+                let condition = FilterCondition::Condition {
+                    fid: Token::from(expiry_field),
+                    op: Condition::LowerThanOrEqual(Token::from(now.as_str())),
+                };
+                // The expiry field is faceted without having to be a filterable attribute.
+                let fields = HashSet::from([expiry_field.to_string()]);
+                Filter::from(condition).evaluate_with_filterable_fields(rtxn, self, &fields)
+            }
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
+    /* version field */
+
+    pub(crate) fn put_version_field(
+        &self,
+        wtxn: &mut RwTxn,
+        version_field: &str,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, Str>(wtxn, main_key::VERSION_FIELD_KEY, version_field)
+    }
+
+    /// Returns the field containing the version of the documents, a document is only
+    /// replaced or updated by a document with a greater version.
+    pub fn version_field<'a>(&self, rtxn: &'a RoTxn) -> heed::Result<Option<&'a str>> {
+        self.main.get::<_, Str, Str>(rtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    pub(crate) fn delete_version_field(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VERSION_FIELD_KEY)
+    }
+
     /* criteria */
 
     pub(crate) fn put_criteria(
@@ -1006,6 +1084,64 @@ impl Index {
         Ok(self.synonyms(rtxn)?.remove(&words))
     }
 
+    /* synonym sets */
+
+    pub(crate) fn put_synonym_sets(
+        &self,
+        wtxn: &mut RwTxn,
+        synonym_sets: &BTreeMap<String, HashMap<Vec<String>, Vec<Vec<String>>>>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SYNONYM_SETS_KEY, synonym_sets)
+    }
+
+    pub(crate) fn delete_synonym_sets(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SYNONYM_SETS_KEY)
+    }
+
+    /// Returns the named synonym sets of the index, the words are normalized.
+    pub fn synonym_sets(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<BTreeMap<String, HashMap<Vec<String>, Vec<Vec<String>>>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<_>>(rtxn, main_key::SYNONYM_SETS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the synonyms of the given selection, merging the synonyms of
+    /// the index with the ones of the selected sets. Unknown sets are ignored.
+    pub fn selected_synonyms(
+        &self,
+        rtxn: &RoTxn,
+        selection: &SynonymsSelection,
+    ) -> heed::Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+        let (mut synonyms, set_names) = match selection {
+            SynonymsSelection::None => return Ok(HashMap::new()),
+            SynonymsSelection::All => (self.synonyms(rtxn)?, None),
+            SynonymsSelection::Sets(set_names) => (HashMap::new(), Some(set_names)),
+        };
+
+        let mut synonym_sets = self.synonym_sets(rtxn)?;
+        if let Some(set_names) = set_names {
+            synonym_sets.retain(|name, _| set_names.contains(name));
+        }
+        if synonym_sets.is_empty() {
+            return Ok(synonyms);
+        }
+
+        for set in synonym_sets.into_values() {
+            for (words, alternatives) in set {
+                synonyms.entry(words).or_default().extend(alternatives);
+            }
+        }
+        for alternatives in synonyms.values_mut() {
+            alternatives.sort_unstable();
+            alternatives.dedup();
+        }
+        Ok(synonyms)
+    }
+
     /* words prefixes fst */
 
     /// Writes the FST which is the words prefixes dictionnary of the engine.
@@ -1029,6 +1165,31 @@ impl Index {
         }
     }
 
+    /* words infixes fst */
+
+    /// Writes the FST which is the words infixes dictionnary of the engine.
+    ///
+    /// Every entry is a suffix of a word followed by a `\0` and the word itself.
+    pub(crate) fn put_words_infixes_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::WORDS_INFIXES_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    /// Returns the FST which is the words infixes dictionnary of the engine.
+    pub fn words_infixes_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Set<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::WORDS_INFIXES_FST_KEY)? {
+            Some(bytes) => Ok(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Set::default().map_data(Cow::Owned)?),
+        }
+    }
+
     /* word documents count */
 
     /// Returns the number of documents ids associated with the given word,
@@ -1216,6 +1377,144 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /// Returns the infix searchable attributes: attributes in which a word can be
+    /// found from any substring of it.
+    pub fn infix_searchable_fields<'t>(&self, txn: &'t RoTxn) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<Vec<&str>>>(txn, main_key::INFIX_SEARCHABLE_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of infix searchable attributes field ids.
+    pub fn infix_searchable_fields_ids(&self, txn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attrs = self.infix_searchable_fields(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the infix searchable attributes to the database.
+    pub(crate) fn put_infix_searchable_fields(
+        &self,
+        txn: &mut RwTxn,
+        attrs: &[&str],
+    ) -> Result<()> {
+        self.main.put::<_, Str, SerdeBincode<&[&str]>>(
+            txn,
+            main_key::INFIX_SEARCHABLE_FIELDS_KEY,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the infix searchable attributes from the store.
+    pub(crate) fn delete_infix_searchable_fields(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::INFIX_SEARCHABLE_FIELDS_KEY)
+    }
+
+    /// Returns the languages whose words are indexed and searched along with their stem.
+    pub fn stemming_languages(&self, txn: &RoTxn) -> heed::Result<Vec<StemmingLanguage>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<StemmingLanguage>>>(txn, main_key::STEMMING_LANGUAGES_KEY)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_stemming_languages(
+        &self,
+        txn: &mut RwTxn,
+        languages: &[StemmingLanguage],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[StemmingLanguage]>>(
+            txn,
+            main_key::STEMMING_LANGUAGES_KEY,
+            &languages,
+        )
+    }
+
+    pub(crate) fn delete_stemming_languages(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::STEMMING_LANGUAGES_KEY)
+    }
+
+    /// Returns the rules declaring the locales of the attributes.
+    pub fn localized_attributes_rules(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main.get::<_, Str, SerdeJson<Vec<LocalizedAttributesRule>>>(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES_KEY,
+        )
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn,
+        rules: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[LocalizedAttributesRule]>>(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES_KEY,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::LOCALIZED_ATTRIBUTES_RULES_KEY)
+    }
+
+    /// Returns the locales declared for each field by the localized attributes rules.
+    pub fn localized_field_ids(&self, txn: &RoTxn) -> Result<LocalizedFieldIds> {
+        let rules = self.localized_attributes_rules(txn)?.unwrap_or_default();
+        let fields_ids_map = self.fields_ids_map(txn)?;
+        Ok(LocalizedFieldIds::new(&rules, &fields_ids_map))
+    }
+
+    /// Returns the schema the documents must respect to be indexed.
+    pub fn document_schema(&self, txn: &RoTxn) -> heed::Result<Option<DocumentSchema>> {
+        self.main.get::<_, Str, SerdeJson<DocumentSchema>>(txn, main_key::DOCUMENT_SCHEMA_KEY)
+    }
+
+    pub(crate) fn put_document_schema(
+        &self,
+        txn: &mut RwTxn,
+        schema: &DocumentSchema,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&DocumentSchema>>(
+            txn,
+            main_key::DOCUMENT_SCHEMA_KEY,
+            &schema,
+        )
+    }
+
+    pub(crate) fn delete_document_schema(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DOCUMENT_SCHEMA_KEY)
+    }
+
+    /// Returns the processors applied in order on the documents before they are indexed.
+    pub fn ingest_pipeline(&self, txn: &RoTxn) -> heed::Result<Vec<IngestProcessor>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<IngestProcessor>>>(txn, main_key::INGEST_PIPELINE_KEY)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_ingest_pipeline(
+        &self,
+        txn: &mut RwTxn,
+        processors: &[IngestProcessor],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[IngestProcessor]>>(
+            txn,
+            main_key::INGEST_PIPELINE_KEY,
+            &processors,
+        )
+    }
+
+    pub(crate) fn delete_ingest_pipeline(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::INGEST_PIPELINE_KEY)
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn) -> heed::Result<Option<usize>> {
         self.main.get::<_, Str, OwnedType<usize>>(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -2488,8 +2787,14 @@ pub(crate) mod tests {
 
         let rtxn = index.read_txn().unwrap();
         let search = Search::new(&rtxn, &index);
-        let SearchResult { matching_words: _, candidates: _, mut documents_ids } =
-            search.execute().unwrap();
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            mut documents_ids,
+            cursor: _,
+            groups: _,
+            trace: _,
+        } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
         let docs = index.documents(&rtxn, documents_ids).unwrap();
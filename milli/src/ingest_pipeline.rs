@@ -0,0 +1,249 @@
+use std::result::Result as StdResult;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime};
+
+use crate::Object;
+
+/// A processor of the ingest pipeline of an index, the processors are applied
+/// in order on the top-level fields of every document sent to the index.
+///
+/// The processors ignore the fields that are missing or `null` in the document.
+/// The partial updates are processed once merged with the stored document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+#[deserr(tag = "type", rename_all = camelCase, deny_unknown_fields)]
+pub enum IngestProcessor {
+    /// Joins the values of the fields into the target field, separated by a space by default.
+    Concatenate {
+        fields: Vec<String>,
+        target: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[deserr(default)]
+        separator: Option<String>,
+    },
+    /// Lowercases the string, or the strings of the array, of the field.
+    Lowercase { field: String },
+    /// Splits the string of the field into an array of trimmed and non-empty strings.
+    Split { field: String, separator: String },
+    /// Copies the value of the field into the target field.
+    Copy { field: String, target: String },
+    /// Moves the value of the field into the target field.
+    Rename { field: String, target: String },
+    /// Removes the field from the document.
+    Drop { field: String },
+    /// Extracts the year, month, day, hour, minute and weekday of the date of the field
+    /// into an object stored in the target field. The date is either an RFC 3339 string,
+    /// a `YYYY-MM-DD` string or a Unix timestamp in seconds.
+    DateParts { field: String, target: String },
+    /// Sets the field to the value when it is missing.
+    Default { field: String, value: Value },
+}
+
+impl IngestProcessor {
+    /// Applies the processor on the document and returns a description of
+    /// the problem when a field has not the expected type.
+    pub fn process(&self, document: &mut Object) -> StdResult<(), String> {
+        match self {
+            IngestProcessor::Concatenate { fields, target, separator } => {
+                let mut parts = Vec::new();
+                for field in fields {
+                    match document.get(field) {
+                        None | Some(Value::Null) => (),
+                        Some(Value::String(string)) => parts.push(string.clone()),
+                        Some(value @ (Value::Number(_) | Value::Bool(_))) => {
+                            parts.push(value.to_string())
+                        }
+                        Some(value) => {
+                            return Err(format!(
+                                "cannot concatenate the `{field}` field: `{value}`"
+                            ))
+                        }
+                    }
+                }
+                if !parts.is_empty() {
+                    let separator = separator.as_deref().unwrap_or(" ");
+                    document.insert(target.clone(), Value::String(parts.join(separator)));
+                }
+            }
+            IngestProcessor::Lowercase { field } => match document.get_mut(field) {
+                None | Some(Value::Null) => (),
+                Some(Value::String(string)) => *string = string.to_lowercase(),
+                Some(Value::Array(values)) => {
+                    for value in values {
+                        if let Value::String(string) = value {
+                            *string = string.to_lowercase();
+                        }
+                    }
+                }
+                Some(value) => {
+                    return Err(format!(
+                        "cannot lowercase the non-string `{field}` field: `{value}`"
+                    ))
+                }
+            },
+            IngestProcessor::Split { field, separator } => match document.get_mut(field) {
+                None | Some(Value::Null) | Some(Value::Array(_)) => (),
+                Some(Value::String(string)) => {
+                    let parts = string
+                        .split(separator.as_str())
+                        .map(str::trim)
+                        .filter(|part| !part.is_empty())
+                        .map(|part| Value::String(part.to_string()))
+                        .collect();
+                    document.insert(field.clone(), Value::Array(parts));
+                }
+                Some(value) => {
+                    return Err(format!("cannot split the non-string `{field}` field: `{value}`"))
+                }
+            },
+            IngestProcessor::Copy { field, target } => match document.get(field) {
+                None | Some(Value::Null) => (),
+                Some(value) => {
+                    let value = value.clone();
+                    document.insert(target.clone(), value);
+                }
+            },
+            IngestProcessor::Rename { field, target } => match document.remove(field) {
+                None | Some(Value::Null) => (),
+                Some(value) => {
+                    document.insert(target.clone(), value);
+                }
+            },
+            IngestProcessor::Drop { field } => {
+                document.remove(field);
+            }
+            IngestProcessor::DateParts { field, target } => match document.get(field) {
+                None | Some(Value::Null) => (),
+                Some(value) => {
+                    let date = parse_date(value).ok_or_else(|| {
+                        format!("cannot extract the date parts of the `{field}` field: `{value}`")
+                    })?;
+                    let parts = json!({
+                        "year": date.year(),
+                        "month": u8::from(date.month()),
+                        "day": date.day(),
+                        "hour": date.hour(),
+                        "minute": date.minute(),
+                        "weekday": date.weekday().to_string().to_lowercase(),
+                    });
+                    document.insert(target.clone(), parts);
+                }
+            },
+            IngestProcessor::Default { field, value } => match document.get(field) {
+                None | Some(Value::Null) => {
+                    document.insert(field.clone(), value.clone());
+                }
+                Some(_) => (),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_date(value: &Value) -> Option<OffsetDateTime> {
+    match value {
+        Value::String(string) => OffsetDateTime::parse(string, &Rfc3339).ok().or_else(|| {
+            let date = Date::parse(string, format_description!("[year]-[month]-[day]")).ok()?;
+            Some(date.midnight().assume_utc())
+        }),
+        Value::Number(number) => OffsetDateTime::from_unix_timestamp(number.as_i64()?).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(processors: Value, document: Value) -> StdResult<Value, String> {
+        let processors: Vec<IngestProcessor> = serde_json::from_value(processors).unwrap();
+        let mut document = document.as_object().unwrap().clone();
+        for processor in &processors {
+            processor.process(&mut document)?;
+        }
+        Ok(Value::Object(document))
+    }
+
+    #[test]
+    fn process_documents() {
+        let processors = json!([
+            { "type": "concatenate", "fields": ["first_name", "last_name"], "target": "full_name" },
+            { "type": "split", "field": "category_path", "separator": ">" },
+            { "type": "lowercase", "field": "category_path" },
+            { "type": "copy", "field": "sku", "target": "reference" },
+            { "type": "rename", "field": "desc", "target": "description" },
+            { "type": "drop", "field": "internal" },
+            { "type": "dateParts", "field": "released_at", "target": "released" },
+            { "type": "default", "field": "stock", "value": 0 },
+        ]);
+
+        let document = process(
+            processors.clone(),
+            json!({
+                "id": 1,
+                "first_name": "Kevin",
+                "last_name": "Durant",
+                "category_path": "Sports > Basketball > ",
+                "sku": "KD-35",
+                "desc": "Signature shoe",
+                "internal": true,
+                "released_at": "2022-10-07T18:30:00Z",
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            document,
+            json!({
+                "id": 1,
+                "first_name": "Kevin",
+                "last_name": "Durant",
+                "full_name": "Kevin Durant",
+                "category_path": ["sports", "basketball"],
+                "sku": "KD-35",
+                "reference": "KD-35",
+                "description": "Signature shoe",
+                "released_at": "2022-10-07T18:30:00Z",
+                "released": {
+                    "year": 2022,
+                    "month": 10,
+                    "day": 7,
+                    "hour": 18,
+                    "minute": 30,
+                    "weekday": "friday",
+                },
+                "stock": 0,
+            })
+        );
+
+        // The missing fields are ignored.
+        let document = process(processors, json!({ "id": 2, "stock": 3 })).unwrap();
+        assert_eq!(document, json!({ "id": 2, "stock": 3 }));
+    }
+
+    #[test]
+    fn invalid_processed_documents() {
+        let error = process(
+            json!([{ "type": "split", "field": "tags", "separator": "," }]),
+            json!({ "tags": 12 }),
+        )
+        .unwrap_err();
+        assert_eq!(error, "cannot split the non-string `tags` field: `12`");
+
+        let error = process(
+            json!([{ "type": "dateParts", "field": "date", "target": "parts" }]),
+            json!({ "date": "yesterday" }),
+        )
+        .unwrap_err();
+        assert_eq!(error, r#"cannot extract the date parts of the `date` field: `"yesterday"`"#);
+
+        let error =
+            serde_json::from_value::<IngestProcessor>(json!({ "type": "uppercase" })).unwrap_err();
+        insta::assert_display_snapshot!(error, @"unknown variant `uppercase`, expected one of `concatenate`, `lowercase`, `split`, `copy`, `rename`, `drop`, `dateParts`, `default`");
+    }
+}
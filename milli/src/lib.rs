@@ -10,14 +10,18 @@ pub mod documents;
 
 mod asc_desc;
 mod criterion;
+mod document_schema;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
 pub mod index;
+mod ingest_pipeline;
+mod localized_attributes_rules;
 pub mod proximity;
 mod search;
+mod stemming;
 pub mod update;
 
 #[cfg(test)]
@@ -33,14 +37,20 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, DefaultSearchLogger, GeoSortStrategy, SearchContext, SearchLogger,
-    VisualSearchLogger,
+    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
+    SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use {charabia as tokenizer, heed};
 
-pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::asc_desc::{
+    AscDesc, AscDescError, Collation, Member, MissingPosition, SortCriterion, SortError,
+    SortOptions,
+};
+pub use self::criterion::{
+    default_criteria, Criterion, CriterionError, DecayOrigin, FiniteNumber, RankingFunction,
+};
+pub use self::document_schema::{DocumentSchema, FieldSchema, FieldType};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -52,10 +62,17 @@ pub use self::heed_codec::{
     RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec, UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::ingest_pipeline::IngestProcessor;
+pub use self::localized_attributes_rules::{
+    locales_allow_list, LocalizedAttributesRule, LocalizedFieldIds,
+};
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, Search,
-    SearchResult, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    Boost, FacetDistribution, Filter, FormatOptions, InvalidSearchCursor, MatchBounds, MatchKind,
+    MatcherBuilder, MatchingWords, Search, SearchCursor, SearchGroup, SearchResult, SearchTrace,
+    Similar, SynonymsSelection, TermsMatchingStrategy, TraceEvent, TraceQueryNode,
+    TraceQueryNodeKind, TraceQueryTerm, DEFAULT_VALUES_PER_FACET,
 };
+pub use self::stemming::StemmingLanguage;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
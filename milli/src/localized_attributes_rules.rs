@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use charabia::{Language, Script, TokenizerBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{is_faceted_by, FieldId, FieldsIdsMap};
+
+/// A rule declaring the locales of the attributes matching one of its patterns.
+///
+/// The declared locales replace the automatic language detection of the tokenizer,
+/// both when indexing the matching attributes and when searching them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    pub attribute_patterns: Vec<String>,
+    #[serde(serialize_with = "serialize_locales", deserialize_with = "deserialize_locales")]
+    pub locales: Vec<Language>,
+}
+
+impl LocalizedAttributesRule {
+    pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
+        Self { attribute_patterns, locales }
+    }
+
+    /// Returns `true` if the attribute matches one of the patterns of the rule.
+    ///
+    /// A pattern can start or end with a `*` wildcard, the other patterns match
+    /// the attribute itself and all its nested fields.
+    pub fn match_str(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, attribute))
+    }
+
+    pub fn locales(&self) -> &[Language] {
+        &self.locales
+    }
+}
+
+fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    if pattern == "*" {
+        true
+    } else if let Some(suffix) = pattern.strip_prefix('*') {
+        attribute.ends_with(suffix)
+    } else if let Some(prefix) = pattern.strip_suffix('*') {
+        attribute.starts_with(prefix)
+    } else {
+        is_faceted_by(attribute, pattern)
+    }
+}
+
+fn serialize_locales<S: Serializer>(
+    locales: &[Language],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(locales.iter().map(Language::name))
+}
+
+fn deserialize_locales<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Language>, D::Error> {
+    let codes = Vec::<String>::deserialize(deserializer)?;
+    Ok(codes.iter().map(|code| Language::from_name(code.as_str())).collect())
+}
+
+/// The locales declared for each field of the index, the first matching rule wins.
+#[derive(Debug, Default, Clone)]
+pub struct LocalizedFieldIds {
+    field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+}
+
+impl LocalizedFieldIds {
+    pub fn new(rules: &[LocalizedAttributesRule], fields_ids_map: &FieldsIdsMap) -> Self {
+        let field_id_to_locales = fields_ids_map
+            .iter()
+            .filter_map(|(field_id, name)| {
+                let rule = rules.iter().find(|rule| rule.match_str(name))?;
+                Some((field_id, rule.locales.clone()))
+            })
+            .filter(|(_, locales)| !locales.is_empty())
+            .collect();
+
+        Self { field_id_to_locales }
+    }
+
+    pub fn locales(&self, field_id: FieldId) -> Option<&[Language]> {
+        self.field_id_to_locales.get(&field_id).map(Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.field_id_to_locales.is_empty()
+    }
+}
+
+/// Builds the allow list restricting the language detection of the tokenizer
+/// to the given locales for every script found in the text.
+pub fn locales_allow_list(text: &str, locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let tokenizer = TokenizerBuilder::default().build();
+    tokenizer
+        .tokenize(text)
+        .filter(|token| token.is_word())
+        .map(|token| (token.script, locales.to_vec()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule::new(
+            vec!["title".to_string(), "*_ja".to_string(), "tags.*".to_string()],
+            vec![Language::Jpn],
+        );
+
+        assert!(rule.match_str("title"));
+        assert!(rule.match_str("title.original"));
+        assert!(rule.match_str("description_ja"));
+        assert!(rule.match_str("tags.colors"));
+        assert!(!rule.match_str("subtitle"));
+        assert!(!rule.match_str("tags"));
+        assert!(!rule.match_str("description_en"));
+    }
+}
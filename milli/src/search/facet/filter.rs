@@ -223,11 +223,22 @@ impl<'a> Filter<'a> {
 impl<'a> Filter<'a> {
     pub fn evaluate(&self, rtxn: &heed::RoTxn, index: &Index) -> Result<RoaringBitmap> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
-        let soft_deleted_documents = index.soft_deleted_documents_ids(rtxn)?;
         let filterable_fields = index.filterable_fields(rtxn)?;
+        self.evaluate_with_filterable_fields(rtxn, index, &filterable_fields)
+    }
+
+    /// Evaluates the filter on the given fields, the internal filters can use
+    /// faceted fields that are not filterable attributes, like the expiry field.
+    pub(crate) fn evaluate_with_filterable_fields(
+        &self,
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        filterable_fields: &HashSet<String>,
+    ) -> Result<RoaringBitmap> {
+        let soft_deleted_documents = index.soft_deleted_documents_ids(rtxn)?;
 
         // and finally we delete all the soft_deleted_documents, again, only once at the very end
-        self.inner_evaluate(rtxn, index, &filterable_fields)
+        self.inner_evaluate(rtxn, index, filterable_fields)
             .map(|result| result - soft_deleted_documents)
     }
 
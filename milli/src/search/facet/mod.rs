@@ -5,8 +5,11 @@ use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
 pub use self::facet_distribution::{FacetDistribution, DEFAULT_VALUES_PER_FACET};
+pub(crate) use self::facet_range_search::find_docids_of_facet_within_bounds;
 pub use self::filter::{BadGeoError, Filter};
-use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
+use crate::heed_codec::facet::{
+    FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
+};
 use crate::heed_codec::ByteSliceRefCodec;
 use crate::{Index, Result};
 mod facet_distribution;
@@ -117,6 +120,29 @@ pub(crate) fn get_highest_level<'t>(
         .unwrap_or(0))
 }
 
+/// Get the documents having at least one value for the given field in the facet database
+pub(crate) fn get_faceted_documents_ids<'t>(
+    txn: &'t RoTxn<'t>,
+    db: heed::Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
+    field_id: u16,
+) -> heed::Result<RoaringBitmap> {
+    // The groups of the highest level cover all the values of the field
+    let highest_level = get_highest_level(txn, db, field_id)?;
+    let mut highest_level_prefix = vec![];
+    highest_level_prefix.extend_from_slice(&field_id.to_be_bytes());
+    highest_level_prefix.push(highest_level);
+
+    let mut docids = RoaringBitmap::new();
+    let iter = db
+        .as_polymorph()
+        .prefix_iter::<_, ByteSlice, FacetGroupValueCodec>(txn, highest_level_prefix.as_slice())?;
+    for result in iter {
+        let (_, FacetGroupValue { bitmap, .. }) = result?;
+        docids |= bitmap;
+    }
+    Ok(docids)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use rand::{Rng, SeedableRng};
@@ -1,16 +1,28 @@
 use std::fmt;
 
+use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
 pub use self::facet::{FacetDistribution, Filter, DEFAULT_VALUES_PER_FACET};
-pub use self::new::matches::{FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWords};
-use self::new::PartialSearchResult;
+pub use self::new::matches::{
+    FormatOptions, MatchBounds, MatchKind, Matcher, MatcherBuilder, MatchingWords,
+};
+pub use self::new::{
+    Boost, InvalidSearchCursor, SearchCursor, SearchGroup, SearchTrace, TraceEvent, TraceQueryNode,
+    TraceQueryNodeKind, TraceQueryTerm,
+};
+use self::new::{Grouper, PartialSearchResult, SearchTracer};
+pub use self::similar::Similar;
 use crate::{
-    execute_search, AscDesc, DefaultSearchLogger, DocumentId, Index, Result, SearchContext,
+    execute_search, filtered_universe, AscDesc, Criterion, DocumentId, Index, Result,
+    SearchContext, SortCriterion, UserError,
 };
 
+/// The number of documents ranked at once when grouping the search results.
+const GROUP_BY_CHUNK_SIZE: usize = 1000;
+
 // Building these factories is not free.
 static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
@@ -19,18 +31,27 @@ static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 pub mod facet;
 mod fst_utils;
 pub mod new;
+mod similar;
 
 pub struct Search<'a> {
     query: Option<String>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    boosts: Vec<Boost<'a>>,
+    random_seed: Option<u64>,
+    debug: bool,
     offset: usize,
     limit: usize,
-    sort_criteria: Option<Vec<AscDesc>>,
+    search_after: Option<SearchCursor>,
+    group_by: Option<(String, usize)>,
+    ranking_rules: Option<Vec<Criterion>>,
+    sort_criteria: Option<Vec<SortCriterion>>,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
+    locales: Option<Vec<Language>>,
+    synonyms: SynonymsSelection,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -40,13 +61,21 @@ impl<'a> Search<'a> {
         Search {
             query: None,
             filter: None,
+            boosts: Vec::new(),
+            random_seed: None,
+            debug: false,
             offset: 0,
             limit: 20,
+            search_after: None,
+            group_by: None,
+            ranking_rules: None,
             sort_criteria: None,
             geo_strategy: new::GeoSortStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
+            locales: None,
+            synonyms: SynonymsSelection::default(),
             rtxn,
             index,
         }
@@ -67,7 +96,35 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Only return the documents ranked after the given cursor, which is
+    /// the position of the last document of a previous search.
+    pub fn search_after(&mut self, cursor: SearchCursor) -> &mut Search<'a> {
+        self.search_after = Some(cursor);
+        self
+    }
+
+    /// Group the documents by the values of the given filterable field and return,
+    /// for each group, its best ranked documents. The offset and the limit then
+    /// apply to the groups instead of the documents, and the search-after cursor is ignored.
+    pub fn group_by(&mut self, field: String, hits_per_group: usize) -> &mut Search<'a> {
+        self.group_by = Some((field, hits_per_group));
+        self
+    }
+
+    /// Rank the documents with the given ranking rules instead of the ones of the index.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> &mut Search<'a> {
+        self.ranking_rules = Some(ranking_rules);
+        self
+    }
+
     pub fn sort_criteria(&mut self, criteria: Vec<AscDesc>) -> &mut Search<'a> {
+        self.sort_criteria = Some(criteria.into_iter().map(SortCriterion::from).collect());
+        self
+    }
+
+    /// Sort the documents like [`Search::sort_criteria`], with the given options telling
+    /// how the string values are compared and where the documents without values are ranked.
+    pub fn sort_criteria_with_options(&mut self, criteria: Vec<SortCriterion>) -> &mut Search<'a> {
         self.sort_criteria = Some(criteria);
         self
     }
@@ -87,6 +144,39 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Promote the documents matching the filters of the boosts inside of the buckets
+    /// of the ranking rules, without excluding the other documents.
+    pub fn boosts(&mut self, boosts: Vec<Boost<'a>>) -> &mut Search<'a> {
+        self.boosts = boosts;
+        self
+    }
+
+    /// Shuffle the documents ranked equally by the ranking rules and the boosts
+    /// in a deterministic order given by the seed.
+    pub fn random_seed(&mut self, seed: u64) -> &mut Search<'a> {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Record the trace of the execution of the ranking rules and return it in the result.
+    pub fn debug(&mut self, debug: bool) -> &mut Search<'a> {
+        self.debug = debug;
+        self
+    }
+
+    /// Tokenize the query with the given locales instead of the languages
+    /// detected in the documents of the index.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Expand the query with the synonyms of the given selection only.
+    pub fn synonyms(&mut self, selection: SynonymsSelection) -> &mut Search<'a> {
+        self.synonyms = selection;
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_strategy = strategy;
@@ -101,30 +191,140 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        if let SynonymsSelection::Sets(set_names) = &self.synonyms {
+            let synonym_sets = self.index.synonym_sets(self.rtxn)?;
+            if let Some(set_name) = set_names.iter().find(|name| !synonym_sets.contains_key(*name))
+            {
+                return Err(UserError::InvalidSynonymSet {
+                    set_name: set_name.to_string(),
+                    valid_sets: synonym_sets.into_keys().collect(),
+                }
+                .into());
+            }
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn);
-        let PartialSearchResult { located_query_terms, candidates, documents_ids } =
+        ctx.synonyms = self.synonyms.clone();
+        let universe = filtered_universe(&ctx, &self.filter)?;
+        if let Some((field, hits_per_group)) = &self.group_by {
+            return self.execute_group_by(ctx, universe, field, *hits_per_group);
+        }
+
+        let mut tracer = SearchTracer::new(self.debug);
+        let (placeholder_logger, query_graph_logger) = tracer.loggers();
+        let PartialSearchResult { located_query_terms, candidates, documents_ids, cursor } =
             execute_search(
                 &mut ctx,
                 &self.query,
                 self.terms_matching_strategy,
                 self.exhaustive_number_hits,
-                &self.filter,
+                universe,
+                &self.ranking_rules,
                 &self.sort_criteria,
                 self.geo_strategy,
+                &self.boosts,
+                self.random_seed,
                 self.offset,
                 self.limit,
+                self.search_after.as_ref(),
+                Some(self.words_limit),
+                self.locales.as_deref(),
+                placeholder_logger,
+                query_graph_logger,
+            )?;
+        let trace = tracer.finish(&mut ctx)?;
+
+        // consume context and located_query_terms to build MatchingWords.
+        let matching_words = match located_query_terms {
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms)?,
+            None => MatchingWords::default(),
+        };
+
+        Ok(SearchResult { matching_words, candidates, documents_ids, cursor, groups: None, trace })
+    }
+
+    fn execute_group_by(
+        &self,
+        mut ctx: SearchContext,
+        universe: RoaringBitmap,
+        field: &str,
+        hits_per_group: usize,
+    ) -> Result<SearchResult> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        if !crate::is_faceted(field, &filterable_fields) {
+            return Err(UserError::InvalidGroupByAttribute {
+                field: field.to_string(),
+                valid_fields: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+        let field_id = self.index.fields_ids_map(self.rtxn)?.id(field);
+
+        // The documents are ranked chunk by chunk until the groups of the requested page are full.
+        let mut grouper: Option<Grouper> = None;
+        let mut cursor = None;
+        // The trace records the ranking of every chunk.
+        let mut tracer = SearchTracer::new(self.debug);
+        let (located_query_terms, candidates, grouper) = loop {
+            let (placeholder_logger, query_graph_logger) = tracer.loggers();
+            let PartialSearchResult {
+                located_query_terms,
+                candidates,
+                documents_ids,
+                cursor: next_cursor,
+            } = execute_search(
+                &mut ctx,
+                &self.query,
+                self.terms_matching_strategy,
+                self.exhaustive_number_hits,
+                universe.clone(),
+                &self.ranking_rules,
+                &self.sort_criteria,
+                self.geo_strategy,
+                &self.boosts,
+                self.random_seed,
+                0,
+                GROUP_BY_CHUNK_SIZE,
+                cursor.as_ref(),
                 Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
+                self.locales.as_deref(),
+                placeholder_logger,
+                query_graph_logger,
             )?;
 
+            let mut current_grouper = grouper.take().unwrap_or_else(|| {
+                Grouper::new(field_id, self.offset, self.limit, hits_per_group, candidates.clone())
+            });
+            for &docid in &documents_ids {
+                current_grouper.push(ctx.index, ctx.txn, docid)?;
+            }
+
+            if documents_ids.len() < GROUP_BY_CHUNK_SIZE || current_grouper.is_complete() {
+                break (located_query_terms, candidates, current_grouper);
+            }
+            grouper = Some(current_grouper);
+            cursor = next_cursor;
+        };
+
+        let trace = tracer.finish(&mut ctx)?;
+        let groups = grouper.into_groups();
+        let documents_ids =
+            groups.iter().flat_map(|group| group.documents_ids.iter().copied()).collect();
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
-            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms)?,
             None => MatchingWords::default(),
         };
 
-        Ok(SearchResult { matching_words, candidates, documents_ids })
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            documents_ids,
+            cursor: None,
+            groups: Some(groups),
+            trace,
+        })
     }
 }
 
@@ -133,25 +333,41 @@ impl fmt::Debug for Search<'_> {
         let Search {
             query,
             filter,
+            boosts,
+            random_seed,
+            debug,
             offset,
             limit,
+            search_after,
+            group_by,
+            ranking_rules,
             sort_criteria,
             geo_strategy: _,
             terms_matching_strategy,
             words_limit,
             exhaustive_number_hits,
+            locales,
+            synonyms,
             rtxn: _,
             index: _,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
             .field("filter", filter)
+            .field("boosts", boosts)
+            .field("random_seed", random_seed)
+            .field("debug", debug)
             .field("offset", offset)
             .field("limit", limit)
+            .field("search_after", search_after)
+            .field("group_by", group_by)
+            .field("ranking_rules", ranking_rules)
             .field("sort_criteria", sort_criteria)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
+            .field("locales", locales)
+            .field("synonyms", synonyms)
             .finish()
     }
 }
@@ -162,6 +378,12 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     // TODO those documents ids should be associated with their criteria scores.
     pub documents_ids: Vec<DocumentId>,
+    /// The position of the last returned document, to resume the search after it.
+    pub cursor: Option<SearchCursor>,
+    /// The groups of documents, when the search results are grouped.
+    pub groups: Option<Vec<SearchGroup>>,
+    /// The trace of the execution of the ranking rules, when it is requested.
+    pub trace: Option<SearchTrace>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -178,6 +400,18 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+/// The synonyms used to expand the words of the query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SynonymsSelection {
+    /// The synonyms of the index and all its named synonym sets.
+    #[default]
+    All,
+    /// The query is not expanded with synonyms.
+    None,
+    /// The given named synonym sets only.
+    Sets(Vec<String>),
+}
+
 fn get_first(s: &str) -> &str {
     match s.chars().next() {
         Some(c) => &s[..c.len_utf8()],
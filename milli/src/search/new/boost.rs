@@ -0,0 +1,145 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{limits, RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::{Error, Filter, Result, UserError};
+
+/// A filter promoting the documents matching it by the given weight, without excluding the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boost<'a> {
+    pub filter: Filter<'a>,
+    pub weight: f64,
+}
+
+/// A ranking rule that orders the documents by the sum of the weights of the boosts they match.
+///
+/// It is placed after all the other ranking rules so that it only reorders the documents
+/// inside of the buckets of the relevancy ranking rules.
+pub struct Boosting<Query> {
+    boosts: Vec<(RoaringBitmap, f64)>,
+    original_query: Option<Query>,
+    buckets: std::vec::IntoIter<RoaringBitmap>,
+}
+
+impl<Query> Boosting<Query> {
+    pub fn new(ctx: &SearchContext, boosts: &[Boost]) -> Result<Self> {
+        if boosts.len() > limits::MAX_BOOST_COUNT {
+            return Err(UserError::TooManyBoosts(boosts.len(), limits::MAX_BOOST_COUNT).into());
+        }
+
+        let boosts = boosts
+            .iter()
+            .map(|Boost { filter, weight }| match filter.evaluate(ctx.txn, ctx.index) {
+                Ok(docids) => Ok((docids, *weight)),
+                Err(Error::UserError(UserError::InvalidFilter(message))) => {
+                    Err(UserError::InvalidBoost(message).into())
+                }
+                Err(e) => Err(e),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { boosts, original_query: None, buckets: Vec::new().into_iter() })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Boosting<Query> {
+    fn id(&self) -> String {
+        "boost".to_owned()
+    }
+
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        query: &Query,
+    ) -> Result<()> {
+        self.original_query = Some(query.clone());
+        self.buckets = boosted_buckets(universe, &self.boosts).into_iter();
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        let candidates = match self.buckets.next() {
+            Some(bucket) => bucket & universe,
+            None => universe.clone(),
+        };
+        Ok(Some(RankingRuleOutput { query, candidates }))
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = Vec::new().into_iter();
+    }
+}
+
+/// Split the universe into buckets of documents with the same total weight,
+/// sorted by decreasing weight.
+fn boosted_buckets(
+    universe: &RoaringBitmap,
+    boosts: &[(RoaringBitmap, f64)],
+) -> Vec<RoaringBitmap> {
+    let mut buckets = vec![(0.0, universe.clone())];
+    for (docids, weight) in boosts {
+        buckets = buckets
+            .into_iter()
+            .flat_map(|(score, candidates)| {
+                let boosted = &candidates & docids;
+                [(score + weight, boosted), (score, candidates - docids)]
+            })
+            .filter(|(_, candidates)| !candidates.is_empty())
+            .collect();
+    }
+
+    buckets.sort_by(|(left, _), (right, _)| f64::total_cmp(right, left));
+
+    let mut merged: Vec<(f64, RoaringBitmap)> = Vec::with_capacity(buckets.len());
+    for (score, candidates) in buckets {
+        match merged.last_mut() {
+            Some((last_score, last_candidates)) if *last_score == score => {
+                *last_candidates |= candidates
+            }
+            _ => merged.push((score, candidates)),
+        }
+    }
+
+    merged.into_iter().map(|(_, candidates)| candidates).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use roaring::RoaringBitmap;
+
+    use super::boosted_buckets;
+
+    #[test]
+    fn split_universe_by_weight() {
+        let universe: RoaringBitmap = (0..10).collect();
+        let boosts: [(RoaringBitmap, f64); 4] = [
+            ((0..3).collect(), 1.0),
+            ((2..6).collect(), 2.0),
+            ((5..7).collect(), 1.0),
+            ((8..9).collect(), -1.0),
+        ];
+        let buckets = boosted_buckets(&universe, &boosts);
+        insta::assert_debug_snapshot!(buckets, @r###"
+        [
+            RoaringBitmap<[2, 5]>,
+            RoaringBitmap<[3, 4]>,
+            RoaringBitmap<[0, 1, 6]>,
+            RoaringBitmap<[7, 9]>,
+            RoaringBitmap<[8]>,
+        ]
+        "###);
+    }
+}
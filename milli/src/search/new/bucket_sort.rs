@@ -1,5 +1,8 @@
+use std::ops::Bound;
+
 use roaring::RoaringBitmap;
 
+use super::cursor::{BucketPosition, SearchCursor};
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
@@ -9,6 +12,8 @@ use crate::Result;
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
     pub all_candidates: RoaringBitmap,
+    /// The position of the last returned document, if any.
+    pub cursor: Option<SearchCursor>,
 }
 
 pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
@@ -18,6 +23,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     universe: &RoaringBitmap,
     from: usize,
     length: usize,
+    search_after: Option<&SearchCursor>,
     logger: &mut dyn SearchLogger<Q>,
 ) -> Result<BucketSortOutput> {
     logger.initial_query(query);
@@ -31,11 +37,22 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     };
 
     if universe.len() < from as u64 {
-        return Ok(BucketSortOutput { docids: vec![], all_candidates: universe.clone() });
+        return Ok(BucketSortOutput {
+            docids: vec![],
+            all_candidates: universe.clone(),
+            cursor: None,
+        });
     }
     if ranking_rules.is_empty() {
+        // Without any ranking rule, the documents are only ordered by their ids.
+        let is_before_cursor =
+            |docid: u32| search_after.map_or(false, |cursor| docid <= cursor.docid);
+        let to_cursor =
+            |docids: &[u32]| docids.last().map(|&docid| SearchCursor { buckets: vec![], docid });
+
         if let Some(distinct_fid) = distinct_fid {
             let mut excluded = RoaringBitmap::new();
+            let mut skipped = vec![];
             let mut results = vec![];
             for docid in universe.iter() {
                 if results.len() >= from + length {
@@ -45,14 +62,26 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     continue;
                 }
                 distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
-                results.push(docid);
+                if is_before_cursor(docid) {
+                    skipped.push(docid);
+                } else {
+                    results.push(docid);
+                }
             }
             let mut all_candidates = universe - excluded;
+            all_candidates.extend(skipped.iter().copied());
             all_candidates.extend(results.iter().copied());
-            return Ok(BucketSortOutput { docids: results, all_candidates });
+            let cursor = to_cursor(&results);
+            return Ok(BucketSortOutput { docids: results, all_candidates, cursor });
         } else {
-            let docids = universe.iter().skip(from).take(length).collect();
-            return Ok(BucketSortOutput { docids, all_candidates: universe.clone() });
+            let docids: Vec<_> = universe
+                .iter()
+                .skip_while(|&docid| is_before_cursor(docid))
+                .skip(from)
+                .take(length)
+                .collect();
+            let cursor = to_cursor(&docids);
+            return Ok(BucketSortOutput { docids, all_candidates: universe.clone(), cursor });
         };
     }
 
@@ -66,6 +95,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     ranking_rule_universes[0] = universe.clone();
 
     let mut cur_ranking_rule_index = 0;
+    // The index of the next bucket to be returned by each ranking rule,
+    // which is used to locate the buckets relative to the cursors.
+    let mut next_bucket_indexes: Vec<u32> = vec![0; ranking_rules_len];
 
     /// Finish iterating over the current ranking rule, yielding
     /// control to the parent (or finishing the search if not possible).
@@ -95,9 +127,25 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     let mut all_candidates = universe.clone();
     let mut valid_docids = vec![];
     let mut cur_offset = 0usize;
+    let mut cursor = None;
+
+    macro_rules! skip_before_cursor {
+        ($candidates:expr, $path:expr) => {
+            skip_before_cursor(
+                ctx,
+                search_after,
+                $path,
+                distinct_fid,
+                &mut ranking_rule_universes,
+                &mut all_candidates,
+                $candidates,
+            )?
+        };
+    }
 
     macro_rules! maybe_add_to_results {
-        ($candidates:expr) => {
+        ($candidates:expr, $path:expr) => {
+            let results_len = valid_docids.len();
             maybe_add_to_results(
                 ctx,
                 from,
@@ -112,6 +160,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 distinct_fid,
                 $candidates,
             )?;
+            if valid_docids.len() > results_len {
+                cursor = valid_docids.last().map(|&docid| SearchCursor { buckets: $path, docid });
+            }
         };
     }
 
@@ -120,7 +171,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         // anything, just extend the results and go back to the parent ranking rule.
         if ranking_rule_universes[cur_ranking_rule_index].len() <= 1 {
             let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
-            maybe_add_to_results!(bucket);
+            let path = bucket_path(&next_bucket_indexes, cur_ranking_rule_index);
+            let bucket = skip_before_cursor!(bucket, &path);
+            maybe_add_to_results!(bucket, path);
             back!();
             continue;
         }
@@ -142,15 +195,23 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         );
         ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
 
+        let path = bucket_path(&next_bucket_indexes, cur_ranking_rule_index);
+        next_bucket_indexes[cur_ranking_rule_index] += 1;
+        let position = search_after.map(|cursor| cursor.locate(&path));
+
         if cur_ranking_rule_index == ranking_rules_len - 1
             || next_bucket.candidates.len() <= 1
-            || cur_offset + (next_bucket.candidates.len() as usize) < from
+            || position == Some(BucketPosition::Before)
+            || (position != Some(BucketPosition::Ancestor)
+                && cur_offset + (next_bucket.candidates.len() as usize) < from)
         {
-            maybe_add_to_results!(next_bucket.candidates);
+            let candidates = skip_before_cursor!(next_bucket.candidates, &path);
+            maybe_add_to_results!(candidates, path);
             continue;
         }
 
         cur_ranking_rule_index += 1;
+        next_bucket_indexes[cur_ranking_rule_index] = 0;
         ranking_rule_universes[cur_ranking_rule_index] = next_bucket.candidates.clone();
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
@@ -166,7 +227,54 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         )?;
     }
 
-    Ok(BucketSortOutput { docids: valid_docids, all_candidates })
+    Ok(BucketSortOutput { docids: valid_docids, all_candidates, cursor })
+}
+
+/// Return the indexes of the buckets leading to the next bucket of the given ranking rule.
+fn bucket_path(next_bucket_indexes: &[u32], ranking_rule_index: usize) -> Vec<u32> {
+    let (parents, next) = next_bucket_indexes[..=ranking_rule_index].split_at(ranking_rule_index);
+    // the parents already returned the bucket being sorted by their children
+    parents.iter().map(|index| index - 1).chain(next.iter().copied()).collect()
+}
+
+/// Remove the candidates ranked before the `search_after` cursor from the given bucket.
+///
+/// The removed candidates are still used to apply the distinct rule, so that the documents
+/// returned after a cursor are the ones that would have been returned by paginating with an offset.
+fn skip_before_cursor(
+    ctx: &mut SearchContext,
+    search_after: Option<&SearchCursor>,
+    path: &[u32],
+    distinct_fid: Option<u16>,
+    ranking_rule_universes: &mut [RoaringBitmap],
+    all_candidates: &mut RoaringBitmap,
+    candidates: RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    let Some(search_after) = search_after else { return Ok(candidates) };
+
+    let skipped = match search_after.locate(path) {
+        BucketPosition::Before => candidates.clone(),
+        BucketPosition::Ancestor | BucketPosition::Same => {
+            let mut skipped = candidates.clone();
+            skipped.remove_range((Bound::Excluded(search_after.docid), Bound::Unbounded));
+            skipped
+        }
+        BucketPosition::After => return Ok(candidates),
+    };
+
+    let remaining = candidates - &skipped;
+    if let Some(distinct_fid) = distinct_fid {
+        let DistinctOutput { remaining: kept, excluded } =
+            apply_distinct_rule(ctx, distinct_fid, &skipped)?;
+        for universe in ranking_rule_universes.iter_mut() {
+            *universe -= &excluded;
+        }
+        *all_candidates -= &excluded;
+        *all_candidates |= kept;
+        Ok(remaining - excluded)
+    } else {
+        Ok(remaining)
+    }
 }
 
 /// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::DocumentId;
+
+/// The number of hexadecimal characters used to encode each number of a cursor.
+const ENCODED_NUMBER_LEN: usize = 8;
+
+/// The position of a document in the ranking of a search.
+///
+/// It is made of the index of the bucket the document was found in for each ranking
+/// rule that was iterated to reach it, and of its document id, which breaks the ties
+/// inside the last bucket. Resuming a search after a cursor is only consistent if the
+/// query, the settings and the documents of the index didn't change in between.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchCursor {
+    pub(crate) buckets: Vec<u32>,
+    pub(crate) docid: DocumentId,
+}
+
+#[derive(Error, Debug)]
+#[error("`{0}` is not a valid search cursor.")]
+pub struct InvalidSearchCursor(String);
+
+/// Where a bucket of the bucket sort is located relative to a [`SearchCursor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BucketPosition {
+    /// All the documents of the bucket are ranked before the cursor.
+    Before,
+    /// The bucket contains the bucket of the cursor, it must be sorted further.
+    Ancestor,
+    /// The bucket is the one of the cursor, only the documents with a greater
+    /// document id are ranked after the cursor.
+    Same,
+    /// All the documents of the bucket are ranked after the cursor.
+    After,
+}
+
+impl SearchCursor {
+    /// Locate the bucket reached by following the given bucket indexes, one per ranking rule.
+    pub(crate) fn locate(&self, path: &[u32]) -> BucketPosition {
+        for (bucket, cursor_bucket) in path.iter().zip(&self.buckets) {
+            match bucket.cmp(cursor_bucket) {
+                Ordering::Less => return BucketPosition::Before,
+                Ordering::Greater => return BucketPosition::After,
+                Ordering::Equal => (),
+            }
+        }
+
+        if path.len() < self.buckets.len() {
+            BucketPosition::Ancestor
+        } else {
+            BucketPosition::Same
+        }
+    }
+}
+
+impl fmt::Display for SearchCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for number in std::iter::once(&self.docid).chain(&self.buckets) {
+            write!(f, "{number:08x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SearchCursor {
+    type Err = InvalidSearchCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidSearchCursor(s.to_string());
+
+        if s.is_empty()
+            || s.len() % ENCODED_NUMBER_LEN != 0
+            || !s.bytes().all(|byte| byte.is_ascii_hexdigit())
+        {
+            return Err(invalid());
+        }
+
+        let mut numbers = s
+            .as_bytes()
+            .chunks(ENCODED_NUMBER_LEN)
+            // the string only contains ascii hexadecimal digits
+            .map(|chunk| u32::from_str_radix(std::str::from_utf8(chunk).unwrap(), 16));
+
+        let docid = numbers.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+        let buckets = numbers.collect::<Result<_, _>>().map_err(|_| invalid())?;
+
+        Ok(SearchCursor { buckets, docid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_cursor() {
+        let cursor = SearchCursor { buckets: vec![0, 3, 250], docid: 42 };
+        let encoded = cursor.to_string();
+        insta::assert_snapshot!(encoded, @"0000002a0000000000000003000000fa");
+        assert_eq!(encoded.parse::<SearchCursor>().unwrap(), cursor);
+
+        let cursor = SearchCursor { buckets: vec![], docid: u32::MAX };
+        let encoded = cursor.to_string();
+        insta::assert_snapshot!(encoded, @"ffffffff");
+        assert_eq!(encoded.parse::<SearchCursor>().unwrap(), cursor);
+
+        assert!("".parse::<SearchCursor>().is_err());
+        assert!("0000002".parse::<SearchCursor>().is_err());
+        assert!("0000002g".parse::<SearchCursor>().is_err());
+        assert!("+000002a".parse::<SearchCursor>().is_err());
+    }
+
+    #[test]
+    fn locate_bucket() {
+        let cursor = SearchCursor { buckets: vec![1, 2], docid: 42 };
+        assert_eq!(cursor.locate(&[0]), BucketPosition::Before);
+        assert_eq!(cursor.locate(&[1, 1]), BucketPosition::Before);
+        assert_eq!(cursor.locate(&[1]), BucketPosition::Ancestor);
+        assert_eq!(cursor.locate(&[1, 2]), BucketPosition::Same);
+        assert_eq!(cursor.locate(&[1, 3]), BucketPosition::After);
+        assert_eq!(cursor.locate(&[2]), BucketPosition::After);
+    }
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::hash::Hash;
 
 use fxhash::FxHashMap;
@@ -10,6 +11,7 @@ use roaring::RoaringBitmap;
 use super::interner::Interned;
 use super::Word;
 use crate::heed_codec::StrBEU16Codec;
+use crate::stemming::Stemmers;
 use crate::{
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Result, RoaringBitmapCodec, SearchContext,
 };
@@ -42,6 +44,12 @@ pub struct DatabaseCache<'ctx> {
     pub word_prefix_fid_docids: FxHashMap<(Interned<String>, u16), Option<&'ctx [u8]>>,
     pub word_fids: FxHashMap<Interned<String>, Vec<u16>>,
     pub word_prefix_fids: FxHashMap<Interned<String>, Vec<u16>>,
+
+    pub infix_fids: Option<Vec<u16>>,
+
+    pub synonyms: Option<HashMap<Vec<String>, Vec<Vec<String>>>>,
+
+    pub stemmers: Option<Stemmers>,
 }
 impl<'ctx> DatabaseCache<'ctx> {
     fn get_value<'v, K1, KC>(
@@ -77,6 +85,49 @@ impl<'ctx> SearchContext<'ctx> {
         }
     }
 
+    /// Returns the alternatives of the given words in the synonyms selected for the search.
+    pub fn get_synonyms(&mut self, words: &[String]) -> Result<Vec<Vec<String>>> {
+        if self.db_cache.synonyms.is_none() {
+            let synonyms = self.index.selected_synonyms(self.txn, &self.synonyms)?;
+            self.db_cache.synonyms = Some(synonyms);
+        }
+        let synonyms = self.db_cache.synonyms.as_ref().and_then(|synonyms| synonyms.get(words));
+        Ok(synonyms.cloned().unwrap_or_default())
+    }
+
+    /// Returns the stems of the given word in the stemming languages of the index.
+    pub fn get_stems(&mut self, word: &str) -> Result<Vec<String>> {
+        if self.db_cache.stemmers.is_none() {
+            let languages = self.index.stemming_languages(self.txn)?;
+            self.db_cache.stemmers = Some(Stemmers::new(&languages));
+        }
+        Ok(self.db_cache.stemmers.as_ref().map(|stemmers| stemmers.stems(word)).unwrap_or_default())
+    }
+
+    /// Returns the sorted ids of the infix searchable fields.
+    pub fn get_infix_fids(&mut self) -> Result<Vec<u16>> {
+        if let Some(fids) = self.db_cache.infix_fids.clone() {
+            Ok(fids)
+        } else {
+            let mut fids: Vec<_> =
+                self.index.infix_searchable_fields_ids(self.txn)?.into_iter().collect();
+            fids.sort_unstable();
+            self.db_cache.infix_fids = Some(fids.clone());
+            Ok(fids)
+        }
+    }
+
+    /// Returns the documents containing the given word in one of the infix searchable fields.
+    pub fn get_db_word_infix_docids(&mut self, word: Interned<String>) -> Result<RoaringBitmap> {
+        let mut docids = RoaringBitmap::new();
+        for fid in self.get_infix_fids()? {
+            if let Some(word_fid_docids) = self.get_db_word_fid_docids(word, fid)? {
+                docids |= word_fid_docids;
+            }
+        }
+        Ok(docids)
+    }
+
     pub fn word_docids(&mut self, word: Word) -> Result<Option<RoaringBitmap>> {
         match word {
             Word::Original(word) => {
@@ -70,7 +70,7 @@ pub fn distinct_single_docid(
 }
 
 /// Return all the docids containing the given value in the given field
-fn facet_value_docids(
+pub(super) fn facet_value_docids(
     database: Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
     txn: &RoTxn,
     field_id: u16,
@@ -82,7 +82,7 @@ fn facet_value_docids(
 }
 
 /// Return an iterator over each number value in the given field of the given document.
-fn facet_number_values<'a>(
+pub(super) fn facet_number_values<'a>(
     docid: u32,
     field_id: u16,
     index: &Index,
@@ -100,7 +100,7 @@ fn facet_number_values<'a>(
 }
 
 /// Return an iterator over each string value in the given field of the given document.
-fn facet_string_values<'a>(
+pub(super) fn facet_string_values<'a>(
     docid: u32,
     field_id: u16,
     index: &Index,
@@ -0,0 +1,154 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use heed::{BytesDecode, RoTxn};
+use roaring::RoaringBitmap;
+use serde_json::{Number, Value};
+
+use super::distinct::{facet_number_values, facet_string_values, facet_value_docids};
+use crate::heed_codec::facet::OrderedF64Codec;
+use crate::{DocumentId, FieldId, Index, Result};
+
+/// A group of documents sharing the same value for the field used to group the search results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchGroup {
+    /// The value shared by all the documents of the group.
+    pub value: Value,
+    /// The best ranked documents of the group, in ranking order.
+    pub documents_ids: Vec<DocumentId>,
+    /// The number of documents of the group matching the search.
+    pub total: u64,
+}
+
+/// Dispatch the ranked documents of a search into the groups of their values.
+///
+/// The groups are ordered by their best ranked document. Only the groups of the
+/// requested page keep their documents, the previous ones are only tracked to know
+/// where the page starts.
+pub struct Grouper {
+    field_id: Option<FieldId>,
+    offset: usize,
+    limit: usize,
+    hits_per_group: usize,
+    candidates: RoaringBitmap,
+    groups: Vec<SearchGroup>,
+    // the facet values are identified by their type and their key in the facet databases
+    group_indexes: HashMap<(FacetType, Vec<u8>), usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum FacetType {
+    String,
+    Number,
+}
+
+impl Grouper {
+    pub fn new(
+        field_id: Option<FieldId>,
+        offset: usize,
+        limit: usize,
+        hits_per_group: usize,
+        candidates: RoaringBitmap,
+    ) -> Self {
+        Grouper {
+            field_id,
+            offset,
+            limit,
+            hits_per_group,
+            candidates,
+            groups: Vec::new(),
+            group_indexes: HashMap::new(),
+        }
+    }
+
+    /// Add the document to the groups of its values.
+    ///
+    /// The documents must be pushed in ranking order.
+    pub fn push(&mut self, index: &Index, txn: &RoTxn, docid: DocumentId) -> Result<()> {
+        let Some(field_id) = self.field_id else { return Ok(()) };
+
+        for item in facet_string_values(docid, field_id, index, txn)? {
+            let ((_, _, key), original) = item?;
+            self.push_into_group(index, txn, field_id, docid, FacetType::String, key, || {
+                Value::String(original.to_owned())
+            })?;
+        }
+
+        for item in facet_number_values(docid, field_id, index, txn)? {
+            let ((_, _, key), ()) = item?;
+            self.push_into_group(index, txn, field_id, docid, FacetType::Number, key, || {
+                OrderedF64Codec::bytes_decode(key)
+                    .and_then(Number::from_f64)
+                    .map_or(Value::Null, Value::Number)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_into_group(
+        &mut self,
+        index: &Index,
+        txn: &RoTxn,
+        field_id: FieldId,
+        docid: DocumentId,
+        facet_type: FacetType,
+        key: &[u8],
+        value: impl FnOnce() -> Value,
+    ) -> Result<()> {
+        let position = match self.group_indexes.entry((facet_type, key.to_vec())) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                // the groups after the requested page are never returned
+                if self.groups.len() >= self.offset + self.limit {
+                    return Ok(());
+                }
+
+                let docids = match facet_type {
+                    FacetType::String => facet_value_docids(
+                        index.facet_id_string_docids.remap_types(),
+                        txn,
+                        field_id,
+                        key,
+                    )?,
+                    FacetType::Number => facet_value_docids(
+                        index.facet_id_f64_docids.remap_types(),
+                        txn,
+                        field_id,
+                        key,
+                    )?,
+                };
+                let total = docids.map_or(0, |docids| docids.intersection_len(&self.candidates));
+
+                self.groups.push(SearchGroup { value: value(), documents_ids: Vec::new(), total });
+                *entry.insert(self.groups.len() - 1)
+            }
+        };
+
+        let group = &mut self.groups[position];
+        if position >= self.offset && group.documents_ids.len() < self.hits_per_group {
+            group.documents_ids.push(docid);
+        }
+
+        Ok(())
+    }
+
+    /// Whether all the groups of the requested page are known and hold
+    /// as many documents as they can.
+    pub fn is_complete(&self) -> bool {
+        if self.field_id.is_none() {
+            return true;
+        }
+
+        self.groups.len() >= self.offset + self.limit
+            && self.groups[self.offset..].iter().all(|group| {
+                group.documents_ids.len() as u64 >= group.total.min(self.hits_per_group as u64)
+            })
+    }
+
+    /// Return the groups of the requested page.
+    pub fn into_groups(self) -> Vec<SearchGroup> {
+        self.groups.into_iter().skip(self.offset).collect()
+    }
+}
@@ -7,6 +7,8 @@ pub const MAX_PREFIX_COUNT: usize = 1_000;
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
 pub const MAX_TWO_TYPOS_COUNT: usize = 50;
+/// Maximum number of words that can be derived from a single word by finding it inside of them.
+pub const MAX_INFIX_COUNT: usize = 100;
 
 /// Maximum amount of synonym phrases that can be derived from a single word.
 pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
@@ -15,3 +17,13 @@ pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 ///
 /// This limit is meant to gracefully handle the case where a word would have very long phrases as synonyms.
 pub const MAX_SYNONYM_WORD_COUNT: usize = 100;
+
+/// Maximum number of boosts in a single search.
+///
+/// Every boost can split each bucket of documents in two, this limit bounds the number of buckets.
+pub const MAX_BOOST_COUNT: usize = 10;
+
+/// Maximum number of ranking rule events recorded by the trace of a single search.
+///
+/// A search ranking many documents iterates many times over its ranking rules, this limit bounds the size of its trace.
+pub const MAX_TRACE_EVENT_COUNT: usize = 1_000;
@@ -1,3 +1,4 @@
+pub mod trace;
 // #[cfg(test)]
 pub mod visual;
 
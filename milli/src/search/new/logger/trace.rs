@@ -0,0 +1,290 @@
+use std::any::Any;
+
+use roaring::RoaringBitmap;
+use serde::Serialize;
+
+use super::{DefaultSearchLogger, SearchLogger};
+use crate::search::new::limits;
+use crate::search::new::query_graph::QueryNodeData;
+use crate::search::new::ranking_rules::{BoxRankingRule, PlaceholderQuery, RankingRuleQueryTrait};
+use crate::search::new::{QueryGraph, RankingRule, SearchContext};
+use crate::Result;
+
+/// The trace of the execution of a search, returned to debug its relevancy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTrace {
+    /// The query graph built from the terms of the query, if it is not a placeholder search.
+    pub query_graph: Option<Vec<TraceQueryNode>>,
+    /// The query graph used to compute the documents matching the query.
+    pub query_graph_for_universe: Option<Vec<TraceQueryNode>>,
+    /// The number of documents matching the query and the filters.
+    pub universe_len: u64,
+    /// The ids of the ranking rules, in the order they were applied.
+    pub ranking_rules: Vec<String>,
+    /// Every iteration of the ranking rules, in the order they happened.
+    pub events: Vec<TraceEvent>,
+    /// The number of events that were not recorded because the trace was full.
+    pub dropped_events: u64,
+}
+
+/// A node of a query graph with the derivations of its term.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceQueryNode {
+    pub id: u16,
+    pub kind: TraceQueryNodeKind,
+    pub term: Option<TraceQueryTerm>,
+    pub successors: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceQueryNodeKind {
+    Start,
+    End,
+    Term,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceQueryTerm {
+    /// The word of the query the term was built from.
+    pub original: String,
+    /// The positions of the words of the query covered by the term.
+    pub positions: (u16, u16),
+    pub max_typos: u8,
+    /// The words derived from the term, including the typos, synonyms and ngrams.
+    pub words: Vec<String>,
+    pub phrases: Vec<String>,
+    /// The prefix, when the documents are found using the prefix databases.
+    pub prefix_db: Option<String>,
+}
+
+/// An action of a ranking rule, identified by its index in the ranking rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TraceEvent {
+    #[serde(rename_all = "camelCase")]
+    StartIteration { ranking_rule: usize, universe_len: u64 },
+    #[serde(rename_all = "camelCase")]
+    NextBucket { ranking_rule: usize, universe_len: u64, bucket_len: u64 },
+    #[serde(rename_all = "camelCase")]
+    SkipBucket { ranking_rule: usize, bucket_len: u64 },
+    #[serde(rename_all = "camelCase")]
+    EndIteration { ranking_rule: usize, universe_len: u64 },
+    #[serde(rename_all = "camelCase")]
+    AddToResults { documents_len: usize },
+}
+
+/// A [`SearchLogger`] recording a [`SearchTrace`].
+///
+/// When the search is executed several times, like when grouping its results chunk by chunk,
+/// the query and the universe of the first execution are kept and the events are appended.
+#[derive(Default)]
+pub struct TraceSearchLogger {
+    initial_query: Option<QueryGraph>,
+    query_for_universe: Option<QueryGraph>,
+    universe_len: Option<u64>,
+    ranking_rules: Vec<String>,
+    events: Vec<TraceEvent>,
+    dropped_events: u64,
+}
+
+impl TraceSearchLogger {
+    fn push_event(&mut self, event: TraceEvent) {
+        if self.events.len() < limits::MAX_TRACE_EVENT_COUNT {
+            self.events.push(event);
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for TraceSearchLogger {
+    fn initial_query(&mut self, query: &Q) {
+        if self.initial_query.is_none() {
+            let query: &dyn Any = query;
+            self.initial_query = query.downcast_ref::<QueryGraph>().cloned();
+        }
+    }
+
+    fn initial_universe(&mut self, universe: &RoaringBitmap) {
+        self.universe_len.get_or_insert(universe.len());
+    }
+
+    fn query_for_initial_universe(&mut self, query: &Q) {
+        if self.query_for_universe.is_none() {
+            let query: &dyn Any = query;
+            self.query_for_universe = query.downcast_ref::<QueryGraph>().cloned();
+        }
+    }
+
+    fn ranking_rules(&mut self, rr: &[BoxRankingRule<Q>]) {
+        if self.ranking_rules.is_empty() {
+            self.ranking_rules = rr.iter().map(|rr| rr.id()).collect();
+        }
+    }
+
+    fn start_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        _query: &Q,
+        universe: &RoaringBitmap,
+    ) {
+        self.push_event(TraceEvent::StartIteration {
+            ranking_rule: ranking_rule_idx,
+            universe_len: universe.len(),
+        });
+    }
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        self.push_event(TraceEvent::NextBucket {
+            ranking_rule: ranking_rule_idx,
+            universe_len: universe.len(),
+            bucket_len: candidates.len(),
+        });
+    }
+
+    fn skip_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        candidates: &RoaringBitmap,
+    ) {
+        self.push_event(TraceEvent::SkipBucket {
+            ranking_rule: ranking_rule_idx,
+            bucket_len: candidates.len(),
+        });
+    }
+
+    fn end_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        universe: &RoaringBitmap,
+    ) {
+        self.push_event(TraceEvent::EndIteration {
+            ranking_rule: ranking_rule_idx,
+            universe_len: universe.len(),
+        });
+    }
+
+    fn add_to_results(&mut self, docids: &[u32]) {
+        if !docids.is_empty() {
+            self.push_event(TraceEvent::AddToResults { documents_len: docids.len() });
+        }
+    }
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
+
+impl TraceSearchLogger {
+    /// Resolve the interned terms of the recorded query graphs into a [`SearchTrace`].
+    pub fn finish(self, ctx: &mut SearchContext) -> Result<SearchTrace> {
+        let query_graph = self.initial_query.map(|qg| trace_query_graph(ctx, &qg)).transpose()?;
+        let query_graph_for_universe =
+            self.query_for_universe.map(|qg| trace_query_graph(ctx, &qg)).transpose()?;
+
+        Ok(SearchTrace {
+            query_graph,
+            query_graph_for_universe,
+            universe_len: self.universe_len.unwrap_or_default(),
+            ranking_rules: self.ranking_rules,
+            events: self.events,
+            dropped_events: self.dropped_events,
+        })
+    }
+}
+
+fn trace_query_graph(ctx: &mut SearchContext, qg: &QueryGraph) -> Result<Vec<TraceQueryNode>> {
+    let mut nodes = Vec::new();
+    for (node_id, node) in qg.nodes.iter() {
+        let (kind, term) = match &node.data {
+            QueryNodeData::Deleted => continue,
+            QueryNodeData::Start => (TraceQueryNodeKind::Start, None),
+            QueryNodeData::End => (TraceQueryNodeKind::End, None),
+            QueryNodeData::Term(located_term) => {
+                let term_subset = &located_term.term_subset;
+                let infix_words = term_subset.infix_words(ctx)?;
+                let words = term_subset
+                    .all_single_words_except_prefix_db(ctx)?
+                    .into_iter()
+                    .map(|w| w.interned())
+                    .chain(infix_words)
+                    .map(|w| ctx.word_interner.get(w).to_owned())
+                    .collect();
+                let phrases =
+                    term_subset.all_phrases(ctx)?.into_iter().map(|p| p.description(ctx)).collect();
+                let prefix_db = term_subset
+                    .use_prefix_db(ctx)
+                    .map(|w| ctx.word_interner.get(w.interned()).to_owned());
+
+                let term = TraceQueryTerm {
+                    original: term_subset.description(ctx),
+                    positions: (*located_term.positions.start(), *located_term.positions.end()),
+                    max_typos: term_subset.max_typo_cost(ctx),
+                    words,
+                    phrases,
+                    prefix_db,
+                };
+                (TraceQueryNodeKind::Term, Some(term))
+            }
+        };
+
+        nodes.push(TraceQueryNode {
+            id: node_id.into_raw(),
+            kind,
+            term,
+            successors: node.successors.iter().map(|s| s.into_raw()).collect(),
+        });
+    }
+    Ok(nodes)
+}
+
+/// The loggers given to [`execute_search`](crate::execute_search), recording a [`SearchTrace`]
+/// only when it is enabled.
+///
+/// A search only uses one of the two loggers, depending on whether it is a placeholder search.
+pub enum SearchTracer {
+    Disabled(DefaultSearchLogger, DefaultSearchLogger),
+    Enabled(TraceSearchLogger, TraceSearchLogger),
+}
+
+impl SearchTracer {
+    pub fn new(enabled: bool) -> Self {
+        if enabled {
+            SearchTracer::Enabled(TraceSearchLogger::default(), TraceSearchLogger::default())
+        } else {
+            SearchTracer::Disabled(DefaultSearchLogger, DefaultSearchLogger)
+        }
+    }
+
+    pub fn loggers(
+        &mut self,
+    ) -> (&mut dyn SearchLogger<PlaceholderQuery>, &mut dyn SearchLogger<QueryGraph>) {
+        match self {
+            SearchTracer::Disabled(placeholder, query_graph) => (placeholder, query_graph),
+            SearchTracer::Enabled(placeholder, query_graph) => (placeholder, query_graph),
+        }
+    }
+
+    pub fn finish(self, ctx: &mut SearchContext) -> Result<Option<SearchTrace>> {
+        match self {
+            SearchTracer::Disabled(..) => Ok(None),
+            SearchTracer::Enabled(placeholder, query_graph) => {
+                // the query graph logger is not used when the query has no terms
+                let logger =
+                    if query_graph.initial_query.is_some() { query_graph } else { placeholder };
+                logger.finish(ctx).map(Some)
+            }
+        }
+    }
+}
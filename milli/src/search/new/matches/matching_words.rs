@@ -3,19 +3,22 @@ use std::fmt;
 use std::ops::RangeInclusive;
 
 use charabia::Token;
+use serde::Serialize;
 
 use super::super::interner::Interned;
 use super::super::query_term::LocatedQueryTerm;
 use super::super::{DedupInterner, Phrase};
-use crate::SearchContext;
+use crate::stemming::Stemmers;
+use crate::{Result, SearchContext};
 
 pub struct LocatedMatchingPhrase {
     pub value: Interned<Phrase>,
+    pub kind: MatchKind,
     pub positions: RangeInclusive<WordId>,
 }
 
 pub struct LocatedMatchingWords {
-    pub value: Vec<Interned<String>>,
+    pub value: Vec<(Interned<String>, MatchKind)>,
     pub positions: RangeInclusive<WordId>,
     pub is_prefix: bool,
     pub original_char_count: usize,
@@ -29,10 +32,11 @@ pub struct MatchingWords {
     phrase_interner: DedupInterner<Phrase>,
     phrases: Vec<LocatedMatchingPhrase>,
     words: Vec<LocatedMatchingWords>,
+    stemmers: Stemmers,
 }
 
 impl MatchingWords {
-    pub fn new(ctx: SearchContext, located_terms: Vec<LocatedQueryTerm>) -> Self {
+    pub fn new(ctx: SearchContext, located_terms: Vec<LocatedQueryTerm>) -> Result<Self> {
         let mut phrases = Vec::new();
         let mut words = Vec::new();
 
@@ -42,9 +46,10 @@ impl MatchingWords {
             let term = ctx.term_interner.get(located_term.value);
             let (matching_words, matching_phrases) = term.all_computed_derivations();
 
-            for matching_phrase in matching_phrases {
+            for (matching_phrase, kind) in matching_phrases {
                 phrases.push(LocatedMatchingPhrase {
                     value: matching_phrase,
+                    kind,
                     positions: located_term.positions.clone(),
                 });
             }
@@ -60,12 +65,20 @@ impl MatchingWords {
         // Sort word to put prefixes at the bottom prioritizing the exact matches.
         words.sort_unstable_by_key(|lmw| (lmw.is_prefix, Reverse(lmw.positions.len())));
 
-        Self {
+        // The stems of the tokens are compared to the stems of the query,
+        // the stemmers built during the search are reused when there are some.
+        let stemmers = match ctx.db_cache.stemmers {
+            Some(stemmers) => stemmers,
+            None => Stemmers::new(&ctx.index.stemming_languages(ctx.txn)?),
+        };
+
+        Ok(Self {
             phrases,
             words,
+            stemmers,
             word_interner: ctx.word_interner,
             phrase_interner: ctx.phrase_interner,
-        }
+        })
     }
 
     /// Returns an iterator over terms that match or partially match the given token.
@@ -75,9 +88,11 @@ impl MatchingWords {
 
     /// Try to match the token with one of the located_words.
     fn match_unique_words<'a>(&'a self, token: &Token) -> Option<MatchType<'a>> {
+        // the stems of the token are only computed if the query has stems to match.
+        let mut token_stems = None;
         for located_words in &self.words {
-            for word in &located_words.value {
-                let word = self.word_interner.get(*word);
+            for &(word, kind) in &located_words.value {
+                let word = self.word_interner.get(word);
                 // if the word is a prefix we match using starts_with.
                 if located_words.is_prefix && token.lemma().starts_with(word) {
                     let Some((char_index, c)) = word.char_indices().take(located_words.original_char_count).last() else {
@@ -86,12 +101,26 @@ impl MatchingWords {
                     let prefix_length = char_index + c.len_utf8();
                     let char_len = token.original_lengths(prefix_length).0;
                     let ids = &located_words.positions;
-                    return Some(MatchType::Full { char_len, ids });
+                    // an exact word only matching the start of the token is a prefix match.
+                    let kind = match kind {
+                        MatchKind::Exact if token.lemma() != word => MatchKind::Prefix,
+                        kind => kind,
+                    };
+                    return Some(MatchType::Full { char_len, ids, kind });
                 // else we exact match the token.
                 } else if token.lemma() == word {
                     let char_len = token.char_end - token.char_start;
                     let ids = &located_words.positions;
-                    return Some(MatchType::Full { char_len, ids });
+                    return Some(MatchType::Full { char_len, ids, kind });
+                // else we match the stems of the token.
+                } else if kind == MatchKind::Stem {
+                    let token_stems =
+                        token_stems.get_or_insert_with(|| self.stemmers.stems(token.lemma()));
+                    if token_stems.iter().any(|stem| stem == word) {
+                        let char_len = token.char_end - token.char_start;
+                        let ids = &located_words.positions;
+                        return Some(MatchType::Full { char_len, ids, kind });
+                    }
                 }
             }
         }
@@ -120,6 +149,7 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                 // create a PartialMatch struct to make it compute the first match
                 // instead of duplicating the code.
                 let ids = &located_phrase.positions;
+                let kind = located_phrase.kind;
                 // collect the references of words from the interner.
                 let words = phrase
                     .words
@@ -128,7 +158,7 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                         word.map(|word| self.matching_words.word_interner.get(word).as_str())
                     })
                     .collect();
-                let partial = PartialMatch { matching_words: words, ids, char_len: 0 };
+                let partial = PartialMatch { matching_words: words, ids, kind, char_len: 0 };
 
                 partial.match_token(self.token).or_else(|| self.next())
             }
@@ -141,13 +171,31 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
 /// Id of a matching term corespounding to a word written by the end user.
 pub type WordId = u16;
 
+/// The derivation of a query term that produced a match.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchKind {
+    /// The query word or phrase itself.
+    Exact,
+    /// A word starting with the query word.
+    Prefix,
+    /// A word containing typos, or the query word split into several words.
+    Typo,
+    /// A synonym of the query word or phrase.
+    Synonym,
+    /// A word sharing its stem with the query word.
+    Stem,
+    /// A word of an infix searchable attribute containing the query word.
+    Infix,
+}
+
 /// A given token can partially match a query word for several reasons:
 /// - split words
 /// - multi-word synonyms
 /// In these cases we need to match consecutively several tokens to consider that the match is full.
 #[derive(Debug, PartialEq)]
 pub enum MatchType<'a> {
-    Full { char_len: usize, ids: &'a RangeInclusive<WordId> },
+    Full { char_len: usize, ids: &'a RangeInclusive<WordId>, kind: MatchKind },
     Partial(PartialMatch<'a>),
 }
 
@@ -156,6 +204,7 @@ pub enum MatchType<'a> {
 pub struct PartialMatch<'a> {
     matching_words: Vec<Option<&'a str>>,
     ids: &'a RangeInclusive<WordId>,
+    kind: MatchKind,
     char_len: usize,
 }
 
@@ -165,7 +214,7 @@ impl<'a> PartialMatch<'a> {
     /// - Partial if the given token matches the partial match but doesn't complete it
     /// - Full if the given token completes the partial match
     pub fn match_token(self, token: &Token) -> Option<MatchType<'a>> {
-        let Self { mut matching_words, ids, .. } = self;
+        let Self { mut matching_words, ids, kind, .. } = self;
 
         let is_matching = match matching_words.first()? {
             Some(word) => &token.lemma() == word,
@@ -179,11 +228,11 @@ impl<'a> PartialMatch<'a> {
         // return a new Partial match allowing the highlighter to continue.
         if is_matching && matching_words.len() > 1 {
             matching_words.remove(0);
-            Some(MatchType::Partial(PartialMatch { matching_words, ids, char_len }))
+            Some(MatchType::Partial(PartialMatch { matching_words, ids, kind, char_len }))
         // if there is no remaining word to match in the phrase and the current token is matching,
         // return a Full match.
         } else if is_matching {
-            Some(MatchType::Full { char_len, ids })
+            Some(MatchType::Full { char_len, ids, kind })
         // if the current token doesn't match, return None to break the match sequence.
         } else {
             None
@@ -197,7 +246,7 @@ impl<'a> PartialMatch<'a> {
 
 impl fmt::Debug for MatchingWords {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let MatchingWords { word_interner, phrase_interner, phrases, words } = self;
+        let MatchingWords { word_interner, phrase_interner, phrases, words, stemmers: _ } = self;
 
         let phrases: Vec<_> = phrases
             .iter()
@@ -220,7 +269,7 @@ impl fmt::Debug for MatchingWords {
             .flat_map(|w| {
                 w.value
                     .iter()
-                    .map(|s| (word_interner.get(*s), w.positions.clone(), w.is_prefix))
+                    .map(|(s, _)| (word_interner.get(*s), w.positions.clone(), w.is_prefix))
                     .collect::<Vec<_>>()
             })
             .collect();
@@ -259,7 +308,7 @@ pub(crate) mod tests {
         let tokenizer = TokenizerBuilder::new().build();
         let tokens = tokenizer.tokenize("split this world");
         let query_terms = located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
-        let matching_words = MatchingWords::new(ctx, query_terms);
+        let matching_words = MatchingWords::new(ctx, query_terms).unwrap();
 
         assert_eq!(
             matching_words
@@ -271,7 +320,7 @@ pub(crate) mod tests {
                     ..Default::default()
                 })
                 .next(),
-            Some(MatchType::Full { char_len: 5, ids: &(0..=0) })
+            Some(MatchType::Full { char_len: 5, ids: &(0..=0), kind: MatchKind::Exact })
         );
         assert_eq!(
             matching_words
@@ -295,7 +344,7 @@ pub(crate) mod tests {
                     ..Default::default()
                 })
                 .next(),
-            Some(MatchType::Full { char_len: 5, ids: &(2..=2) })
+            Some(MatchType::Full { char_len: 5, ids: &(2..=2), kind: MatchKind::Exact })
         );
         assert_eq!(
             matching_words
@@ -307,7 +356,7 @@ pub(crate) mod tests {
                     ..Default::default()
                 })
                 .next(),
-            Some(MatchType::Full { char_len: 5, ids: &(2..=2) })
+            Some(MatchType::Full { char_len: 5, ids: &(2..=2), kind: MatchKind::Prefix })
         );
         assert_eq!(
             matching_words
@@ -1,13 +1,14 @@
 use std::borrow::Cow;
 
 use charabia::{SeparatorKind, Token, Tokenizer};
-pub use matching_words::MatchingWords;
+pub use matching_words::{MatchKind, MatchingWords};
 use matching_words::{MatchType, PartialMatch, WordId};
 use serde::Serialize;
 
 pub mod matching_words;
 
 const DEFAULT_CROP_MARKER: &str = "…";
+const DEFAULT_CROP_SNIPPETS: usize = 1;
 const DEFAULT_HIGHLIGHT_PREFIX: &str = "<em>";
 const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
 
@@ -16,6 +17,7 @@ pub struct MatcherBuilder<'a, A> {
     matching_words: MatchingWords,
     tokenizer: Tokenizer<'a, 'a, A>,
     crop_marker: Option<String>,
+    crop_snippets: Option<usize>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
 }
@@ -26,6 +28,7 @@ impl<'a, A> MatcherBuilder<'a, A> {
             matching_words,
             tokenizer,
             crop_marker: None,
+            crop_snippets: None,
             highlight_prefix: None,
             highlight_suffix: None,
         }
@@ -36,6 +39,13 @@ impl<'a, A> MatcherBuilder<'a, A> {
         self
     }
 
+    /// Set the maximum number of crop windows returned when cropping a text,
+    /// the windows are joined with the crop marker.
+    pub fn crop_snippets(&mut self, snippets: usize) -> &Self {
+        self.crop_snippets = Some(snippets);
+        self
+    }
+
     pub fn highlight_prefix(&mut self, prefix: String) -> &Self {
         self.highlight_prefix = Some(prefix);
         self
@@ -65,6 +75,7 @@ impl<'a, A> MatcherBuilder<'a, A> {
             matching_words: &self.matching_words,
             tokenizer: &self.tokenizer,
             crop_marker,
+            crop_snippets: self.crop_snippets.unwrap_or(DEFAULT_CROP_SNIPPETS),
             highlight_prefix,
             highlight_suffix,
             matches: None,
@@ -89,6 +100,8 @@ pub struct Match {
     match_len: usize,
     // ids of the query words that matches.
     ids: Vec<WordId>,
+    // derivation of the query words that matches.
+    kind: MatchKind,
     // position of the word in the whole text.
     word_position: usize,
     // position of the token in the whole text.
@@ -96,9 +109,13 @@ pub struct Match {
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct MatchBounds {
     pub start: usize,
     pub length: usize,
+    /// The position in the query of the first word the match corresponds to.
+    pub query_term_index: WordId,
+    pub kind: MatchKind,
 }
 
 /// Structure used to analize a string, compute words that match,
@@ -108,6 +125,7 @@ pub struct Matcher<'t, 'm, A> {
     matching_words: &'m MatchingWords,
     tokenizer: &'m Tokenizer<'m, 'm, A>,
     crop_marker: &'m str,
+    crop_snippets: usize,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
@@ -136,13 +154,14 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         partial
                     }
                     // partial match is now full, we keep this matches and we advance positions
-                    Some(MatchType::Full { char_len, ids }) => {
+                    Some(MatchType::Full { char_len, ids, kind }) => {
                         let ids: Vec<_> = ids.clone().collect();
                         // save previously matched tokens as matches.
                         let iter = potential_matches.into_iter().map(
                             |(token_position, word_position, match_len)| Match {
                                 match_len,
                                 ids: ids.clone(),
+                                kind,
                                 word_position,
                                 token_position,
                             },
@@ -153,6 +172,7 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         matches.push(Match {
                             match_len: char_len,
                             ids,
+                            kind,
                             word_position,
                             token_position,
                         });
@@ -191,11 +211,12 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                 match match_type {
                     // we match, we save the current token as a match,
                     // then we continue the rest of the tokens.
-                    MatchType::Full { char_len, ids } => {
+                    MatchType::Full { char_len, ids, kind } => {
                         let ids: Vec<_> = ids.clone().collect();
                         matches.push(Match {
                             match_len: char_len,
                             ids,
+                            kind,
                             word_position,
                             token_position,
                         });
@@ -234,6 +255,8 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                 .map(|m| MatchBounds {
                     start: tokens[m.token_position].byte_start,
                     length: m.match_len,
+                    query_term_index: m.ids.first().copied().unwrap_or_default(),
+                    kind: m.kind,
                 })
                 .collect(),
         }
@@ -410,6 +433,69 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
         }
     }
 
+    /// Returns up to `crop_snippets` crop windows that don't overlap, in the order they appear in the text,
+    /// with the matches interval they are built around and their bounds in byte index.
+    fn crop_windows<'a>(
+        &self,
+        tokens: &[Token],
+        matches: &'a [Match],
+        crop_size: usize,
+    ) -> Vec<(&'a [Match], usize, usize)> {
+        if self.crop_snippets <= 1 || matches.is_empty() {
+            let matches = self.find_best_match_interval(matches, crop_size);
+            let (byte_start, byte_end) = self.crop_bounds(tokens, matches, crop_size);
+            return vec![(matches, byte_start, byte_end)];
+        }
+
+        let mut windows: Vec<(&[Match], usize, usize)> = Vec::new();
+        // the sequences of matches that are not in a crop window yet.
+        let mut segments = vec![matches];
+        while windows.len() < self.crop_snippets {
+            // find the best matches interval among all the segments, the first one wins the ties.
+            let best = segments
+                .iter()
+                .enumerate()
+                .filter(|(_, segment)| !segment.is_empty())
+                .map(|(index, segment)| {
+                    let interval = self.find_best_match_interval(segment, crop_size);
+                    (self.match_interval_score(interval), index, interval)
+                })
+                .max_by(|(score, index, _), (other_score, other_index, _)| {
+                    score.cmp(other_score).then(other_index.cmp(index))
+                });
+            let Some((_, index, interval)) = best else { break };
+
+            let (byte_start, byte_end) = self.crop_bounds(tokens, interval, crop_size);
+            // the window is dropped if it overlaps a better one,
+            // its matches are already partially shown.
+            if windows.iter().all(|&(_, start, end)| byte_end <= start || end <= byte_start) {
+                windows.push((interval, byte_start, byte_end));
+            }
+
+            // split the segment around the window, removing the matches it contains.
+            // The matches of the interval are always removed, even if the window doesn't contain them,
+            // so that the same interval can't be chosen again.
+            let segment = segments.remove(index);
+            let interval_start =
+                segment.iter().position(|m| std::ptr::eq(m, &interval[0])).unwrap();
+            let before = segment
+                .iter()
+                .take_while(|m| tokens[m.token_position].byte_end <= byte_start)
+                .count()
+                .min(interval_start);
+            let after = segment
+                .iter()
+                .position(|m| tokens[m.token_position].byte_start >= byte_end)
+                .unwrap_or(segment.len())
+                .max(interval_start + interval.len());
+            segments.insert(index, &segment[after..]);
+            segments.insert(index, &segment[..before]);
+        }
+
+        windows.sort_unstable_by_key(|&(_, byte_start, _)| byte_start);
+        windows
+    }
+
     // Returns the formatted version of the original text.
     pub fn format(&mut self, format_options: FormatOptions) -> Cow<'t, str> {
         if !format_options.highlight && format_options.crop.is_none() {
@@ -419,73 +505,72 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
             match &self.matches {
                 Some((tokens, matches)) => {
                     // If the text has to be cropped,
-                    // compute the best interval to crop around.
-                    let matches = match format_options.crop {
+                    // crop around the best intervals.
+                    let windows = match format_options.crop {
                         Some(crop_size) if crop_size > 0 => {
-                            self.find_best_match_interval(matches, crop_size)
+                            self.crop_windows(tokens, matches, crop_size)
                         }
-                        _ => matches,
-                    };
-
-                    // If the text has to be cropped,
-                    // crop around the best interval.
-                    let (byte_start, byte_end) = match format_options.crop {
-                        Some(crop_size) if crop_size > 0 => {
-                            self.crop_bounds(tokens, matches, crop_size)
-                        }
-                        _ => (0, self.text.len()),
+                        _ => vec![(matches.as_slice(), 0, self.text.len())],
                     };
 
                     let mut formatted = Vec::new();
 
-                    // push crop marker if it's not the start of the text.
-                    if byte_start > 0 && !self.crop_marker.is_empty() {
-                        formatted.push(self.crop_marker);
-                    }
+                    for &(matches, byte_start, byte_end) in &windows {
+                        // push crop marker if it's not the start of the text,
+                        // and if the previous window doesn't already end with it.
+                        if byte_start > 0
+                            && !self.crop_marker.is_empty()
+                            && formatted.last() != Some(&self.crop_marker)
+                        {
+                            formatted.push(self.crop_marker);
+                        }
 
-                    let mut byte_index = byte_start;
+                        let mut byte_index = byte_start;
 
-                    if format_options.highlight {
-                        // insert highlight markers around matches.
-                        for m in matches {
-                            let token = &tokens[m.token_position];
+                        if format_options.highlight {
+                            // insert highlight markers around matches.
+                            for m in matches {
+                                let token = &tokens[m.token_position];
 
-                            if byte_index < token.byte_start {
-                                formatted.push(&self.text[byte_index..token.byte_start]);
-                            }
+                                if byte_index < token.byte_start {
+                                    formatted.push(&self.text[byte_index..token.byte_start]);
+                                }
 
-                            let highlight_byte_index = self.text[token.byte_start..]
-                                .char_indices()
-                                .enumerate()
-                                .find(|(i, _)| *i == m.match_len)
-                                .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
-                            formatted.push(self.highlight_prefix);
-                            formatted.push(&self.text[token.byte_start..highlight_byte_index]);
-                            formatted.push(self.highlight_suffix);
-                            // if it's a prefix highlight, we put the end of the word after the highlight marker.
-                            if highlight_byte_index < token.byte_end {
-                                formatted.push(&self.text[highlight_byte_index..token.byte_end]);
-                            }
+                                let highlight_byte_index = self.text[token.byte_start..]
+                                    .char_indices()
+                                    .enumerate()
+                                    .find(|(i, _)| *i == m.match_len)
+                                    .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
+                                formatted.push(self.highlight_prefix);
+                                formatted.push(&self.text[token.byte_start..highlight_byte_index]);
+                                formatted.push(self.highlight_suffix);
+                                // if it's a prefix highlight, we put the end of the word after the highlight marker.
+                                if highlight_byte_index < token.byte_end {
+                                    formatted
+                                        .push(&self.text[highlight_byte_index..token.byte_end]);
+                                }
 
-                            byte_index = token.byte_end;
+                                byte_index = token.byte_end;
+                            }
                         }
-                    }
 
-                    // push the rest of the text between last match and the end of crop.
-                    if byte_index < byte_end {
-                        formatted.push(&self.text[byte_index..byte_end]);
-                    }
+                        // push the rest of the text between last match and the end of crop.
+                        if byte_index < byte_end {
+                            formatted.push(&self.text[byte_index..byte_end]);
+                        }
 
-                    // push crop marker if it's not the end of the text.
-                    if byte_end < self.text.len() && !self.crop_marker.is_empty() {
-                        formatted.push(self.crop_marker);
+                        // push crop marker if it's not the end of the text.
+                        if byte_end < self.text.len() && !self.crop_marker.is_empty() {
+                            formatted.push(self.crop_marker);
+                        }
                     }
 
-                    if formatted.len() == 1 {
+                    match windows.as_slice() {
                         // avoid concatenating if there is already 1 slice.
-                        Cow::Borrowed(&self.text[byte_start..byte_end])
-                    } else {
-                        Cow::Owned(formatted.concat())
+                        [(_, byte_start, byte_end)] if formatted.len() == 1 => {
+                            Cow::Borrowed(&self.text[*byte_start..*byte_end])
+                        }
+                        _ => Cow::Owned(formatted.concat()),
                     }
                 }
                 None => self.compute_matches().format(format_options),
@@ -496,27 +581,33 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
 
 #[cfg(test)]
 mod tests {
-    use charabia::TokenizerBuilder;
+    use charabia::{TokenKind, TokenizerBuilder};
     use matching_words::tests::temp_index_with_documents;
 
     use super::*;
     use crate::index::tests::TempIndex;
-    use crate::{execute_search, SearchContext};
+    use crate::{execute_search, filtered_universe, SearchContext};
 
     impl<'a> MatcherBuilder<'a, &[u8]> {
         fn new_test(rtxn: &'a heed::RoTxn, index: &'a TempIndex, query: &str) -> Self {
             let mut ctx = SearchContext::new(index, rtxn);
+            let universe = filtered_universe(&ctx, &None).unwrap();
             let crate::search::PartialSearchResult { located_query_terms, .. } = execute_search(
                 &mut ctx,
                 &Some(query.to_string()),
                 crate::TermsMatchingStrategy::default(),
                 false,
+                universe,
                 &None,
                 &None,
                 crate::search::new::GeoSortStrategy::default(),
+                &[],
+                None,
                 0,
                 100,
+                None,
                 Some(10),
+                None,
                 &mut crate::DefaultSearchLogger,
                 &mut crate::DefaultSearchLogger,
             )
@@ -524,7 +615,7 @@ mod tests {
 
             // consume context and located_query_terms to build MatchingWords.
             let matching_words = match located_query_terms {
-                Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+                Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms).unwrap(),
                 None => MatchingWords::default(),
             };
 
@@ -835,6 +926,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_crop_snippets() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split the world");
+        builder.crop_snippets(2);
+
+        let format_options = FormatOptions { highlight: false, crop: Some(3) };
+
+        // Text containing two distant groups of matches.
+        let text = "split the world void void void void void void void void void void void the world void void";
+        let mut matcher = builder.build(text);
+        // crop should return both windows joined by a marker.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"split the world…void the world…"
+        );
+
+        // Text where the best window is after a weaker one.
+        let text = "void world void void void void void void void split the world void void";
+        let mut matcher = builder.build(text);
+        // crop should return the windows in the order of the text.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"void world void…split the world…"
+        );
+
+        let format_options = FormatOptions { highlight: true, crop: Some(3) };
+
+        // Text containing two distant groups of matches.
+        let text = "split the world void void void void void void void void void void void the world void void";
+        let mut matcher = builder.build(text);
+        // crop should return both windows with highlighted matches.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"<em>split</em> <em>the</em> <em>world</em>…void <em>the</em> <em>world</em>…"
+        );
+    }
+
+    #[test]
+    fn crop_snippets_window_before_the_match() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split");
+        builder.crop_snippets(2);
+
+        // Two words without any separator between them, the crop window of size 1
+        // built around the match only contains the word before it.
+        let text = "世split";
+        let tokens = vec![
+            Token {
+                kind: TokenKind::Word,
+                lemma: Cow::Borrowed("世"),
+                char_end: 1,
+                byte_end: 3,
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Word,
+                lemma: Cow::Borrowed("split"),
+                char_start: 1,
+                char_end: 6,
+                byte_start: 3,
+                byte_end: 8,
+                ..Default::default()
+            },
+        ];
+        let matches = vec![Match {
+            match_len: 5,
+            ids: vec![0],
+            kind: MatchKind::Exact,
+            word_position: 1,
+            token_position: 1,
+        }];
+
+        let matcher = builder.build(text);
+        // the interval must not be chosen again once its window was built.
+        let windows = matcher.crop_windows(&tokens, &matches, 1);
+        let bounds: Vec<_> = windows.iter().map(|&(_, start, end)| (start, end)).collect();
+        assert_eq!(bounds, vec![(0, 3)]);
+    }
+
     #[test]
     fn partial_matches() {
         let temp_index = temp_index_with_documents();
@@ -1,14 +1,19 @@
+mod boost;
 mod bucket_sort;
+mod cursor;
 mod db_cache;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
+mod group_by;
 mod interner;
 mod limits;
 mod logger;
 pub mod matches;
 mod query_graph;
 mod query_term;
+mod random;
+mod ranking_function;
 mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
@@ -22,30 +27,43 @@ mod tests;
 
 use std::collections::HashSet;
 
+pub use boost::Boost;
+use boost::Boosting;
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
+pub use cursor::{InvalidSearchCursor, SearchCursor};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
+pub use group_by::{Grouper, SearchGroup};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
+pub use logger::trace::{
+    SearchTrace, SearchTracer, TraceEvent, TraceQueryNode, TraceQueryNodeKind, TraceQueryTerm,
+};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
 use query_term::{located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryTerm};
+use random::Random;
+use ranking_function::FunctionSort;
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
+use time::OffsetDateTime;
 
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use crate::search::new::distinct::apply_distinct_rule;
-use crate::{AscDesc, DocumentId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError};
+use crate::{
+    AscDesc, DocumentId, Filter, Index, Member, Result, SortCriterion, SynonymsSelection,
+    TermsMatchingStrategy, UserError,
+};
 
 /// A structure used throughout the execution of a search query.
 pub struct SearchContext<'ctx> {
@@ -56,6 +74,7 @@ pub struct SearchContext<'ctx> {
     pub phrase_interner: DedupInterner<Phrase>,
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
+    pub synonyms: SynonymsSelection,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -68,6 +87,7 @@ impl<'ctx> SearchContext<'ctx> {
             phrase_interner: <_>::default(),
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
+            synonyms: <_>::default(),
         }
     }
 }
@@ -132,14 +152,14 @@ fn resolve_universe(
 /// Return the list of initialised ranking rules to be used for a placeholder search.
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
-    sort_criteria: &Option<Vec<AscDesc>>,
+    settings_ranking_rules: Vec<crate::Criterion>,
+    sort_criteria: &Option<Vec<SortCriterion>>,
     geo_strategy: geo_sort::Strategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
@@ -176,6 +196,10 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Function(field_name, function) => {
+                ranking_rules
+                    .push(Box::new(FunctionSort::new(ctx.index, ctx.txn, field_name, function)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -184,7 +208,8 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
 /// Return the list of initialised ranking rules to be used for a query graph search.
 fn get_ranking_rules_for_query_graph_search<'ctx>(
     ctx: &SearchContext<'ctx>,
-    sort_criteria: &Option<Vec<AscDesc>>,
+    settings_ranking_rules: Vec<crate::Criterion>,
+    sort_criteria: &Option<Vec<SortCriterion>>,
     geo_strategy: geo_sort::Strategy,
     terms_matching_strategy: TermsMatchingStrategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
@@ -204,7 +229,6 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
@@ -285,13 +309,17 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Function(field_name, function) => {
+                ranking_rules
+                    .push(Box::new(FunctionSort::new(ctx.index, ctx.txn, field_name, function)?));
+            }
         }
     }
     Ok(ranking_rules)
 }
 
 fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
-    sort_criteria: &Option<Vec<AscDesc>>,
+    sort_criteria: &Option<Vec<SortCriterion>>,
     ctx: &SearchContext<'ctx>,
     ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
     sorted_fields: &mut HashSet<String>,
@@ -300,21 +328,25 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
 ) -> Result<()> {
     let sort_criteria = sort_criteria.clone().unwrap_or_default();
     ranking_rules.reserve(sort_criteria.len());
-    for criterion in sort_criteria {
-        match criterion {
+    for SortCriterion { asc_desc, options } in sort_criteria {
+        match asc_desc {
             AscDesc::Asc(Member::Field(field_name)) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
+                ranking_rules.push(Box::new(Sort::with_options(
+                    ctx.index, ctx.txn, field_name, true, options,
+                )?));
             }
             AscDesc::Desc(Member::Field(field_name)) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules.push(Box::new(Sort::with_options(
+                    ctx.index, ctx.txn, field_name, false, options,
+                )?));
             }
             AscDesc::Asc(Member::Geo(point)) => {
                 if *geo_sorted {
@@ -345,28 +377,71 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
     Ok(())
 }
 
+/// Add the boosting ranking rule after all the other ranking rules, so that the boosts
+/// only reorder the documents inside of the buckets of the other ranking rules.
+fn add_boosting_rule<'ctx, Query: RankingRuleQueryTrait>(
+    ctx: &SearchContext<'ctx>,
+    ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
+    boosts: &[Boost],
+) -> Result<()> {
+    if !boosts.is_empty() {
+        ranking_rules.push(Box::new(Boosting::new(ctx, boosts)?));
+    }
+    Ok(())
+}
+
+/// Add the random ranking rule after all the other ranking rules, including the boosting one,
+/// so that it only shuffles the documents that they rank equally.
+fn add_random_rule<'ctx, Query: RankingRuleQueryTrait>(
+    ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
+    random_seed: Option<u64>,
+) {
+    if let Some(seed) = random_seed {
+        ranking_rules.push(Box::new(Random::new(seed)));
+    }
+}
+
+/// Returns the documents that the search is allowed to return,
+/// i.e. all the non-expired documents of the index matching the given filter.
+pub fn filtered_universe(ctx: &SearchContext, filters: &Option<Filter>) -> Result<RoaringBitmap> {
+    let universe = if let Some(filters) = filters {
+        filters.evaluate(ctx.txn, ctx.index)?
+    } else {
+        ctx.index.documents_ids(ctx.txn)?
+    };
+    // The expired documents are hidden until the index scheduler deletes them.
+    let expired = ctx.index.expired_documents_ids(ctx.txn, OffsetDateTime::now_utc())?;
+    Ok(universe - expired)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_search(
     ctx: &mut SearchContext,
     query: &Option<String>,
     terms_matching_strategy: TermsMatchingStrategy,
     exhaustive_number_hits: bool,
-    filters: &Option<Filter>,
-    sort_criteria: &Option<Vec<AscDesc>>,
+    mut universe: RoaringBitmap,
+    ranking_rules: &Option<Vec<crate::Criterion>>,
+    sort_criteria: &Option<Vec<SortCriterion>>,
     geo_strategy: geo_sort::Strategy,
+    boosts: &[Boost],
+    random_seed: Option<u64>,
     from: usize,
     length: usize,
+    search_after: Option<&SearchCursor>,
     words_limit: Option<usize>,
+    locales: Option<&[Language]>,
     placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery>,
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
 ) -> Result<PartialSearchResult> {
-    let mut universe = if let Some(filters) = filters {
-        filters.evaluate(ctx.txn, ctx.index)?
-    } else {
-        ctx.index.documents_ids(ctx.txn)?
+    let ranking_rules = match ranking_rules {
+        Some(ranking_rules) => {
+            check_ranking_rules(ctx, ranking_rules)?;
+            ranking_rules.clone()
+        }
+        None => ctx.index.criteria(ctx.txn)?,
     };
-
-    check_sort_criteria(ctx, sort_criteria.as_ref())?;
+    check_sort_criteria(ctx, &ranking_rules, sort_criteria.as_ref())?;
 
     let mut located_query_terms = None;
 
@@ -379,7 +454,11 @@ pub fn execute_search(
             tokbuilder.stop_words(stop_words);
         }
 
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
+        // The locales given with the query replace the languages detected in the documents.
+        let script_lang_map = match locales {
+            Some(locales) => crate::locales_allow_list(query, locales),
+            None => ctx.index.script_language(ctx.txn)?,
+        };
         if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
@@ -401,20 +480,38 @@ pub fn execute_search(
         let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
         located_query_terms = Some(new_located_query_terms);
 
-        let ranking_rules = get_ranking_rules_for_query_graph_search(
+        let mut ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
+            ranking_rules,
             sort_criteria,
             geo_strategy,
             terms_matching_strategy,
         )?;
+        add_boosting_rule(ctx, &mut ranking_rules, boosts)?;
+        add_random_rule(&mut ranking_rules, random_seed);
 
         universe =
             resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
 
-        bucket_sort(ctx, ranking_rules, &graph, &universe, from, length, query_graph_logger)?
+        bucket_sort(
+            ctx,
+            ranking_rules,
+            &graph,
+            &universe,
+            from,
+            length,
+            search_after,
+            query_graph_logger,
+        )?
     } else {
-        let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
+        let mut ranking_rules = get_ranking_rules_for_placeholder_search(
+            ctx,
+            ranking_rules,
+            sort_criteria,
+            geo_strategy,
+        )?;
+        add_boosting_rule(ctx, &mut ranking_rules, boosts)?;
+        add_random_rule(&mut ranking_rules, random_seed);
         bucket_sort(
             ctx,
             ranking_rules,
@@ -422,11 +519,12 @@ pub fn execute_search(
             &universe,
             from,
             length,
+            search_after,
             placeholder_search_logger,
         )?
     };
 
-    let BucketSortOutput { docids, mut all_candidates } = bucket_sort_output;
+    let BucketSortOutput { docids, mut all_candidates, cursor } = bucket_sort_output;
 
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
@@ -442,10 +540,38 @@ pub fn execute_search(
         candidates: all_candidates,
         documents_ids: docids,
         located_query_terms,
+        cursor,
     })
 }
 
-fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>) -> Result<()> {
+/// Check that the custom ranking rules given at search time only use fields
+/// that are in the facet databases, i.e. that can be used to sort the documents.
+fn check_ranking_rules(ctx: &SearchContext, ranking_rules: &[crate::Criterion]) -> Result<()> {
+    let faceted_fields = ctx.index.user_defined_faceted_fields(ctx.txn)?;
+    for criterion in ranking_rules {
+        match criterion {
+            crate::Criterion::Asc(field)
+            | crate::Criterion::Desc(field)
+            | crate::Criterion::Function(field, _)
+                if !crate::is_faceted(field, &faceted_fields) =>
+            {
+                return Err(UserError::InvalidRankingRuleAttribute {
+                    field: field.to_string(),
+                    valid_fields: faceted_fields.into_iter().collect(),
+                })?
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn check_sort_criteria(
+    ctx: &SearchContext,
+    ranking_rules: &[crate::Criterion],
+    sort_criteria: Option<&Vec<SortCriterion>>,
+) -> Result<()> {
     let sort_criteria = if let Some(sort_criteria) = sort_criteria {
         sort_criteria
     } else {
@@ -458,7 +584,7 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
 
     // We check that the sort ranking rule exists and throw an
     // error if we try to use it and that it doesn't.
-    let sort_ranking_rule_missing = !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort);
+    let sort_ranking_rule_missing = !ranking_rules.contains(&crate::Criterion::Sort);
     if sort_ranking_rule_missing {
         return Err(UserError::SortRankingRuleMissing.into());
     }
@@ -466,7 +592,7 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
     // We check that we are allowed to use the sort criteria, we check
     // that they are declared in the sortable fields.
     let sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
-    for asc_desc in sort_criteria {
+    for SortCriterion { asc_desc, .. } in sort_criteria {
         match asc_desc.member() {
             Member::Field(ref field) if !crate::is_faceted(field, &sortable_fields) => {
                 return Err(UserError::InvalidSortableAttribute {
@@ -491,4 +617,5 @@ pub struct PartialSearchResult {
     pub located_query_terms: Option<Vec<LocatedQueryTerm>>,
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
+    pub cursor: Option<SearchCursor>,
 }
@@ -11,6 +11,7 @@ use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::query_term::TwoTypoTerm;
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
+use crate::update::{INFIX_WORD_SEPARATOR, MIN_INFIX_LENGTH};
 use crate::{Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -41,6 +42,9 @@ impl Interned<QueryTerm> {
             let s = ctx.term_interner.get_mut(self);
             assert!(s.one_typo.is_init() && s.two_typo.is_init());
         }
+        if ctx.term_interner.get(self).infix.is_uninit() {
+            self.initialize_infix_subterm(ctx)?;
+        }
         Ok(())
     }
 }
@@ -69,6 +73,47 @@ fn find_zero_typo_prefix_derivations(
     Ok(())
 }
 
+/// Find the words of the infix searchable attributes which contain the given word
+/// without starting with it.
+///
+/// Short words and ngrams are ignored as they would match far too many words.
+fn find_infix_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+    is_ngram: bool,
+    mut visit: impl FnMut(Interned<String>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let word = ctx.word_interner.get(word_interned).to_owned();
+    if is_ngram || word.chars().count() < MIN_INFIX_LENGTH {
+        return Ok(());
+    }
+
+    let infix_fst = ctx.index.words_infixes_fst(ctx.txn)?;
+    if infix_fst.is_empty() {
+        return Ok(());
+    }
+    // The infixes dictionnary is not updated on documents deletion,
+    // we make sure that the derived words still exist.
+    let words_fst = ctx.get_words_fst()?;
+
+    let prefix = Str::new(&word).starts_with();
+    let mut stream = infix_fst.search(prefix).into_stream();
+
+    while let Some(entry) = stream.next() {
+        let entry = std::str::from_utf8(entry)?;
+        let Some((_, derived_word)) = entry.split_once(INFIX_WORD_SEPARATOR) else { continue };
+        if derived_word == word || !words_fst.contains(derived_word) {
+            continue;
+        }
+        let derived_word_interned = ctx.word_interner.insert(derived_word.to_owned());
+        let cf = visit(derived_word_interned)?;
+        if cf.is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 fn find_zero_one_typo_derivations(
     ctx: &mut SearchContext,
     word_interned: Interned<String>,
@@ -189,6 +234,7 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                infix: Lazy::Init(<_>::default()),
             }
         });
     }
@@ -218,6 +264,19 @@ pub fn partially_initialized_term_from_word(
         zero_typo = Some(word_interned);
     }
 
+    // the stems are indexed prefixed by a marker and are not part of the words FST,
+    // they are only found from the original word.
+    let word_docids = ctx.index.word_docids.remap_data_type::<DecodeIgnore>();
+    let exact_word_docids = ctx.index.exact_word_docids.remap_data_type::<DecodeIgnore>();
+    let mut stems = BTreeSet::new();
+    for stem in ctx.get_stems(word)? {
+        if word_docids.get(ctx.txn, &stem)?.is_some()
+            || exact_word_docids.get(ctx.txn, &stem)?.is_some()
+        {
+            stems.insert(ctx.word_interner.insert(stem));
+        }
+    }
+
     if is_prefix && use_prefix_db.is_none() {
         find_zero_typo_prefix_derivations(
             word_interned,
@@ -233,12 +292,9 @@ pub fn partially_initialized_term_from_word(
             },
         )?;
     }
-    let synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = ctx.get_synonyms(&[word.to_owned()])?;
     let mut synonym_word_count = 0;
     let synonyms = synonyms
-        .get(&vec![word.to_owned()])
-        .cloned()
-        .unwrap_or_default()
         .into_iter()
         .take(limits::MAX_SYNONYM_PHRASE_COUNT)
         .filter_map(|words| {
@@ -251,7 +307,7 @@ pub fn partially_initialized_term_from_word(
         })
         .collect();
     let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db };
 
     Ok(QueryTerm {
         original: word_interned,
@@ -261,6 +317,7 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        infix: Lazy::Uninit,
     })
 }
 
@@ -396,6 +453,42 @@ impl Interned<QueryTerm> {
         self_mut.one_typo = Lazy::Init(one_typo);
         self_mut.two_typo = Lazy::Init(two_typo);
 
+        Ok(())
+    }
+    fn initialize_infix_subterm(self, ctx: &mut SearchContext) -> Result<()> {
+        let self_mut = ctx.term_interner.get_mut(self);
+        if self_mut.infix.is_init() {
+            return Ok(());
+        }
+        let original = self_mut.original;
+        let is_ngram = self_mut.ngram_words.is_some();
+
+        // the words already derived with fewer typos keep their lower cost
+        let mut already_derived: BTreeSet<_> = self_mut.zero_typo.prefix_of.clone();
+        already_derived.extend(self_mut.zero_typo.exact);
+        if let Lazy::Init(OneTypoTerm { one_typo, .. }) = &self_mut.one_typo {
+            already_derived.extend(one_typo);
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &self_mut.two_typo {
+            already_derived.extend(two_typos);
+        }
+
+        let mut infix_of = BTreeSet::new();
+        find_infix_derivations(ctx, original, is_ngram, |derived_word| {
+            if already_derived.contains(&derived_word) {
+                return Ok(ControlFlow::Continue(()));
+            }
+            if infix_of.len() < limits::MAX_INFIX_COUNT {
+                infix_of.insert(derived_word);
+                Ok(ControlFlow::Continue(()))
+            } else {
+                Ok(ControlFlow::Break(()))
+            }
+        })?;
+
+        let self_mut = ctx.term_interner.get_mut(self);
+        self_mut.infix = Lazy::Init(InfixTerm { infix_of });
+
         Ok(())
     }
 }
@@ -3,7 +3,7 @@ mod ntypo_subset;
 mod parse_query;
 mod phrase;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 use std::ops::RangeInclusive;
 
@@ -14,6 +14,7 @@ pub use parse_query::{located_query_terms_from_tokens, make_ngram, number_of_typ
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
+use super::matches::MatchKind;
 use super::{limits, SearchContext, Word};
 use crate::Result;
 
@@ -31,6 +32,7 @@ pub struct QueryTermSubset {
     zero_typo_subset: NTypoTermSubset,
     one_typo_subset: NTypoTermSubset,
     two_typo_subset: NTypoTermSubset,
+    infix_subset: NTypoTermSubset,
     /// `true` if the term cannot be deleted through the term matching strategy
     ///
     /// Note that there are other reasons for which a term cannot be deleted, such as
@@ -50,6 +52,8 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    // May not be computed yet
+    infix: Lazy<InfixTerm>,
 }
 
 // SubTerms will be in a dedup interner
@@ -63,6 +67,8 @@ struct ZeroTypoTerm {
     prefix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
+    /// The stems of the original word in the stemming languages of the index
+    stems: BTreeSet<Interned<String>>,
     /// A prefix in the prefix databases matching the original word
     use_prefix_db: Option<Interned<String>>,
 }
@@ -78,6 +84,12 @@ struct TwoTypoTerm {
     /// Words that are 2 typos away from the original word
     two_typos: BTreeSet<Interned<String>>,
 }
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+struct InfixTerm {
+    /// Words of the infix searchable attributes that contain the original word
+    /// somewhere else than at their start, they only match within these attributes
+    infix_of: BTreeSet<Interned<String>>,
+}
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum Lazy<T> {
@@ -147,6 +159,7 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::Nothing,
             one_typo_subset: NTypoTermSubset::Nothing,
             two_typo_subset: NTypoTermSubset::Nothing,
+            infix_subset: NTypoTermSubset::Nothing,
             mandatory: false,
         }
     }
@@ -156,6 +169,7 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::All,
             one_typo_subset: NTypoTermSubset::All,
             two_typo_subset: NTypoTermSubset::All,
+            infix_subset: NTypoTermSubset::All,
             mandatory: false,
         }
     }
@@ -165,12 +179,14 @@ impl QueryTermSubset {
         self.zero_typo_subset.union(&other.zero_typo_subset);
         self.one_typo_subset.union(&other.one_typo_subset);
         self.two_typo_subset.union(&other.two_typo_subset);
+        self.infix_subset.union(&other.infix_subset);
     }
     pub fn intersect(&mut self, other: &Self) {
         assert!(self.original == other.original);
         self.zero_typo_subset.intersect(&other.zero_typo_subset);
         self.one_typo_subset.intersect(&other.one_typo_subset);
         self.two_typo_subset.intersect(&other.two_typo_subset);
+        self.infix_subset.intersect(&other.infix_subset);
     }
 
     pub fn use_prefix_db(&self, ctx: &SearchContext) -> Option<Word> {
@@ -214,6 +230,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 result.extend(zero_typo.iter().copied().map(|w| {
@@ -223,7 +240,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
-                result.extend(prefix_of.iter().copied().map(|w| {
+                result.extend(prefix_of.iter().chain(stems).copied().map(|w| {
                     if original.ngram_words.is_some() {
                         Word::Derived(w)
                     } else {
@@ -237,6 +254,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 if let Some(zero_typo) = zero_typo {
@@ -248,7 +266,8 @@ impl QueryTermSubset {
                         }
                     }
                 }
-                result.extend(prefix_of.intersection(words).copied().map(|w| {
+                let derived_words = prefix_of.intersection(words).chain(stems.intersection(words));
+                result.extend(derived_words.copied().map(|w| {
                     if original.ngram_words.is_some() {
                         Word::Derived(w)
                     } else {
@@ -301,7 +320,7 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, stems: _, use_prefix_db: _ } =
             &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
@@ -328,6 +347,27 @@ impl QueryTermSubset {
 
         Ok(result)
     }
+    /// Returns the words of the infix searchable attributes containing the original word,
+    /// they must only be resolved within these attributes.
+    pub fn infix_words(&self, ctx: &mut SearchContext) -> Result<BTreeSet<Interned<String>>> {
+        if self.infix_subset.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(InfixTerm { infix_of }) = &original.infix else {
+            panic!()
+        };
+        let words = match &self.infix_subset {
+            NTypoTermSubset::All => infix_of.clone(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                infix_of.intersection(words).copied().collect()
+            }
+            NTypoTermSubset::Nothing => BTreeSet::new(),
+        };
+        Ok(words)
+    }
 
     pub fn original_phrase(&self, ctx: &SearchContext) -> Option<Interned<Phrase>> {
         let t = ctx.term_interner.get(self.original);
@@ -379,6 +419,7 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_infix_subset();
                 }
                 ExactTerm::Word(w) => {
                     self.zero_typo_subset = NTypoTermSubset::Subset {
@@ -387,6 +428,7 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_infix_subset();
                 }
             }
         }
@@ -400,6 +442,9 @@ impl QueryTermSubset {
     pub fn clear_two_typo_subset(&mut self) {
         self.two_typo_subset = NTypoTermSubset::Nothing;
     }
+    pub fn clear_infix_subset(&mut self) {
+        self.infix_subset = NTypoTermSubset::Nothing;
+    }
     pub fn description(&self, ctx: &SearchContext) -> String {
         let t = ctx.term_interner.get(self.original);
         ctx.word_interner.get(t.original).to_owned()
@@ -408,11 +453,13 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db } =
+            self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
             && synonyms.is_empty()
+            && stems.is_empty()
             && use_prefix_db.is_none()
     }
 }
@@ -428,6 +475,12 @@ impl TwoTypoTerm {
         two_typos.is_empty()
     }
 }
+impl InfixTerm {
+    fn is_empty(&self) -> bool {
+        let InfixTerm { infix_of } = self;
+        infix_of.is_empty()
+    }
+}
 
 impl QueryTerm {
     fn is_empty(&self) -> bool {
@@ -437,8 +490,11 @@ impl QueryTerm {
         let Lazy::Init(two_typo) = &self.two_typo else {
             return false;
         };
+        let Lazy::Init(infix) = &self.infix else {
+            return false;
+        };
 
-        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty()
+        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty() && infix.is_empty()
     }
     fn allows_split_words(&self) -> bool {
         self.zero_typo.phrase.is_none()
@@ -481,24 +537,51 @@ impl QueryTerm {
     pub fn original_word(&self, ctx: &SearchContext) -> String {
         ctx.word_interner.get(self.original).clone()
     }
-    pub fn all_computed_derivations(&self) -> (Vec<Interned<String>>, Vec<Interned<Phrase>>) {
-        let mut words = BTreeSet::new();
-        let mut phrases = BTreeSet::new();
+    /// Returns all the computed derivations of the term with the kind of match they produce,
+    /// a derivation found in several categories keeps the kind of the most exact one.
+    pub fn all_computed_derivations(
+        &self,
+    ) -> (Vec<(Interned<String>, MatchKind)>, Vec<(Interned<Phrase>, MatchKind)>) {
+        let mut words = BTreeMap::new();
+        let mut phrases = BTreeMap::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db: _ } =
             &self.zero_typo;
-        words.extend(zero_typo.iter().copied());
-        words.extend(prefix_of.iter().copied());
-        phrases.extend(phrase.iter().copied());
-        phrases.extend(synonyms.iter().copied());
+        for word in zero_typo {
+            words.entry(*word).or_insert(MatchKind::Exact);
+        }
+        for word in prefix_of {
+            words.entry(*word).or_insert(MatchKind::Prefix);
+        }
+        for word in stems {
+            words.entry(*word).or_insert(MatchKind::Stem);
+        }
+        for phrase in phrase {
+            phrases.entry(*phrase).or_insert(MatchKind::Exact);
+        }
+        for synonym in synonyms {
+            phrases.entry(*synonym).or_insert(MatchKind::Synonym);
+        }
 
         if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &self.one_typo {
-            words.extend(one_typo.iter().copied());
-            phrases.extend(split_words.iter().copied());
+            for word in one_typo {
+                words.entry(*word).or_insert(MatchKind::Typo);
+            }
+            for phrase in split_words {
+                phrases.entry(*phrase).or_insert(MatchKind::Typo);
+            }
         };
 
         if let Lazy::Init(TwoTypoTerm { two_typos }) = &self.two_typo {
-            words.extend(two_typos.iter().copied());
+            for word in two_typos {
+                words.entry(*word).or_insert(MatchKind::Typo);
+            }
+        };
+
+        if let Lazy::Init(InfixTerm { infix_of }) = &self.infix {
+            for word in infix_of {
+                words.entry(*word).or_insert(MatchKind::Infix);
+            }
         };
 
         (words.into_iter().collect(), phrases.into_iter().collect())
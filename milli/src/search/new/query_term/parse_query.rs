@@ -199,10 +199,10 @@ pub fn make_ngram(
         partially_initialized_term_from_word(ctx, &ngram_str, max_nbr_typos, is_prefix, true)?;
 
     // Now add the synonyms
-    let index_synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = ctx.get_synonyms(&words)?;
 
     term.zero_typo.synonyms.extend(
-        index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
+        synonyms.into_iter().take(super::limits::MAX_SYNONYM_PHRASE_COUNT).map(|words| {
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
             ctx.phrase_interner.insert(Phrase { words })
         }),
@@ -216,6 +216,7 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        infix: Lazy::Uninit,
     };
 
     let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
@@ -271,10 +272,12 @@ impl PhraseBuilder {
                         exact: None,
                         prefix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
+                        stems: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    infix: Lazy::Init(<_>::default()),
                 }
             }),
             positions: self.start..=self.end,
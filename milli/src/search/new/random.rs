@@ -0,0 +1,102 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::{DocumentId, Result};
+
+/// A ranking rule that shuffles the documents in a deterministic order given by a seed.
+///
+/// It is placed after all the other ranking rules so that it only shuffles the documents
+/// inside of their buckets. The same seed always gives the same order, which keeps the
+/// pagination stable.
+pub struct Random<Query> {
+    seed: u64,
+    original_query: Option<Query>,
+    docids: std::vec::IntoIter<DocumentId>,
+}
+
+impl<Query> Random<Query> {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, original_query: None, docids: Vec::new().into_iter() }
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Random<Query> {
+    fn id(&self) -> String {
+        format!("random:{}", self.seed)
+    }
+
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        query: &Query,
+    ) -> Result<()> {
+        self.original_query = Some(query.clone());
+        self.docids = shuffle(self.seed, universe).into_iter();
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        let candidates = match self.docids.next() {
+            Some(docid) if universe.contains(docid) => std::iter::once(docid).collect(),
+            Some(_) => RoaringBitmap::new(),
+            None => universe.clone(),
+        };
+        Ok(Some(RankingRuleOutput { query, candidates }))
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.docids = Vec::new().into_iter();
+    }
+}
+
+/// Return the documents of the universe in the order given by the seed.
+fn shuffle(seed: u64, universe: &RoaringBitmap) -> Vec<DocumentId> {
+    let mut keyed: Vec<_> =
+        universe.iter().map(|docid| (shuffle_key(seed, docid), docid)).collect();
+    keyed.sort_unstable();
+    keyed.into_iter().map(|(_, docid)| docid).collect()
+}
+
+/// Mix the seed and the document id with the SplitMix64 finalizer, which gives
+/// the same result on every platform and across versions, unlike the std hashers.
+fn shuffle_key(seed: u64, docid: DocumentId) -> u64 {
+    let mut z = seed.wrapping_add(u64::from(docid).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use roaring::RoaringBitmap;
+
+    use super::shuffle;
+
+    #[test]
+    fn shuffle_is_a_deterministic_permutation() {
+        let universe: RoaringBitmap = (0..10).collect();
+
+        let shuffled = shuffle(42, &universe);
+        insta::assert_snapshot!(format!("{shuffled:?}"), @"[5, 2, 7, 3, 9, 4, 0, 1, 8, 6]");
+        assert_eq!(shuffled, shuffle(42, &universe));
+        assert_ne!(shuffled, shuffle(43, &universe));
+
+        let mut sorted = shuffled;
+        sorted.sort_unstable();
+        assert_eq!(sorted, universe.iter().collect::<Vec<_>>());
+    }
+}
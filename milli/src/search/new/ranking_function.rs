@@ -0,0 +1,245 @@
+use std::ops::Bound;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::OrderedF64Codec;
+use crate::search::facet::{facet_max_value, find_docids_of_facet_within_bounds};
+use crate::{DecayOrigin, FieldId, Index, RankingFunction, Result};
+
+/// The number of buckets the scores of the decay functions, between 0 and 1, are split into.
+const DECAY_BUCKETS: u32 = 10;
+
+type ValueRange = (Bound<f64>, Bound<f64>);
+
+/// A ranking rule that splits the documents into buckets of decreasing scores of a
+/// [`RankingFunction`] applied on the numeric values of a field.
+///
+/// The buckets are ranges of values, so that the documents are retrieved from the facet
+/// database without computing the score of each of them. The documents without any numeric
+/// value for the field are returned last.
+pub struct FunctionSort<Query> {
+    field_name: String,
+    field_id: Option<FieldId>,
+    function: RankingFunction,
+    original_query: Option<Query>,
+    buckets: std::vec::IntoIter<RoaringBitmap>,
+}
+
+impl<Query> FunctionSort<Query> {
+    pub fn new(
+        index: &Index,
+        rtxn: &heed::RoTxn,
+        field_name: String,
+        function: RankingFunction,
+    ) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&field_name);
+
+        Ok(Self {
+            field_name,
+            field_id,
+            function,
+            original_query: None,
+            buckets: Vec::new().into_iter(),
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for FunctionSort<Query> {
+    fn id(&self) -> String {
+        let Self { field_name, function, .. } = self;
+        format!("{field_name}:{function}")
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        query: &Query,
+    ) -> Result<()> {
+        let mut buckets = Vec::new();
+        if let Some(field_id) = self.field_id {
+            let ranges = match self.function {
+                RankingFunction::Gauss { origin, scale } => {
+                    let scale = scale.get();
+                    let inverse = |score: f64| scale * (score.ln() / 0.5_f64.ln()).sqrt();
+                    decay_ranges(origin, inverse)
+                }
+                RankingFunction::Exp { origin, scale } => {
+                    let scale = scale.get();
+                    let inverse = |score: f64| scale * score.ln() / 0.5_f64.ln();
+                    decay_ranges(origin, inverse)
+                }
+                RankingFunction::Linear { origin, scale } => {
+                    let scale = scale.get();
+                    let inverse = |score: f64| 2.0 * scale * (1.0 - score);
+                    decay_ranges(origin, inverse)
+                }
+                RankingFunction::Log => {
+                    match facet_max_value(ctx.index, ctx.txn, field_id, universe.clone())? {
+                        Some(max) => log_ranges(max),
+                        None => Vec::new(),
+                    }
+                }
+            };
+
+            for bucket_ranges in ranges {
+                let mut docids = RoaringBitmap::new();
+                for (left, right) in bucket_ranges.iter().filter(|range| !is_empty(range)) {
+                    find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                        ctx.txn,
+                        ctx.index.facet_id_f64_docids,
+                        field_id,
+                        left,
+                        right,
+                        &mut docids,
+                    )?;
+                }
+                buckets.push(docids & universe);
+            }
+        }
+
+        self.original_query = Some(query.clone());
+        self.buckets = buckets.into_iter();
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        let candidates = match self.buckets.next() {
+            Some(bucket) => bucket & universe,
+            None => universe.clone(),
+        };
+        Ok(Some(RankingRuleOutput { query, candidates }))
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = Vec::new().into_iter();
+    }
+}
+
+fn is_empty((left, right): &ValueRange) -> bool {
+    match (left, right) {
+        (Bound::Included(l), Bound::Included(r)) => l > r,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Excluded(r) | Bound::Included(r)) => {
+            l >= r
+        }
+        _ => false,
+    }
+}
+
+/// Return the ranges of values of each bucket of a decay function, from the best to the worst.
+///
+/// The `inverse` function returns the distance to the origin at which the decay function
+/// reaches the given score.
+fn decay_ranges(origin: DecayOrigin, inverse: impl Fn(f64) -> f64) -> Vec<Vec<ValueRange>> {
+    let origin = match origin {
+        DecayOrigin::Now => {
+            SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |now| now.as_secs_f64())
+        }
+        DecayOrigin::Value(origin) => origin.get(),
+    };
+
+    // the distances at which the score goes below each of the buckets, from the best one
+    let distances: Vec<f64> = (1..DECAY_BUCKETS)
+        .map(|bucket| inverse(f64::from(DECAY_BUCKETS - bucket) / f64::from(DECAY_BUCKETS)))
+        .collect();
+
+    let mut ranges = Vec::with_capacity(DECAY_BUCKETS as usize);
+    let mut previous = None;
+    for distance in distances.into_iter().map(Some).chain([None]) {
+        let bucket_ranges = match (previous, distance) {
+            (None, Some(distance)) => {
+                vec![(Bound::Included(origin - distance), Bound::Included(origin + distance))]
+            }
+            (Some(previous), Some(distance)) => vec![
+                (Bound::Included(origin - distance), Bound::Excluded(origin - previous)),
+                (Bound::Excluded(origin + previous), Bound::Included(origin + distance)),
+            ],
+            (Some(previous), None) => vec![
+                (Bound::Unbounded, Bound::Excluded(origin - previous)),
+                (Bound::Excluded(origin + previous), Bound::Unbounded),
+            ],
+            (None, None) => vec![(Bound::Unbounded, Bound::Unbounded)],
+        };
+        ranges.push(bucket_ranges);
+        previous = distance;
+    }
+
+    ranges
+}
+
+/// Return the ranges of values of each bucket of the logarithm function, from the best to the
+/// worst, given the maximum value of the documents.
+///
+/// The buckets are the integer parts of the natural logarithm of `1 + value`.
+fn log_ranges(max: f64) -> Vec<Vec<ValueRange>> {
+    let highest_bucket = max.max(0.0).ln_1p().floor() as i32;
+    (0..=highest_bucket)
+        .rev()
+        .map(|bucket| {
+            let left = match bucket {
+                0 => Bound::Unbounded,
+                bucket => Bound::Included(f64::from(bucket).exp_m1()),
+            };
+            let right = match bucket {
+                bucket if bucket == highest_bucket => Bound::Unbounded,
+                bucket => Bound::Excluded(f64::from(bucket + 1).exp_m1()),
+            };
+            vec![(left, right)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound::*;
+
+    use super::*;
+    use crate::FiniteNumber;
+
+    #[test]
+    fn decay_buckets() {
+        let origin = DecayOrigin::Value(FiniteNumber::new(100.0).unwrap());
+        let ranges = decay_ranges(origin, |score| 10.0 - score * 10.0);
+        assert_eq!(ranges.len(), DECAY_BUCKETS as usize);
+        assert_eq!(ranges[0], vec![(Included(99.0), Included(101.0))]);
+        assert_eq!(
+            ranges[1],
+            vec![(Included(98.0), Excluded(99.0)), (Excluded(101.0), Included(102.0))]
+        );
+        assert_eq!(
+            ranges[DECAY_BUCKETS as usize - 1],
+            vec![(Unbounded, Excluded(91.0)), (Excluded(109.0), Unbounded)]
+        );
+    }
+
+    #[test]
+    fn log_buckets() {
+        let exp_m1 = |n: i32| f64::from(n).exp_m1();
+        assert_eq!(
+            log_ranges(20.0),
+            vec![
+                vec![(Included(exp_m1(3)), Unbounded)],
+                vec![(Included(exp_m1(2)), Excluded(exp_m1(3)))],
+                vec![(Included(exp_m1(1)), Excluded(exp_m1(2)))],
+                vec![(Unbounded, Excluded(exp_m1(1)))],
+            ]
+        );
+        assert_eq!(log_ranges(0.0), vec![vec![(Unbounded, Unbounded)]]);
+    }
+}
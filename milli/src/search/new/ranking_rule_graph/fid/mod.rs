@@ -67,6 +67,15 @@ impl RankingRuleGraphTrait for FidGraph {
             all_fields.extend(fields);
         }
 
+        let infix_words = term.term_subset.infix_words(ctx)?;
+        if !infix_words.is_empty() {
+            let infix_fids = ctx.get_infix_fids()?;
+            for word in infix_words {
+                let fields = ctx.get_db_word_fids(word)?;
+                all_fields.extend(fields.into_iter().filter(|fid| infix_fids.contains(fid)));
+            }
+        }
+
         let mut edges = vec![];
         for fid in all_fields {
             edges.push((
@@ -74,6 +74,11 @@ impl RankingRuleGraphTrait for PositionGraph {
             all_positions.extend(positions);
         }
 
+        for word in term.term_subset.infix_words(ctx)? {
+            let positions = ctx.get_db_word_positions(word)?;
+            all_positions.extend(positions);
+        }
+
         let mut positions_for_costs = FxHashMap::<u32, Vec<u16>>::default();
 
         for position in all_positions {
@@ -50,7 +50,8 @@ impl RankingRuleGraphTrait for TypoGraph {
         // 3-gram -> equivalent to 2 typos
         let base_cost = if term.term_ids.len() == 1 { 0 } else { term.term_ids.len() as u32 };
 
-        for nbr_typos in 0..=term.term_subset.max_typo_cost(ctx) {
+        let max_typo_cost = term.term_subset.max_typo_cost(ctx);
+        for nbr_typos in 0..=max_typo_cost {
             let mut term = term.clone();
             match nbr_typos {
                 0 => {
@@ -67,6 +68,21 @@ impl RankingRuleGraphTrait for TypoGraph {
                 }
                 _ => panic!(),
             };
+            term.term_subset.clear_infix_subset();
+
+            edges.push((
+                nbr_typos as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+            ));
+        }
+
+        // Infix derivations are ranked after all the typo derivations of the term
+        if !term.term_subset.infix_words(ctx)?.is_empty() {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            let nbr_typos = max_typo_cost + 1;
 
             edges.push((
                 nbr_typos as u32 + base_cost,
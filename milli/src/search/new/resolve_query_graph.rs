@@ -43,6 +43,10 @@ pub fn compute_query_term_subset_docids(
         docids |= ctx.get_phrase_docids(phrase)?;
     }
 
+    for word in term.infix_words(ctx)? {
+        docids |= ctx.get_db_word_infix_docids(word)?;
+    }
+
     if let Some(prefix) = term.use_prefix_db(ctx) {
         if let Some(prefix_docids) = ctx.word_prefix_docids(prefix)? {
             docids |= prefix_docids;
@@ -74,6 +78,15 @@ pub fn compute_query_term_subset_docids_within_field_id(
         }
     }
 
+    // Infix derivations only match within the infix searchable fields
+    if ctx.get_infix_fids()?.contains(&fid) {
+        for word in term.infix_words(ctx)? {
+            if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(word, fid)? {
+                docids |= word_fid_docids;
+            }
+        }
+    }
+
     if let Some(word_prefix) = term.use_prefix_db(ctx) {
         if let Some(word_fid_docids) =
             ctx.get_db_word_prefix_fid_docids(word_prefix.interned(), fid)?
@@ -109,6 +122,12 @@ pub fn compute_query_term_subset_docids_within_position(
         }
     }
 
+    for word in term.infix_words(ctx)? {
+        if let Some(word_position_docids) = ctx.get_db_word_position_docids(word, position)? {
+            docids |= ctx.get_db_word_infix_docids(word)? & word_position_docids;
+        }
+    }
+
     if let Some(word_prefix) = term.use_prefix_db(ctx) {
         if let Some(word_position_docids) =
             ctx.get_db_word_prefix_position_docids(word_prefix.interned(), position)?
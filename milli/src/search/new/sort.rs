@@ -1,11 +1,16 @@
+use std::cmp::Ordering;
+
+use heed::types::ByteSlice;
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
 use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
-use crate::heed_codec::facet::FacetGroupKeyCodec;
+use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec};
 use crate::heed_codec::ByteSliceRefCodec;
-use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
-use crate::{FieldId, Index, Result};
+use crate::search::facet::{
+    ascending_facet_sort, descending_facet_sort, get_faceted_documents_ids,
+};
+use crate::{Collation, FieldId, Index, MissingPosition, Result, SortOptions};
 
 pub trait RankingRuleOutputIter<'ctx, Query> {
     fn next_bucket(&mut self) -> Result<Option<RankingRuleOutput<Query>>>;
@@ -47,6 +52,10 @@ pub struct Sort<'ctx, Query> {
     field_name: String,
     field_id: Option<FieldId>,
     is_ascending: bool,
+    options: SortOptions,
+    /// The documents of every string value of the field in the natural order,
+    /// loaded on the first iteration and reused for the rest of the search.
+    natural_values: Option<Vec<RoaringBitmap>>,
     original_query: Option<Query>,
     iter: Option<RankingRuleOutputIterWrapper<'ctx, Query>>,
 }
@@ -56,11 +65,29 @@ impl<'ctx, Query> Sort<'ctx, Query> {
         rtxn: &'ctx heed::RoTxn,
         field_name: String,
         is_ascending: bool,
+    ) -> Result<Self> {
+        Self::with_options(index, rtxn, field_name, is_ascending, SortOptions::default())
+    }
+
+    pub fn with_options(
+        index: &Index,
+        rtxn: &'ctx heed::RoTxn,
+        field_name: String,
+        is_ascending: bool,
+        options: SortOptions,
     ) -> Result<Self> {
         let fields_ids_map = index.fields_ids_map(rtxn)?;
         let field_id = fields_ids_map.id(&field_name);
 
-        Ok(Self { field_name, field_id, is_ascending, original_query: None, iter: None })
+        Ok(Self {
+            field_name,
+            field_id,
+            is_ascending,
+            options,
+            natural_values: None,
+            original_query: None,
+            iter: None,
+        })
     }
 }
 
@@ -87,45 +114,81 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                     .facet_id_string_docids
                     .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
 
-                let (number_iter, string_iter) = if self.is_ascending {
-                    let number_iter = ascending_facet_sort(
+                // The documents without any value are returned first, before the sorted ones,
+                // otherwise they are returned last with the remaining universe.
+                let missing_docids = match self.options.missing {
+                    MissingPosition::First => {
+                        let mut faceted_docids =
+                            get_faceted_documents_ids(ctx.txn, number_db, field_id)?;
+                        faceted_docids |= get_faceted_documents_ids(ctx.txn, string_db, field_id)?;
+                        Some(parent_candidates - faceted_docids)
+                    }
+                    MissingPosition::Last => None,
+                };
+
+                let number_iter = if self.is_ascending {
+                    itertools::Either::Left(ascending_facet_sort(
                         ctx.txn,
                         number_db,
                         field_id,
                         parent_candidates.clone(),
-                    )?;
-                    let string_iter = ascending_facet_sort(
-                        ctx.txn,
-                        string_db,
-                        field_id,
-                        parent_candidates.clone(),
-                    )?;
-
-                    (itertools::Either::Left(number_iter), itertools::Either::Left(string_iter))
+                    )?)
                 } else {
-                    let number_iter = descending_facet_sort(
+                    itertools::Either::Right(descending_facet_sort(
                         ctx.txn,
                         number_db,
                         field_id,
                         parent_candidates.clone(),
-                    )?;
-                    let string_iter = descending_facet_sort(
-                        ctx.txn,
-                        string_db,
-                        field_id,
-                        parent_candidates.clone(),
-                    )?;
+                    )?)
+                };
 
-                    (itertools::Either::Right(number_iter), itertools::Either::Right(string_iter))
+                let string_iter = match self.options.collation {
+                    Collation::CaseInsensitive if self.is_ascending => {
+                        let iter = ascending_facet_sort(
+                            ctx.txn,
+                            string_db,
+                            field_id,
+                            parent_candidates.clone(),
+                        )?;
+                        itertools::Either::Left(itertools::Either::Left(iter))
+                    }
+                    Collation::CaseInsensitive => {
+                        let iter = descending_facet_sort(
+                            ctx.txn,
+                            string_db,
+                            field_id,
+                            parent_candidates.clone(),
+                        )?;
+                        itertools::Either::Left(itertools::Either::Right(iter))
+                    }
+                    Collation::Natural => {
+                        if self.natural_values.is_none() {
+                            let values = natural_facet_values(ctx.txn, string_db, field_id)?;
+                            self.natural_values = Some(values);
+                        }
+                        let buckets = natural_buckets(
+                            self.natural_values.as_deref().unwrap_or_default(),
+                            parent_candidates,
+                            self.is_ascending,
+                        );
+                        itertools::Either::Right(
+                            buckets.into_iter().map(|docids| Ok((docids, &[][..]))),
+                        )
+                    }
                 };
 
                 let query_graph = parent_query.clone();
-                RankingRuleOutputIterWrapper::new(Box::new(number_iter.chain(string_iter).map(
-                    move |r| {
-                        let (docids, _) = r?;
-                        Ok(RankingRuleOutput { query: query_graph.clone(), candidates: docids })
-                    },
-                )))
+                RankingRuleOutputIterWrapper::new(Box::new(
+                    missing_docids
+                        .into_iter()
+                        .map(|docids| Ok((docids, &[][..])))
+                        .chain(number_iter)
+                        .chain(string_iter)
+                        .map(move |r| {
+                            let (docids, _) = r?;
+                            Ok(RankingRuleOutput { query: query_graph.clone(), candidates: docids })
+                        }),
+                ))
             }
             None => RankingRuleOutputIterWrapper::new(Box::new(std::iter::empty())),
         };
@@ -159,3 +222,108 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
         self.iter = None;
     }
 }
+
+/// Return the documents of every string value of the given field sorted by their value,
+/// comparing the sequences of digits by their numeric value.
+fn natural_facet_values(
+    rtxn: &heed::RoTxn,
+    db: heed::Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
+    field_id: FieldId,
+) -> Result<Vec<RoaringBitmap>> {
+    let mut level0_prefix = field_id.to_be_bytes().to_vec();
+    level0_prefix.push(0);
+
+    let mut values = Vec::new();
+    let iter = db
+        .as_polymorph()
+        .prefix_iter::<_, ByteSlice, FacetGroupValueCodec>(rtxn, level0_prefix.as_slice())?;
+    for result in iter {
+        let (key, FacetGroupValue { bitmap, .. }) = result?;
+        let value = String::from_utf8_lossy(&key[level0_prefix.len()..]).into_owned();
+        values.push((value, bitmap));
+    }
+
+    values.sort_by(|(left, _), (right, _)| natural_cmp(left, right));
+    Ok(values.into_iter().map(|(_, docids)| docids).collect())
+}
+
+/// Return the buckets of documents of the given candidates in the order of the values.
+///
+/// Like with the facet sort, a document is only returned for its first value in that order.
+fn natural_buckets(
+    values: &[RoaringBitmap],
+    candidates: &RoaringBitmap,
+    is_ascending: bool,
+) -> Vec<RoaringBitmap> {
+    let values = if is_ascending {
+        itertools::Either::Left(values.iter())
+    } else {
+        itertools::Either::Right(values.iter().rev())
+    };
+
+    let mut seen = RoaringBitmap::new();
+    let mut buckets = Vec::new();
+    for docids in values {
+        let docids = (docids & candidates) - &seen;
+        if !docids.is_empty() {
+            seen |= &docids;
+            buckets.push(docids);
+        }
+    }
+    buckets
+}
+
+/// Compare two strings chunk by chunk, the chunks of digits being compared by their numeric value.
+fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left_chunks = natural_chunks(left);
+    let mut right_chunks = natural_chunks(right);
+    loop {
+        let ordering = match (left_chunks.next(), right_chunks.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => {
+                let left_digits = left.starts_with(|c: char| c.is_ascii_digit());
+                let right_digits = right.starts_with(|c: char| c.is_ascii_digit());
+                if left_digits && right_digits {
+                    let left = left.trim_start_matches('0');
+                    let right = right.trim_start_matches('0');
+                    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+                } else {
+                    left.cmp(right)
+                }
+            }
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+/// Split a string into its alternating chunks of ASCII digits and of other characters.
+fn natural_chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let is_digit = first.is_ascii_digit();
+        let end = rest.find(|c: char| c.is_ascii_digit() != is_digit).unwrap_or(rest.len());
+        let (chunk, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(chunk)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::natural_cmp;
+
+    #[test]
+    fn natural_order() {
+        let mut values = vec!["item10", "item2", "item", "item02b", "item2a", "10", "9", "b", "a1"];
+        values.sort_by(|left, right| natural_cmp(left, right));
+        assert_eq!(values, ["9", "10", "a1", "b", "item", "item2", "item2a", "item02b", "item10"]);
+        assert_eq!(natural_cmp("item007", "item7"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,86 @@
+/*!
+This module tests the boosts of the search:
+
+1. the documents matching the boosts are promoted inside of the buckets of the ranking rules
+2. the documents are never moved to another bucket of the ranking rules
+3. the weights of the boosts matched by a document are summed, negative weights demote the documents
+4. the documents not matching any boost are still returned
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{Boost, Criterion, Filter, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("brand"), S("in_stock") });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "brand": "acme", "in_stock": false, "text": "red running shoes" },
+            { "id": 1, "brand": "globex", "in_stock": true, "text": "red running shoes" },
+            { "id": 2, "brand": "acme", "in_stock": true, "text": "red running shoes" },
+            { "id": 3, "brand": "globex", "in_stock": false, "text": "red shoes" },
+            { "id": 4, "brand": "acme", "in_stock": true, "text": "red shoes" },
+            { "id": 5, "brand": "initech", "in_stock": false, "text": "red shoes" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn boost(filter: &str, weight: f64) -> Boost {
+    Boost { filter: Filter::from_str(filter).unwrap().unwrap(), weight }
+}
+
+#[test]
+fn test_boost() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("red running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+
+    // the documents in stock are promoted, but only inside of the buckets of the words rule
+    let mut s = Search::new(&txn, &index);
+    s.query("red running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.boosts(vec![boost("in_stock = true", 1.0)]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0, 4, 3, 5]");
+
+    // the weights are summed and the negative weights demote the documents
+    let mut s = Search::new(&txn, &index);
+    s.query("red running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.boosts(vec![
+        boost("in_stock = true", 1.0),
+        boost("brand = acme", 2.0),
+        boost("brand = globex", -1.0),
+    ]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1, 4, 5, 3]");
+}
+
+#[test]
+fn test_boost_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boosts(vec![boost("brand = acme", 1.0), boost("in_stock = true", 1.0)]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 4, 0, 1, 3, 5]");
+}
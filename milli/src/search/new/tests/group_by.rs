@@ -0,0 +1,109 @@
+/*!
+This module tests the grouping of the search results:
+
+1. the groups are ordered by their best ranked document
+2. each group contains at most `hits_per_group` documents, in ranking order, and its total number of matching documents
+3. the offset and the limit paginate over the groups
+4. the documents with multiple values belong to multiple groups
+5. an error is returned if the field is not filterable
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Member, Search, SearchGroup, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+            s.set_filterable_fields(hashset! { S("company"), S("level") });
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "company": "Acme", "level": 1, "rank": 0, "title": "rust developer" },
+            { "id": 1, "company": "Globex", "level": 2, "rank": 7, "title": "rust developer" },
+            { "id": 2, "company": "Acme", "level": 2, "rank": 5, "title": "rust engineer" },
+            { "id": 3, "company": "Initech", "level": 1, "rank": 3, "title": "rust developer" },
+            { "id": 4, "company": "Acme", "level": 3, "rank": 6, "title": "rust developer" },
+            { "id": 5, "company": "Globex", "level": 1, "rank": 1, "title": "python developer" },
+            { "id": 6, "company": ["Initech", "Acme"], "level": 2, "rank": 2, "title": "rust" },
+            { "id": 7, "company": "Umbrella", "level": 1, "rank": 4, "title": "go developer" },
+            { "id": 8, "level": 1, "rank": 8, "title": "rust developer" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn groups_summary(groups: &[SearchGroup]) -> Vec<String> {
+    groups
+        .iter()
+        .map(|group| format!("{}: {:?} / {}", group.value, group.documents_ids, group.total))
+        .collect()
+}
+
+#[test]
+fn test_group_by() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("rust");
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.group_by(S("company"), 2);
+    let SearchResult { documents_ids, groups, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(groups_summary(&groups.unwrap()), @r###"
+    [
+        "\"Globex\": [1] / 1",
+        "\"Acme\": [4, 2] / 4",
+        "\"Initech\": [3, 6] / 2",
+    ]
+    "###);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 2, 3, 6]");
+
+    // the groups are paginated
+    let mut s = Search::new(&txn, &index);
+    s.query("rust");
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.group_by(S("company"), 1);
+    s.offset(1);
+    s.limit(1);
+    let SearchResult { groups, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(groups_summary(&groups.unwrap()), @r###"
+    [
+        "\"Acme\": [4] / 4",
+    ]
+    "###);
+
+    // numbers can also be used to group the documents
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+    s.group_by(S("level"), 3);
+    let SearchResult { groups, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(groups_summary(&groups.unwrap()), @r###"
+    [
+        "1.0: [0, 5, 3] / 5",
+        "2.0: [6, 2, 1] / 3",
+        "3.0: [4] / 1",
+    ]
+    "###);
+}
+
+#[test]
+fn test_group_by_not_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.group_by(S("title"), 1);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `title` is not filterable and thus cannot be used to group the search results. Available filterable attributes are: `company, level`.");
+}
@@ -0,0 +1,136 @@
+/*!
+This module tests the following properties about infix search:
+1. a word can be found from any of its substrings in the infix searchable attributes
+2. the other searchable attributes are not affected
+3. documents matching through an infix are ranked after the ones matching the word exactly
+4. infixes shorter than three characters are ignored
+5. updating the infix searchable attributes does not require the documents to be reindexed
+6. a word containing the query in both kinds of attributes only matches through the infix ones
+*/
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "sku".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "shirt",
+                "sku": "aa01",
+            },
+            {
+                "id": 1,
+                "title": "pack",
+                "sku": "tshirtpack",
+            },
+            {
+                "id": 2,
+                "title": "sweatshirt",
+                "sku": "bb02",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_infix_search() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("shirt");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.set_infix_searchable_fields(HashSet::from_iter(["sku".to_owned()]));
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // `sweatshirt` is not in an infix searchable attribute
+    let mut s = Search::new(&txn, &index);
+    s.query("shirt");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("irtpa");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("01");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.reset_infix_searchable_fields();
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("irtpa");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn test_infix_search_only_within_infix_attributes() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_infix_searchable_fields(HashSet::from_iter(["sku".to_owned()]));
+        })
+        .unwrap();
+
+    // the infixes of the new documents are added to the existing ones
+    index
+        .add_documents(documents!([
+            {
+                "id": 3,
+                "title": "overshirt",
+                "sku": "cc03",
+            },
+            {
+                "id": 4,
+                "title": "jacket",
+                "sku": "overshirt",
+            },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // `overshirt` is only matched in the `sku` attribute, after the exact match
+    let mut s = Search::new(&txn, &index);
+    s.query("shirt");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4]");
+}
@@ -1,16 +1,26 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boost;
 pub mod distinct;
 pub mod exactness;
 pub mod geo_sort;
+pub mod group_by;
+pub mod infix;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod random;
+pub mod ranking_function;
+pub mod ranking_rules_override;
+pub mod search_after;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
+pub mod synonym_sets;
+pub mod trace;
 pub mod typo;
 pub mod typo_proximity;
 pub mod words_tms;
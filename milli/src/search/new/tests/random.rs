@@ -0,0 +1,84 @@
+/*!
+This module tests the random ordering given by a seed at search time:
+
+1. the same seed always returns the documents in the same order
+2. the documents are only shuffled inside of the buckets of the other ranking rules
+3. the pagination is stable for a given seed
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "red shoes" },
+            { "id": 1, "text": "red" },
+            { "id": 2, "text": "red shoes" },
+            { "id": 3, "text": "red shoes" },
+            { "id": 4, "text": "red" },
+            { "id": 5, "text": "red shoes" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_random_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+
+    let mut s = Search::new(&txn, &index);
+    s.random_seed(42);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 2, 3, 4, 0, 1]");
+
+    // the same seed gives the same order
+    let mut s = Search::new(&txn, &index);
+    s.random_seed(42);
+    let SearchResult { documents_ids: again, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, again);
+
+    let mut s = Search::new(&txn, &index);
+    s.random_seed(7);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1, 5, 4, 3]");
+}
+
+#[test]
+fn test_random_keeps_buckets() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the documents matching both words are still returned first
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.random_seed(42);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 2, 3, 0, 4, 1]");
+
+    // the pages of the same seed don't overlap
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.random_seed(42);
+    s.offset(2);
+    s.limit(2);
+    let SearchResult { documents_ids: page, .. } = s.execute().unwrap();
+    assert_eq!(page, documents_ids[2..4]);
+}
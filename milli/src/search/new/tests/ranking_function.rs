@@ -0,0 +1,84 @@
+/*!
+This module tests the ranking rules scoring the documents with a function of a numeric field:
+
+1. the documents are sorted by decreasing bucket of score
+2. the documents in the same bucket are not sorted by their exact value
+3. the documents without a value for the field are returned last
+4. the ranking functions don't override the ranking rules placed before them
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "published_at": 100, "popularity": 0, "text": "red shoes" },
+            { "id": 1, "published_at": 85, "popularity": 1000, "text": "shoes" },
+            { "id": 2, "published_at": 103, "popularity": 5, "text": "red shoes" },
+            { "id": 3, "published_at": 130, "popularity": 150, "text": "red shoes" },
+            { "id": 4, "text": "red shoes" },
+            { "id": 5, "published_at": 99, "popularity": 2, "text": "shoes" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn set_criteria(index: &TempIndex, criteria: &[&str]) {
+    let criteria = criteria.iter().map(|criterion| criterion.parse().unwrap()).collect();
+    index
+        .update_settings(|s| {
+            s.set_criteria(criteria);
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_linear_decay() {
+    let index = create_index();
+    set_criteria(&index, &["published_at:linear(100, 10)"]);
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 5, 2, 1, 3, 4]");
+}
+
+#[test]
+fn test_log() {
+    let index = create_index();
+    set_criteria(&index, &["words", "popularity:log"]);
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // the documents 2 and 5 are in the same bucket and are thus sorted by docid
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 3, 2, 5, 0, 4]");
+
+    // the documents matching more words are still ranked first
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 2, 0, 4, 1, 5]");
+}
+
+#[test]
+fn test_ranking_function_fields_are_faceted() {
+    let index = create_index();
+    set_criteria(&index, &["popularity:log"]);
+
+    let txn = index.read_txn().unwrap();
+    let faceted_fields = index.user_defined_faceted_fields(&txn).unwrap();
+    assert!(faceted_fields.contains("popularity"));
+    assert_eq!(index.criteria(&txn).unwrap(), vec!["popularity:log".parse::<Criterion>().unwrap()]);
+}
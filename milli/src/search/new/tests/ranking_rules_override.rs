@@ -0,0 +1,89 @@
+/*!
+This module tests the ranking rules given at search time:
+
+1. they replace the ranking rules of the index for this search only
+2. the `sort` parameter can only be used if they contain the `sort` ranking rule
+3. their custom ranking rules can only use fields that are in the facet databases
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Member, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("date") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Proximity]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "date": 1, "text": "running shoes" },
+            { "id": 1, "date": 4, "text": "running" },
+            { "id": 2, "date": 3, "text": "running shoes" },
+            { "id": 3, "date": 5, "text": "running with red shoes" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn ranking_rules(rules: &[&str]) -> Vec<Criterion> {
+    rules.iter().map(|rule| rule.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_ranking_rules_override() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 3, 1]");
+
+    // the proximity ranking rule is replaced by a custom ranking rule
+    let mut s = Search::new(&txn, &index);
+    s.query("running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.ranking_rules(ranking_rules(&["words", "date:desc"]));
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 2, 0, 1]");
+
+    // the sort is the only ranking rule, the number of matching words is ignored
+    let mut s = Search::new(&txn, &index);
+    s.query("running shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.ranking_rules(ranking_rules(&["sort"]));
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("date")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1, 3]");
+
+    // the ranking rules of the index are left untouched
+    assert_eq!(index.criteria(&txn).unwrap(), vec![Criterion::Words, Criterion::Proximity]);
+}
+
+#[test]
+fn test_ranking_rules_override_errors() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(ranking_rules(&["words"]));
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("date")))]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.");
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(ranking_rules(&["words", "text:asc"]));
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `text` cannot be used in the ranking rules of the search as it is not filterable, sortable or used in the ranking rules of the index. Available attributes are: `date`.");
+}
@@ -0,0 +1,146 @@
+/*!
+This module tests the search-after cursors:
+
+1. paginating with cursors returns the same documents, in the same order, as a single search
+2. it is also true when a distinct attribute is set
+3. the cursor of the last document is returned, even without any ranking rule
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use super::collect_field_values;
+use crate::index::tests::TempIndex;
+use crate::{
+    AscDesc, Criterion, Index, Member, Search, SearchCursor, SearchResult, TermsMatchingStrategy,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo, Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "letter": "A", "rank": 3, "text": "the quick brown fox" },
+            { "id": 1, "letter": "A", "rank": 1, "text": "the quick brown fox" },
+            { "id": 2, "letter": "B", "rank": 2, "text": "the quikc brown fox" },
+            { "id": 3, "letter": "B", "rank": 0, "text": "the quick brown" },
+            { "id": 4, "letter": "C", "rank": 4, "text": "the quick" },
+            { "id": 5, "letter": "C", "rank": 2, "text": "the quikc brown" },
+            { "id": 6, "letter": "D", "rank": 1, "text": "the quick fox" },
+            { "id": 7, "letter": "D", "rank": 3, "text": "the quick brown fox" },
+            { "id": 8, "letter": "E", "rank": 0, "text": "the quikc fox" },
+            { "id": 9, "letter": "E", "rank": 5, "text": "the" },
+            { "id": 10, "letter": "F", "rank": 1, "text": "the quick brown foxes" },
+            { "id": 11, "letter": "F", "rank": 1, "text": "the quick brown fox" },
+        ]))
+        .unwrap();
+    index
+}
+
+/// Paginate over all the results of the search by following the cursors.
+fn paginate(index: &Index, query: &str, page_size: usize) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut documents_ids = Vec::new();
+    let mut cursor: Option<SearchCursor> = None;
+
+    loop {
+        let mut s = Search::new(&txn, index);
+        s.query(query);
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+        s.limit(page_size);
+        if let Some(cursor) = cursor.take() {
+            s.search_after(cursor);
+        }
+
+        let SearchResult { documents_ids: page, cursor: next_cursor, .. } = s.execute().unwrap();
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.len() <= page_size);
+        assert_eq!(next_cursor.as_ref().map(|cursor| cursor.docid), page.last().copied());
+        documents_ids.extend(page);
+        cursor = next_cursor;
+    }
+
+    documents_ids
+}
+
+fn search_all(index: &Index, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.limit(100);
+    s.execute().unwrap().documents_ids
+}
+
+#[test]
+fn test_search_after() {
+    let index = create_index();
+
+    let all = search_all(&index, "the quick brown fox");
+    insta::assert_snapshot!(format!("{all:?}"), @"[0, 7, 1, 10, 11, 2, 3, 5, 4, 6, 8, 9]");
+
+    for page_size in 1..=5 {
+        assert_eq!(paginate(&index, "the quick brown fox", page_size), all, "{page_size}");
+    }
+}
+
+#[test]
+fn test_search_after_placeholder() {
+    let index = create_index();
+
+    let all = search_all(&index, "");
+    for page_size in 1..=5 {
+        assert_eq!(paginate(&index, "", page_size), all, "{page_size}");
+    }
+}
+
+#[test]
+fn test_search_after_distinct() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_distinct_field("letter".to_owned());
+        })
+        .unwrap();
+
+    let all = search_all(&index, "the quick brown fox");
+    let txn = index.read_txn().unwrap();
+    let letters = collect_field_values(&index, &txn, "letter", &all);
+    // only one document is returned for each letter
+    assert_eq!(letters.len(), 6);
+
+    for page_size in 1..=5 {
+        assert_eq!(paginate(&index, "the quick brown fox", page_size), all, "{page_size}");
+    }
+}
+
+#[test]
+fn test_search_after_without_ranking_rules() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // a placeholder search without any sort criteria doesn't use any ranking rule
+    let mut s = Search::new(&txn, &index);
+    s.limit(4);
+    let SearchResult { documents_ids, cursor, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.limit(4);
+    s.search_after(cursor.unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 5, 6, 7]");
+}
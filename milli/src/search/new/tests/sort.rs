@@ -9,6 +9,7 @@ This module tests the `sort` ranking rule:
 6. documents with either: (1) no value, (2) null, or (3) an object for the field-to-sort appear at the end of the bucket
 7. boolean values are translated to strings
 8. if a field contains an array, it is sorted by the best value in the array according to the sort rule
+9. the documents without a value can be placed first and the strings can be compared in natural order
 */
 
 use big_s::S;
@@ -16,7 +17,9 @@ use maplit::hashset;
 
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
-use crate::{AscDesc, Criterion, Member, Search, SearchResult, TermsMatchingStrategy};
+use crate::{
+    AscDesc, Criterion, Member, Search, SearchResult, SortCriterion, TermsMatchingStrategy,
+};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -313,3 +316,57 @@ fn test_sort() {
     ]
     "###);
 }
+
+fn create_index_with_missing_values() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("name"), S("price") });
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "item10", "price": 3 },
+            { "id": 1, "name": "Item2" },
+            { "id": 2, "name": "item1", "price": 1 },
+            { "id": 3, "price": 2 },
+            { "id": 4, "name": "apple" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn sort_with_options(index: &TempIndex, criteria: &[&str]) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    let criteria: Vec<SortCriterion> = criteria.iter().map(|c| c.parse().unwrap()).collect();
+    s.sort_criteria_with_options(criteria);
+    s.execute().unwrap().documents_ids
+}
+
+#[test]
+fn test_sort_options() {
+    let index = create_index_with_missing_values();
+
+    let documents_ids = sort_with_options(&index, &["name:asc"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 2, 0, 1, 3]");
+
+    let documents_ids = sort_with_options(&index, &["name:asc:collation:natural"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 2, 1, 0, 3]");
+
+    let documents_ids = sort_with_options(&index, &["name:desc:collation:natural"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 4, 3]");
+
+    let documents_ids = sort_with_options(&index, &["price:asc:missing:first"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 2, 3, 0]");
+
+    let documents_ids = sort_with_options(&index, &["price:desc:missing:first"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 0, 3, 2]");
+
+    let documents_ids = sort_with_options(&index, &["name:asc:collation:natural:missing:first"]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 4, 2, 1, 0]");
+}
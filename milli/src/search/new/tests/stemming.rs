@@ -0,0 +1,94 @@
+/*!
+This module tests the following properties about stemming:
+1. a word can be found from any word sharing its stem when a stemming language is set
+2. documents containing the exact query word are ranked before the ones matching through a stem
+3. no stem is indexed when no stemming language is set
+4. the stems are not part of the words FST, so they are never typo or prefix derivations
+*/
+
+use maplit::btreeset;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, StemmingLanguage, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "many houses",
+            },
+            {
+                "id": 1,
+                "title": "the house",
+            },
+            {
+                "id": 2,
+                "title": "running fast",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_stemming() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("house");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.set_stemming_languages(btreeset! { StemmingLanguage::English });
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let words_fst = index.words_fst(&txn).unwrap();
+    assert!(words_fst.contains("house"));
+    assert!(!words_fst.contains("\u{1}hous"));
+    assert!(index.word_docids.get(&txn, "\u{1}hous").unwrap().is_some());
+
+    // the exact `house` is ranked first
+    let mut s = Search::new(&txn, &index);
+    s.query("house");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("runs");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.reset_stemming_languages();
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("runs");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
@@ -0,0 +1,156 @@
+/*!
+This module tests the following properties about synonym sets:
+1. the query is expanded with the synonyms of the index and all the synonym sets by default
+2. the synonyms can be disabled or restricted to some sets at search time
+3. the synonym sets are added and removed one by one without touching the other sets
+4. an unknown synonym set is rejected at search time
+*/
+
+use std::collections::BTreeMap;
+
+use maplit::hashmap;
+
+use crate::error::{Error, UserError};
+use crate::index::tests::TempIndex;
+use crate::update::Setting;
+use crate::{Search, SearchResult, SynonymsSelection, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+            s.set_synonyms(hashmap! {
+                "sofa".to_owned() => vec!["couch".to_owned()],
+            });
+            s.set_synonym_sets(BTreeMap::from([
+                (
+                    "furniture".to_owned(),
+                    Setting::Set(hashmap! {
+                        "sofa".to_owned() => vec!["settee".to_owned()],
+                    }),
+                ),
+                (
+                    "colors".to_owned(),
+                    Setting::Set(hashmap! {
+                        "red".to_owned() => vec!["scarlet".to_owned()],
+                    }),
+                ),
+            ]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "a sofa",
+            },
+            {
+                "id": 1,
+                "title": "a couch",
+            },
+            {
+                "id": 2,
+                "title": "a settee",
+            },
+            {
+                "id": 3,
+                "title": "a scarlet chair",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str, synonyms: SynonymsSelection) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.synonyms(synonyms);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn test_synonyms_selection() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "sofa", SynonymsSelection::All);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let documents_ids = search(&index, "sofa", SynonymsSelection::None);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    // the synonyms of the index are not used when sets are selected
+    let documents_ids =
+        search(&index, "sofa", SynonymsSelection::Sets(vec!["furniture".to_owned()]));
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+
+    let documents_ids = search(&index, "sofa", SynonymsSelection::Sets(vec!["colors".to_owned()]));
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    let documents_ids = search(&index, "red", SynonymsSelection::All);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3]");
+}
+
+#[test]
+fn test_incremental_synonym_sets() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_synonym_sets(BTreeMap::from([("colors".to_owned(), Setting::Reset)]));
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let synonym_sets = index.synonym_sets(&txn).unwrap();
+    insta::assert_debug_snapshot!(synonym_sets.keys().collect::<Vec<_>>(), @r###"
+    [
+        "furniture",
+    ]
+    "###);
+    drop(txn);
+
+    let documents_ids = search(&index, "red", SynonymsSelection::All);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+    let documents_ids = search(&index, "sofa", SynonymsSelection::All);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    // a reset followed by a new set in the same update only keeps the new set
+    index
+        .update_settings(|s| {
+            s.reset_synonym_sets();
+            s.set_synonym_sets(BTreeMap::from([(
+                "colors".to_owned(),
+                Setting::Set(hashmap! { "red".to_owned() => vec!["scarlet".to_owned()] }),
+            )]));
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let synonym_sets = index.synonym_sets(&txn).unwrap();
+    insta::assert_debug_snapshot!(synonym_sets.keys().collect::<Vec<_>>(), @r###"
+    [
+        "colors",
+    ]
+    "###);
+}
+
+#[test]
+fn test_unknown_synonym_set() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("sofa");
+    s.synonyms(SynonymsSelection::Sets(vec!["furniture".to_owned(), "animals".to_owned()]));
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidSynonymSet { .. })));
+    insta::assert_snapshot!(error, @"Synonym set `animals` does not exist and thus cannot be used to expand the search query. Available synonym sets are: `colors, furniture`.");
+}
@@ -0,0 +1,115 @@
+/*!
+This module tests the trace of the ranking rules returned by a search in debug mode:
+
+1. it is only returned when it is requested
+2. it contains the derivations of the terms of the query
+3. it records the buckets of every ranking rule
+4. it records a bounded number of events
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::limits::MAX_TRACE_EVENT_COUNT;
+use crate::search::new::logger::trace::TraceSearchLogger;
+use crate::search::new::{QueryGraph, SearchContext, SearchLogger};
+use crate::{
+    Criterion, Search, SearchResult, SearchTrace, TermsMatchingStrategy, TraceEvent,
+    TraceQueryNodeKind,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "red shoes" },
+            { "id": 1, "text": "red" },
+            { "id": 2, "text": "red shoos" },
+            { "id": 3, "text": "blue" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_trace_query() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { trace, .. } = s.execute().unwrap();
+    assert!(trace.is_none());
+
+    let mut s = Search::new(&txn, &index);
+    s.query("red shoes");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.debug(true);
+    let SearchResult { documents_ids, trace, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1]");
+    let SearchTrace { query_graph, universe_len, ranking_rules, events, .. } = trace.unwrap();
+
+    assert_eq!(universe_len, 3);
+    assert_eq!(ranking_rules, vec!["words".to_owned(), "typo".to_owned()]);
+
+    let query_graph = query_graph.unwrap();
+    assert_eq!(query_graph.iter().filter(|n| n.kind == TraceQueryNodeKind::Start).count(), 1);
+    assert_eq!(query_graph.iter().filter(|n| n.kind == TraceQueryNodeKind::End).count(), 1);
+    let shoes = query_graph
+        .iter()
+        .filter_map(|n| n.term.as_ref())
+        .find(|term| term.original == "shoes")
+        .unwrap();
+    assert_eq!(shoes.max_typos, 1);
+    assert!(shoes.words.contains(&"shoes".to_owned()));
+    assert!(shoes.words.contains(&"shoos".to_owned()));
+
+    assert_eq!(events.first(), Some(&TraceEvent::StartIteration { ranking_rule: 0, universe_len }));
+    let results_len: usize = events
+        .iter()
+        .map(|e| match e {
+            TraceEvent::AddToResults { documents_len } => *documents_len,
+            _ => 0,
+        })
+        .sum();
+    assert_eq!(results_len, documents_ids.len());
+    assert!(events.iter().any(|e| matches!(e, TraceEvent::NextBucket { ranking_rule: 1, .. })));
+}
+
+#[test]
+fn test_trace_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.debug(true);
+    let SearchResult { documents_ids, trace, .. } = s.execute().unwrap();
+    let SearchTrace { query_graph, universe_len, .. } = trace.unwrap();
+
+    assert!(query_graph.is_none());
+    assert_eq!(universe_len, documents_ids.len() as u64);
+}
+
+#[test]
+fn test_trace_events_limit() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let mut ctx = SearchContext::new(&index, &txn);
+
+    let mut logger = TraceSearchLogger::default();
+    for docid in 0..MAX_TRACE_EVENT_COUNT as u32 + 10 {
+        SearchLogger::<QueryGraph>::add_to_results(&mut logger, &[docid]);
+    }
+    let SearchTrace { events, dropped_events, .. } = logger.finish(&mut ctx).unwrap();
+
+    assert_eq!(events.len(), MAX_TRACE_EVENT_COUNT);
+    assert_eq!(dropped_events, 10);
+}
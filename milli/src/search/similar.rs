@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use charabia::TokenizerBuilder;
+use serde_json::Value;
+
+use super::new::PartialSearchResult;
+use super::{Filter, MatchingWords, SearchResult, TermsMatchingStrategy};
+use crate::error::InternalError;
+use crate::update::json_to_string;
+use crate::{
+    execute_search, filtered_universe, DefaultSearchLogger, DocumentId, GeoSortStrategy, Index,
+    Result, SearchContext, MAX_WORD_LENGTH,
+};
+
+/// Search the documents sharing the most discriminative words of a given document.
+pub struct Similar<'a> {
+    id: DocumentId,
+    filter: Option<Filter<'a>>,
+    offset: usize,
+    limit: usize,
+    words_limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Similar<'a> {
+    pub fn new(id: DocumentId, rtxn: &'a heed::RoTxn, index: &'a Index) -> Similar<'a> {
+        Similar { id, filter: None, offset: 0, limit: 20, words_limit: 10, rtxn, index }
+    }
+
+    pub fn offset(&mut self, offset: usize) -> &mut Similar<'a> {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Similar<'a> {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the maximum number of words of the document used to find the similar documents.
+    pub fn words_limit(&mut self, value: usize) -> &mut Similar<'a> {
+        self.words_limit = value;
+        self
+    }
+
+    pub fn filter(&mut self, condition: Filter<'a>) -> &mut Similar<'a> {
+        self.filter = Some(condition);
+        self
+    }
+
+    /// Returns the most discriminative words of the document, the most discriminative first.
+    ///
+    /// A word is discriminative when it is frequent in the searchable attributes of the
+    /// document but rare in the rest of the index. The frequency of a word in the document
+    /// only counts logarithmically so that repeated common words can't outweigh the rare ones.
+    /// The stop words, the words that only appear in this document and the words that appear
+    /// in every document are ignored as they can't help finding similar documents.
+    pub fn discriminative_words(&self) -> Result<Vec<String>> {
+        let Some((_, obkv)) = self.index.documents(self.rtxn, Some(self.id))?.pop() else {
+            return Ok(Vec::new());
+        };
+        let searchable_fields = self.index.searchable_fields_ids(self.rtxn)?;
+
+        let mut tokenizer_builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokenizer_builder.stop_words(stop_words);
+        }
+        let script_lang_map = self.index.script_language(self.rtxn)?;
+        if !script_lang_map.is_empty() {
+            tokenizer_builder.allow_list(&script_lang_map);
+        }
+        let tokenizer = tokenizer_builder.build();
+
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        let mut buffer = String::new();
+        for (field_id, field_bytes) in obkv.iter() {
+            if searchable_fields.as_ref().map_or(false, |sf| !sf.contains(&field_id)) {
+                continue;
+            }
+
+            let value: Value =
+                serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
+            buffer.clear();
+            if let Some(field) = json_to_string(&value, &mut buffer) {
+                // the stop words are not words for the tokenizer
+                for token in tokenizer.tokenize(field).filter(|token| token.is_word()) {
+                    let word = token.lemma().trim();
+                    if !word.is_empty() && word.len() <= MAX_WORD_LENGTH {
+                        *frequencies.entry(word.to_owned()).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let number_of_documents = self.index.number_of_documents(self.rtxn)? as f64;
+        let mut scored_words = Vec::new();
+        for (word, frequency) in frequencies {
+            let documents_count = self.index.word_documents_count(self.rtxn, &word)?.unwrap_or(0);
+            if documents_count <= 1 {
+                continue;
+            }
+
+            let term_frequency = 1.0 + (frequency as f64).ln();
+            let score = term_frequency * (number_of_documents / documents_count as f64).ln();
+            if score > 0.0 {
+                scored_words.push((score, word));
+            }
+        }
+
+        scored_words.sort_unstable_by(|(left_score, left), (right_score, right)| {
+            right_score.total_cmp(left_score).then_with(|| left.cmp(right))
+        });
+
+        Ok(scored_words.into_iter().take(self.words_limit).map(|(_, word)| word).collect())
+    }
+
+    pub fn execute(&self) -> Result<SearchResult> {
+        let words = self.discriminative_words()?;
+        if words.is_empty() {
+            return Ok(SearchResult::default());
+        }
+
+        let mut ctx = SearchContext::new(self.index, self.rtxn);
+        let mut universe = filtered_universe(&ctx, &self.filter)?;
+        // A document is not similar to itself.
+        universe.remove(self.id);
+
+        // The trailing space prevents the last word from being used as a prefix.
+        let query = format!("{} ", words.join(" "));
+        let PartialSearchResult { located_query_terms, candidates, documents_ids, cursor } =
+            execute_search(
+                &mut ctx,
+                &Some(query),
+                TermsMatchingStrategy::Last,
+                false,
+                universe,
+                &None,
+                &None,
+                GeoSortStrategy::default(),
+                &[],
+                None,
+                self.offset,
+                self.limit,
+                None,
+                Some(self.words_limit),
+                None,
+                &mut DefaultSearchLogger,
+                &mut DefaultSearchLogger,
+            )?;
+
+        let matching_words = match located_query_terms {
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms)?,
+            None => MatchingWords::default(),
+        };
+
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            documents_ids,
+            cursor,
+            groups: None,
+            trace: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use crate::index::tests::TempIndex;
+    use crate::Similar;
+
+    fn create_index() -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_searchable_fields(vec!["title".to_owned()]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "the red dragon of the north", "kind": "dragon" },
+                { "id": 1, "title": "the blue dragon of the south", "kind": "dragon" },
+                { "id": 2, "title": "a red apple", "kind": "fruit" },
+                { "id": 3, "title": "the story of the north", "kind": "book" },
+                { "id": 4, "title": "epilogue", "kind": "book" },
+            ]))
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn discriminative_words() {
+        let index = create_index();
+        let txn = index.read_txn().unwrap();
+
+        // `the` and `of` are more common than `north`, `red` and `dragon`,
+        // appearing twice in the document only makes `the` a bit more discriminative than `of`
+        let similar = Similar::new(0, &txn, &index);
+        let words = similar.discriminative_words().unwrap();
+        insta::assert_debug_snapshot!(words, @r###"
+        [
+            "dragon",
+            "north",
+            "red",
+            "the",
+            "of",
+        ]
+        "###);
+
+        // no word of the document is shared with another one
+        let similar = Similar::new(4, &txn, &index);
+        let words = similar.discriminative_words().unwrap();
+        assert!(words.is_empty());
+        drop(txn);
+
+        index
+            .update_settings(|s| {
+                s.set_stop_words(BTreeSet::from(["the".to_owned(), "of".to_owned()]));
+            })
+            .unwrap();
+
+        // the stop words are never used
+        let txn = index.read_txn().unwrap();
+        let similar = Similar::new(0, &txn, &index);
+        let words = similar.discriminative_words().unwrap();
+        insta::assert_debug_snapshot!(words, @r###"
+        [
+            "dragon",
+            "north",
+            "red",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn similar_documents() {
+        let index = create_index();
+        let txn = index.read_txn().unwrap();
+
+        let similar = Similar::new(0, &txn, &index);
+        let result = similar.execute().unwrap();
+        // the documents must contain `dragon`, the most discriminative word,
+        // which excludes `the story of the north` despite the other shared words
+        insta::assert_snapshot!(format!("{:?}", result.documents_ids), @"[1]");
+    }
+}
@@ -0,0 +1,120 @@
+use deserr::Deserr;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+
+/// The character prefixing the stems in the word databases,
+/// it makes them distinct from the original words of the documents.
+pub const STEM_MARKER: char = '\u{1}';
+
+/// Returns `true` if the word of a word database is a stem added by the [`Stemmers`].
+pub fn is_stem(word: impl AsRef<[u8]>) -> bool {
+    word.as_ref().first() == Some(&(STEM_MARKER as u8))
+}
+
+/// A language whose words are indexed and searched along with their stem,
+/// computed with the Snowball stemming algorithm of the language.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Deserr,
+)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum StemmingLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl StemmingLanguage {
+    fn algorithm(self) -> Algorithm {
+        match self {
+            StemmingLanguage::Arabic => Algorithm::Arabic,
+            StemmingLanguage::Danish => Algorithm::Danish,
+            StemmingLanguage::Dutch => Algorithm::Dutch,
+            StemmingLanguage::English => Algorithm::English,
+            StemmingLanguage::Finnish => Algorithm::Finnish,
+            StemmingLanguage::French => Algorithm::French,
+            StemmingLanguage::German => Algorithm::German,
+            StemmingLanguage::Greek => Algorithm::Greek,
+            StemmingLanguage::Hungarian => Algorithm::Hungarian,
+            StemmingLanguage::Italian => Algorithm::Italian,
+            StemmingLanguage::Norwegian => Algorithm::Norwegian,
+            StemmingLanguage::Portuguese => Algorithm::Portuguese,
+            StemmingLanguage::Romanian => Algorithm::Romanian,
+            StemmingLanguage::Russian => Algorithm::Russian,
+            StemmingLanguage::Spanish => Algorithm::Spanish,
+            StemmingLanguage::Swedish => Algorithm::Swedish,
+            StemmingLanguage::Tamil => Algorithm::Tamil,
+            StemmingLanguage::Turkish => Algorithm::Turkish,
+        }
+    }
+}
+
+/// The stemmers of the stemming languages of an index.
+///
+/// The same stemmers must be used when indexing the documents and when searching them,
+/// every language is applied to every word regardless of the detected language
+/// to keep both sides consistent.
+#[derive(Default)]
+pub struct Stemmers {
+    stemmers: Vec<Stemmer>,
+}
+
+impl Stemmers {
+    pub fn new(languages: &[StemmingLanguage]) -> Self {
+        Self { stemmers: languages.iter().map(|l| Stemmer::create(l.algorithm())).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stemmers.is_empty()
+    }
+
+    /// Returns the stems of the given normalized word, prefixed by the [`STEM_MARKER`].
+    ///
+    /// A stem identical to the word itself is not returned,
+    /// the word is already indexed as is.
+    pub fn stems(&self, word: &str) -> Vec<String> {
+        let mut stems: Vec<String> = Vec::new();
+        for stemmer in &self.stemmers {
+            let stem = stemmer.stem(word);
+            if !stem.is_empty() && stem != word {
+                let stem = format!("{STEM_MARKER}{stem}");
+                if !stems.contains(&stem) {
+                    stems.push(stem);
+                }
+            }
+        }
+        stems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems() {
+        let stemmers = Stemmers::new(&[StemmingLanguage::English]);
+        assert_eq!(stemmers.stems("running"), vec!["\u{1}run".to_string()]);
+        assert_eq!(stemmers.stems("houses"), vec!["\u{1}hous".to_string()]);
+        // the stem is the word itself
+        assert!(stemmers.stems("run").is_empty());
+
+        let stemmers = Stemmers::new(&[]);
+        assert!(stemmers.stems("running").is_empty());
+    }
+}
@@ -51,6 +51,7 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         // We clean some of the main engine datastructures.
         self.index.put_words_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+        self.index.put_words_infixes_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_external_documents_ids(self.wtxn, &ExternalDocumentsIds::default())?;
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_soft_deleted_documents_ids(self.wtxn, &empty_roaring)?;
@@ -101,6 +102,54 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
     }
 }
 
+/// Clears the databases built from the words of the searchable attributes,
+/// the documents and the facet databases are kept as is.
+pub(crate) fn clear_word_databases(wtxn: &mut heed::RwTxn, index: &Index) -> Result<()> {
+    index.put_words_fst(wtxn, &fst::Set::default())?;
+    index.put_words_prefixes_fst(wtxn, &fst::Set::default())?;
+    index.put_words_infixes_fst(wtxn, &fst::Set::default())?;
+
+    index.word_docids.clear(wtxn)?;
+    index.exact_word_docids.clear(wtxn)?;
+    index.word_prefix_docids.clear(wtxn)?;
+    index.exact_word_prefix_docids.clear(wtxn)?;
+    index.word_pair_proximity_docids.clear(wtxn)?;
+    index.word_prefix_pair_proximity_docids.clear(wtxn)?;
+    index.prefix_word_pair_proximity_docids.clear(wtxn)?;
+    index.word_position_docids.clear(wtxn)?;
+    index.word_fid_docids.clear(wtxn)?;
+    index.field_id_word_count_docids.clear(wtxn)?;
+    index.word_prefix_position_docids.clear(wtxn)?;
+    index.word_prefix_fid_docids.clear(wtxn)?;
+    index.script_language_docids.clear(wtxn)?;
+
+    Ok(())
+}
+
+/// Clears the databases built from the values of the faceted attributes,
+/// the documents and the word databases are kept as is.
+pub(crate) fn clear_facet_databases(wtxn: &mut heed::RwTxn, index: &Index) -> Result<()> {
+    let empty_roaring = RoaringBitmap::default();
+
+    index.delete_geo_rtree(wtxn)?;
+    index.delete_geo_faceted_documents_ids(wtxn)?;
+
+    for field_id in index.faceted_fields_ids(wtxn)? {
+        index.put_faceted_documents_ids(wtxn, field_id, FacetType::Number, &empty_roaring)?;
+        index.put_faceted_documents_ids(wtxn, field_id, FacetType::String, &empty_roaring)?;
+    }
+
+    index.facet_id_f64_docids.clear(wtxn)?;
+    index.facet_id_string_docids.clear(wtxn)?;
+    index.facet_id_exists_docids.clear(wtxn)?;
+    index.facet_id_is_null_docids.clear(wtxn)?;
+    index.facet_id_is_empty_docids.clear(wtxn)?;
+    index.field_id_docid_facet_f64s.clear(wtxn)?;
+    index.field_id_docid_facet_strings.clear(wtxn)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +180,7 @@ mod tests {
 
         assert!(index.words_fst(&rtxn).unwrap().is_empty());
         assert!(index.words_prefixes_fst(&rtxn).unwrap().is_empty());
+        assert!(index.words_infixes_fst(&rtxn).unwrap().is_empty());
         assert!(index.external_documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
@@ -0,0 +1,373 @@
+use std::io::Cursor;
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use super::index_documents::primary_key_roots;
+use super::{IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig};
+use crate::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
+use crate::error::UserError;
+use crate::update::UpdateIndexingStep;
+use crate::{all_obkv_to_json, ExternalDocumentsIds, Index, Object, Result};
+
+/// An edition applied on a top-level field of the documents by an [`EditDocuments`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase", deny_unknown_fields)]
+pub enum EditOperation {
+    /// Sets the field to the given value, whatever it was before.
+    Set { field: String, value: Value },
+    /// Removes the field from the document.
+    Unset { field: String },
+    /// Adds `by` to the numeric field, a missing field is considered to be `0`.
+    Increment {
+        field: String,
+        #[serde(default = "default_increment")]
+        by: Number,
+    },
+    /// Appends the value to the array field, a missing field is considered to be an empty array.
+    Push { field: String, value: Value },
+    /// Removes every occurrence of the value from the array field.
+    Remove { field: String, value: Value },
+}
+
+fn default_increment() -> Number {
+    Number::from(1)
+}
+
+impl EditOperation {
+    pub fn field(&self) -> &str {
+        match self {
+            EditOperation::Set { field, .. }
+            | EditOperation::Unset { field }
+            | EditOperation::Increment { field, .. }
+            | EditOperation::Push { field, .. }
+            | EditOperation::Remove { field, .. } => field,
+        }
+    }
+
+    /// Applies the operation on the document and returns a description of
+    /// the problem when the field has not the expected type.
+    fn apply(&self, document: &mut Object) -> std::result::Result<(), String> {
+        match self {
+            EditOperation::Set { field, value } => {
+                document.insert(field.clone(), value.clone());
+            }
+            EditOperation::Unset { field } => {
+                document.remove(field);
+            }
+            EditOperation::Increment { field, by } => {
+                let current = match document.get(field) {
+                    None | Some(Value::Null) => Number::from(0),
+                    Some(Value::Number(number)) => number.clone(),
+                    Some(value) => {
+                        return Err(format!(
+                            "cannot increment the non-numeric `{field}` field: `{value}`"
+                        ))
+                    }
+                };
+                let sum = match (current.as_i64(), by.as_i64()) {
+                    (Some(current), Some(by)) => current.checked_add(by).map(Number::from),
+                    _ => current
+                        .as_f64()
+                        .zip(by.as_f64())
+                        .and_then(|(current, by)| Number::from_f64(current + by)),
+                }
+                .ok_or_else(|| format!("incrementing the `{field}` field by `{by}` overflows"))?;
+                document.insert(field.clone(), Value::Number(sum));
+            }
+            EditOperation::Push { field, value } => match document.get_mut(field) {
+                None | Some(Value::Null) => {
+                    document.insert(field.clone(), Value::Array(vec![value.clone()]));
+                }
+                Some(Value::Array(values)) => values.push(value.clone()),
+                Some(other) => {
+                    return Err(format!(
+                        "cannot push into the non-array `{field}` field: `{other}`"
+                    ))
+                }
+            },
+            EditOperation::Remove { field, value } => match document.get_mut(field) {
+                None | Some(Value::Null) => (),
+                Some(Value::Array(values)) => values.retain(|v| v != value),
+                Some(other) => {
+                    return Err(format!(
+                        "cannot remove from the non-array `{field}` field: `{other}`"
+                    ))
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies a list of [`EditOperation`]s on a set of documents and reindexes the ones that changed.
+pub struct EditDocuments<'t, 'u, 'i, 'a, FP, FA> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+    indexer_config: &'a IndexerConfig,
+    external_documents_ids: ExternalDocumentsIds<'static>,
+    to_edit_docids: RoaringBitmap,
+    operations: Vec<EditOperation>,
+    progress: FP,
+    should_abort: FA,
+}
+
+impl<'t, 'u, 'i, 'a, FP, FA> EditDocuments<'t, 'u, 'i, 'a, FP, FA>
+where
+    FP: Fn(UpdateIndexingStep) + Sync,
+    FA: Fn() -> bool + Sync,
+{
+    pub fn new(
+        wtxn: &'t mut heed::RwTxn<'i, 'u>,
+        index: &'i Index,
+        indexer_config: &'a IndexerConfig,
+        operations: Vec<EditOperation>,
+        progress: FP,
+        should_abort: FA,
+    ) -> Result<EditDocuments<'t, 'u, 'i, 'a, FP, FA>> {
+        let external_documents_ids = index.external_documents_ids(wtxn)?.into_static();
+
+        Ok(EditDocuments {
+            wtxn,
+            index,
+            indexer_config,
+            external_documents_ids,
+            to_edit_docids: RoaringBitmap::new(),
+            operations,
+            progress,
+            should_abort,
+        })
+    }
+
+    pub fn edit_documents(&mut self, docids: &RoaringBitmap) {
+        self.to_edit_docids |= docids;
+    }
+
+    pub fn edit_external_id(&mut self, external_id: &str) -> Option<u32> {
+        let docid = self.external_documents_ids.get(external_id)?;
+        self.to_edit_docids.insert(docid);
+        Some(docid)
+    }
+
+    /// Returns the number of documents that were modified by the operations.
+    pub fn execute(self) -> Result<u64> {
+        let to_edit_docids = self.to_edit_docids & self.index.documents_ids(self.wtxn)?;
+        if to_edit_docids.is_empty() || self.operations.is_empty() {
+            return Ok(0);
+        }
+
+        // Editing the primary key would move the document under another identifier.
+        let primary_key = self.index.primary_key(self.wtxn)?.map(String::from);
+        if let Some(primary_key) = &primary_key {
+            if let Some(operation) = self.operations.iter().find(|operation| {
+                primary_key_roots(primary_key).any(|root| root == operation.field())
+            }) {
+                return Err(
+                    UserError::PrimaryKeyCannotBeEdited(operation.field().to_string()).into()
+                );
+            }
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        for result in self.index.iter_documents(self.wtxn, to_edit_docids)? {
+            let (_docid, obkv) = result?;
+            let original = all_obkv_to_json(obkv, &fields_ids_map)?;
+            let mut document = original.clone();
+            for operation in &self.operations {
+                operation.apply(&mut document).map_err(|reason| {
+                    let document_id = primary_key
+                        .as_ref()
+                        .and_then(|primary_key| original.get(primary_key))
+                        .cloned()
+                        .unwrap_or(Value::Null);
+                    UserError::InvalidDocumentEdition { document_id, reason }
+                })?;
+            }
+            if document != original {
+                builder.append_json_object(&document)?;
+            }
+        }
+
+        let edited_documents = builder.documents_count() as u64;
+        if edited_documents == 0 {
+            return Ok(0);
+        }
+
+        let vector = builder.into_inner()?;
+        let reader = DocumentsBatchReader::from_reader(Cursor::new(vector))?;
+        let config = IndexDocumentsConfig {
+            update_method: IndexDocumentsMethod::ReplaceDocuments,
+            ..Default::default()
+        };
+        let builder = IndexDocuments::new(
+            self.wtxn,
+            self.index,
+            self.indexer_config,
+            config,
+            self.progress,
+            self.should_abort,
+        )?;
+        let (builder, user_result) = builder.add_documents(reader)?;
+        user_result?;
+        builder.execute()?;
+
+        Ok(edited_documents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db_snap;
+    use crate::index::tests::TempIndex;
+
+    fn edit_documents(
+        index: &TempIndex,
+        external_ids: &[&str],
+        operations: Vec<EditOperation>,
+    ) -> Result<u64> {
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = EditDocuments::new(
+            &mut wtxn,
+            index,
+            &index.indexer_config,
+            operations,
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        for id in external_ids {
+            builder.edit_external_id(id);
+        }
+        let edited_documents = builder.execute()?;
+        wtxn.commit().unwrap();
+        Ok(edited_documents)
+    }
+
+    fn operations(operations: Value) -> Vec<EditOperation> {
+        serde_json::from_value(operations).unwrap()
+    }
+
+    #[test]
+    fn edit_operations_deserialization() {
+        let operations = operations(json!([
+            { "op": "set", "field": "title", "value": "hello" },
+            { "op": "unset", "field": "draft" },
+            { "op": "increment", "field": "views" },
+            { "op": "increment", "field": "stock", "by": -2.5 },
+            { "op": "push", "field": "tags", "value": "new" },
+            { "op": "remove", "field": "tags", "value": "old" },
+        ]));
+        insta::assert_debug_snapshot!(operations, @r###"
+        [
+            Set {
+                field: "title",
+                value: String("hello"),
+            },
+            Unset {
+                field: "draft",
+            },
+            Increment {
+                field: "views",
+                by: Number(1),
+            },
+            Increment {
+                field: "stock",
+                by: Number(-2.5),
+            },
+            Push {
+                field: "tags",
+                value: String("new"),
+            },
+            Remove {
+                field: "tags",
+                value: String("old"),
+            },
+        ]
+        "###);
+
+        let error =
+            serde_json::from_value::<EditOperation>(json!({ "op": "multiply", "field": "views" }))
+                .unwrap_err();
+        insta::assert_display_snapshot!(error, @"unknown variant `multiply`, expected one of `set`, `unset`, `increment`, `push`, `remove`");
+    }
+
+    #[test]
+    fn edit_documents_by_external_id() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "views": 1, "tags": ["old", "blue"], "draft": true },
+                { "id": 2, "name": "kevina", "views": 3.5 },
+                { "id": 3, "name": "benoit" },
+            ]))
+            .unwrap();
+
+        let edited = edit_documents(
+            &index,
+            &["1", "2", "404"],
+            operations(json!([
+                { "op": "increment", "field": "views" },
+                { "op": "push", "field": "tags", "value": "new" },
+                { "op": "remove", "field": "tags", "value": "old" },
+                { "op": "unset", "field": "draft" },
+            ])),
+        )
+        .unwrap();
+        assert_eq!(edited, 2);
+
+        db_snap!(index, documents, @r###"
+        {"id":3,"name":"benoit"}
+        {"id":1,"name":"kevin","views":2,"tags":["blue","new"]}
+        {"id":2,"name":"kevina","views":4.5,"tags":["new"]}
+        "###);
+
+        // Setting a field to the value it already has does not edit the document.
+        let edited = edit_documents(
+            &index,
+            &["3"],
+            operations(json!([{ "op": "set", "field": "name", "value": "benoit" }])),
+        )
+        .unwrap();
+        assert_eq!(edited, 0);
+    }
+
+    #[test]
+    fn invalid_edit_operations() {
+        let index = TempIndex::new();
+        index.add_documents(documents!([{ "id": 1, "name": "kevin", "tags": "blue" }])).unwrap();
+
+        let error = edit_documents(
+            &index,
+            &["1"],
+            operations(json!([{ "op": "increment", "field": "name" }])),
+        )
+        .unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"Could not edit the document `1`: cannot increment the non-numeric `name` field: `"kevin"`."###);
+
+        let error = edit_documents(
+            &index,
+            &["1"],
+            operations(json!([{ "op": "push", "field": "tags", "value": "red" }])),
+        )
+        .unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"Could not edit the document `1`: cannot push into the non-array `tags` field: `"blue"`."###);
+
+        let error = edit_documents(
+            &index,
+            &["1"],
+            operations(json!([{ "op": "set", "field": "id", "value": 2 }])),
+        )
+        .unwrap_err();
+        insta::assert_display_snapshot!(error, @"The primary key `id` cannot be modified by a document edition.");
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"name":"kevin","tags":"blue"}
+        "###);
+    }
+}
@@ -13,6 +13,13 @@ use crate::{FieldId, Index, Object, Result};
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
 
+/// The symbol used to separate the fields of a composite primary key.
+const COMPOSITE_PRIMARY_KEY_SEPARATOR: char = ',';
+
+/// The symbol used to join the values of the fields of a composite primary key into
+/// a document id, it can't be part of a single field document id.
+pub const COMPOSITE_DOCUMENT_ID_SEPARATOR: char = ':';
+
 /// The default primary that is used when not specified.
 const DEFAULT_PRIMARY_KEY: &str = "id";
 
@@ -39,6 +46,9 @@ pub fn enrich_documents_batch<R: Read + Seek>(
     // The primary key *field id* that has already been set for this index or the one
     // we will guess by searching for the first key that contains "id" as a substring.
     let primary_key = match index.primary_key(rtxn)? {
+        Some(primary_key) if primary_key.contains(COMPOSITE_PRIMARY_KEY_SEPARATOR) => {
+            PrimaryKey::composite(primary_key)
+        }
         Some(primary_key) if primary_key.contains(PRIMARY_KEY_SPLIT_SYMBOL) => {
             PrimaryKey::nested(primary_key)
         }
@@ -205,15 +215,75 @@ fn fetch_or_generate_document_id(
                 })),
             }
         }
+        composite @ PrimaryKey::Composite { .. } => {
+            // The whole composite primary key is reported when one of its fields is missing.
+            let missing_document_id = || -> Result<_> {
+                Ok(Err(UserError::MissingDocumentId {
+                    primary_key: composite.name().to_string(),
+                    document: obkv_to_object(document, documents_batch_index)?,
+                }))
+            };
+
+            let mut values = Vec::new();
+            for field in primary_key_fields(composite.name()) {
+                let field_primary_key = if field.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
+                    PrimaryKey::nested(field)
+                } else {
+                    match documents_batch_index.id(field) {
+                        Some(field_id) => PrimaryKey::flat(field, field_id),
+                        None => return missing_document_id(),
+                    }
+                };
+
+                match fetch_or_generate_document_id(
+                    document,
+                    documents_batch_index,
+                    field_primary_key,
+                    false,
+                    uuid_buffer,
+                    count,
+                )? {
+                    Ok(document_id) => values.push(document_id.value().to_string()),
+                    Err(UserError::MissingDocumentId { .. }) => return missing_document_id(),
+                    Err(user_error) => return Ok(Err(user_error)),
+                }
+            }
+
+            Ok(Ok(DocumentId::retrieved(join_composite_document_id(values))))
+        }
     }
 }
 
+/// Returns the fields of the primary key, a single one unless it is a composite primary key.
+pub fn primary_key_fields(primary_key: &str) -> impl Iterator<Item = &str> {
+    primary_key.split(COMPOSITE_PRIMARY_KEY_SEPARATOR)
+}
+
+/// Returns the top-level fields of the documents that hold the primary key.
+pub fn primary_key_roots(primary_key: &str) -> impl Iterator<Item = &str> {
+    primary_key_fields(primary_key)
+        .map(|field| field.split(PRIMARY_KEY_SPLIT_SYMBOL).next().unwrap_or(field))
+}
+
+/// Joins the values of the fields of a composite primary key into a document id.
+pub fn join_composite_document_id<S: AsRef<str>>(values: impl IntoIterator<Item = S>) -> String {
+    let mut document_id = String::new();
+    for (i, value) in values.into_iter().enumerate() {
+        if i != 0 {
+            document_id.push(COMPOSITE_DOCUMENT_ID_SEPARATOR);
+        }
+        document_id.push_str(value.as_ref());
+    }
+    document_id
+}
+
 /// A type that represent the type of primary key that has been set
-/// for this index, a classic flat one or a nested one.
+/// for this index, a classic flat one, a nested one or a composite one.
 #[derive(Debug, Clone, Copy)]
 enum PrimaryKey<'a> {
     Flat { name: &'a str, field_id: FieldId },
     Nested { name: &'a str },
+    Composite { name: &'a str },
 }
 
 impl PrimaryKey<'_> {
@@ -225,10 +295,15 @@ impl PrimaryKey<'_> {
         PrimaryKey::Nested { name }
     }
 
+    fn composite(name: &str) -> PrimaryKey {
+        PrimaryKey::Composite { name }
+    }
+
     fn name(&self) -> &str {
         match self {
             PrimaryKey::Flat { name, .. } => name,
             PrimaryKey::Nested { name } => name,
+            PrimaryKey::Composite { name } => name,
         }
     }
 
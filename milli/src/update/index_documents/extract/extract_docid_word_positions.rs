@@ -10,9 +10,11 @@ use serde_json::Value;
 
 use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, GrenadParameters};
 use crate::error::{InternalError, SerializationError};
+use crate::stemming::Stemmers;
 use crate::update::index_documents::MergeFn;
 use crate::{
-    absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
+    absolute_from_relative_position, locales_allow_list, FieldId, LocalizedFieldIds, Result,
+    StemmingLanguage, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
 };
 
 pub type ScriptLanguageDocidsMap = HashMap<(Script, Language), RoaringBitmap>;
@@ -20,6 +22,10 @@ pub type ScriptLanguageDocidsMap = HashMap<(Script, Language), RoaringBitmap>;
 /// Extracts the word and positions where this word appear and
 /// prefixes it by the document id.
 ///
+/// The stems of the words in the given stemming languages are extracted
+/// at the same positions as their word. The attributes with declared locales
+/// are tokenized with these locales instead of the detected ones.
+///
 /// Returns the generated internal documents ids and a grenad reader
 /// with the list of extracted words from the given chunk of documents.
 #[logging_timer::time]
@@ -28,6 +34,8 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
     stop_words: Option<&fst::Set<&[u8]>>,
+    stemming_languages: &[StemmingLanguage],
+    localized_field_ids: &LocalizedFieldIds,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(RoaringBitmap, grenad::Reader<File>, ScriptLanguageDocidsMap)> {
     let max_positions_per_attributes = max_positions_per_attributes
@@ -51,6 +59,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         tokenizer_builder.stop_words(stop_words);
     }
     let tokenizer = tokenizer_builder.build();
+    let stemmers = Stemmers::new(stemming_languages);
 
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
@@ -70,6 +79,9 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             &obkv,
             searchable_fields,
             &tokenizer,
+            stop_words,
+            localized_field_ids,
+            &stemmers,
             max_positions_per_attributes,
             &mut buffers,
             &mut script_language_word_count,
@@ -106,6 +118,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     searchable_fields,
                     &tokenizer,
+                    &stemmers,
                     max_positions_per_attributes,
                     &mut buffers,
                     &mut script_language_word_count,
@@ -128,10 +141,14 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         .map(|reader| (documents_ids, reader, script_language_docids))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn extract_tokens_from_document<T: AsRef<[u8]>>(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
     tokenizer: &Tokenizer<T>,
+    stop_words: Option<&fst::Set<&[u8]>>,
+    localized_field_ids: &LocalizedFieldIds,
+    stemmers: &Stemmers,
     max_positions_per_attributes: u32,
     buffers: &mut Buffers,
     script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
@@ -142,32 +159,81 @@ fn extract_tokens_from_document<T: AsRef<[u8]>>(
             let value = serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
             buffers.field_buffer.clear();
             if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
-                let tokens = process_tokens(tokenizer.tokenize(field))
-                    .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
-
-                for (index, token) in tokens {
-                    // if a language has been detected for the token, we update the counter.
-                    if let Some(language) = token.language {
-                        let script = token.script;
-                        let entry =
-                            script_language_word_count.entry(script).or_insert_with(Vec::new);
-                        match entry.iter_mut().find(|(l, _)| *l == language) {
-                            Some((_, n)) => *n += 1,
-                            None => entry.push((language, 1)),
+                match localized_field_ids.locales(field_id) {
+                    // the declared locales replace the detected ones for this field.
+                    Some(locales) => {
+                        let allow_list = locales_allow_list(field, locales);
+                        let mut tokenizer_builder = TokenizerBuilder::new();
+                        if let Some(stop_words) = stop_words {
+                            tokenizer_builder.stop_words(stop_words);
                         }
+                        tokenizer_builder.allow_list(&allow_list);
+                        let tokenizer = tokenizer_builder.build();
+
+                        extract_tokens_from_field(
+                            field_id,
+                            tokenizer.tokenize(field),
+                            stemmers,
+                            max_positions_per_attributes,
+                            &mut buffers.key_buffer,
+                            script_language_word_count,
+                            docid_word_positions_sorter,
+                        )?;
                     }
-                    let token = token.lemma().trim();
-                    if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
-                        buffers.key_buffer.truncate(mem::size_of::<u32>());
-                        buffers.key_buffer.extend_from_slice(token.as_bytes());
-
-                        let position: u16 = index
-                            .try_into()
-                            .map_err(|_| SerializationError::InvalidNumberSerialization)?;
-                        let position = absolute_from_relative_position(field_id, position);
-                        docid_word_positions_sorter
-                            .insert(&buffers.key_buffer, position.to_ne_bytes())?;
-                    }
+                    None => extract_tokens_from_field(
+                        field_id,
+                        tokenizer.tokenize(field),
+                        stemmers,
+                        max_positions_per_attributes,
+                        &mut buffers.key_buffer,
+                        script_language_word_count,
+                        docid_word_positions_sorter,
+                    )?,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_tokens_from_field<'a>(
+    field_id: FieldId,
+    tokens: impl Iterator<Item = Token<'a>>,
+    stemmers: &Stemmers,
+    max_positions_per_attributes: u32,
+    key_buffer: &mut Vec<u8>,
+    script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
+    docid_word_positions_sorter: &mut grenad::Sorter<MergeFn>,
+) -> Result<()> {
+    let tokens =
+        process_tokens(tokens).take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
+
+    for (index, token) in tokens {
+        // if a language has been detected for the token, we update the counter.
+        if let Some(language) = token.language {
+            let script = token.script;
+            let entry = script_language_word_count.entry(script).or_insert_with(Vec::new);
+            match entry.iter_mut().find(|(l, _)| *l == language) {
+                Some((_, n)) => *n += 1,
+                None => entry.push((language, 1)),
+            }
+        }
+        let token = token.lemma().trim();
+        if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
+            key_buffer.truncate(mem::size_of::<u32>());
+            key_buffer.extend_from_slice(token.as_bytes());
+
+            let position: u16 =
+                index.try_into().map_err(|_| SerializationError::InvalidNumberSerialization)?;
+            let position = absolute_from_relative_position(field_id, position);
+            docid_word_positions_sorter.insert(&key_buffer, position.to_ne_bytes())?;
+
+            for stem in stemmers.stems(token) {
+                if stem.len() <= MAX_WORD_LENGTH {
+                    key_buffer.truncate(mem::size_of::<u32>());
+                    key_buffer.extend_from_slice(stem.as_bytes());
+                    docid_word_positions_sorter.insert(&key_buffer, position.to_ne_bytes())?;
                 }
             }
         }
@@ -177,7 +243,7 @@ fn extract_tokens_from_document<T: AsRef<[u8]>>(
 }
 
 /// Transform a JSON value into a string that can be indexed.
-fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a str> {
+pub fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a str> {
     fn inner(value: &Value, output: &mut String) -> bool {
         use std::fmt::Write;
         match value {
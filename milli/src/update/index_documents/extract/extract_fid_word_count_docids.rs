@@ -10,6 +10,7 @@ use super::helpers::{
 };
 use crate::error::SerializationError;
 use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::stemming::is_stem;
 use crate::{relative_from_absolute_position, DocumentId, FieldId, Result};
 
 /// Extracts the field id word count and the documents ids where
@@ -39,7 +40,7 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
 
     let mut cursor = docid_word_positions.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
-        let (document_id_bytes, _word_bytes) = try_split_array_at(key)
+        let (document_id_bytes, word_bytes) = try_split_array_at(key)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = u32::from_be_bytes(document_id_bytes);
 
@@ -53,6 +54,11 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
             current_document_id = Some(document_id);
         }
 
+        // the stems share the positions of their words and must not be counted twice.
+        if is_stem(word_bytes) {
+            continue;
+        }
+
         for position in read_u32_ne_bytes(value) {
             let (field_id, _) = relative_from_absolute_position(position);
 
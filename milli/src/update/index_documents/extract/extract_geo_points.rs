@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
 use crate::error::GeoError;
-use crate::update::index_documents::extract_finite_float_from_value;
+use crate::update::index_documents::{extract_finite_float_from_value, join_composite_document_id};
 use crate::{FieldId, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
@@ -16,7 +16,7 @@ use crate::{FieldId, InternalError, Result};
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
-    primary_key_id: FieldId,
+    primary_key_ids: &[FieldId],
     (lat_fid, lng_fid): (FieldId, FieldId),
 ) -> Result<grenad::Reader<File>> {
     let mut writer = create_writer(
@@ -31,8 +31,17 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
         // since we only needs the primary key when we throw an error we create this getter to
         // lazily get it when needed
         let document_id = || -> Value {
-            let document_id = obkv.get(primary_key_id).unwrap();
-            serde_json::from_slice(document_id).unwrap()
+            let mut values = primary_key_ids.iter().map(|field_id| -> Value {
+                let document_id = obkv.get(*field_id).unwrap();
+                serde_json::from_slice(document_id).unwrap()
+            });
+            match primary_key_ids {
+                [_] => values.next().unwrap(),
+                _ => Value::from(join_composite_document_id(values.map(|value| match value {
+                    Value::String(string) => string,
+                    value => value.to_string(),
+                }))),
+            }
         };
 
         // first we get the two fields
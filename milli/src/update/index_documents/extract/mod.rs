@@ -17,6 +17,7 @@ use log::debug;
 use rayon::prelude::*;
 
 use self::extract_docid_word_positions::extract_docid_word_positions;
+pub use self::extract_docid_word_positions::json_to_string;
 use self::extract_facet_number_docids::extract_facet_number_docids;
 use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
@@ -31,7 +32,7 @@ use super::helpers::{
     GrenadParameters, MergeFn, MergeableReader,
 };
 use super::{helpers, TypedChunk};
-use crate::{FieldId, Result};
+use crate::{FieldId, LocalizedFieldIds, Result, StemmingLanguage};
 
 /// Extract data for each databases from obkv documents in parallel.
 /// Send data in grenad file over provided Sender.
@@ -41,20 +42,25 @@ pub(crate) fn data_from_obkv_documents(
     flattened_obkv_chunks: impl Iterator<Item = Result<grenad::Reader<File>>> + Send,
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    send_documents: bool,
     searchable_fields: Option<HashSet<FieldId>>,
     faceted_fields: HashSet<FieldId>,
-    primary_key_id: FieldId,
+    primary_key_ids: Vec<FieldId>,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: Option<fst::Set<&[u8]>>,
+    stemming_languages: Vec<StemmingLanguage>,
+    localized_field_ids: LocalizedFieldIds,
     max_positions_per_attributes: Option<u32>,
     exact_attributes: HashSet<FieldId>,
 ) -> Result<()> {
-    original_obkv_chunks
-        .par_bridge()
-        .map(|original_documents_chunk| {
-            send_original_documents_data(original_documents_chunk, lmdb_writer_sx.clone())
-        })
-        .collect::<Result<()>>()?;
+    if send_documents {
+        original_obkv_chunks
+            .par_bridge()
+            .map(|original_documents_chunk| {
+                send_original_documents_data(original_documents_chunk, lmdb_writer_sx.clone())
+            })
+            .collect::<Result<()>>()?;
+    }
 
     #[allow(clippy::type_complexity)]
     let result: Result<(Vec<_>, (Vec<_>, (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>)))))> =
@@ -67,9 +73,11 @@ pub(crate) fn data_from_obkv_documents(
                     lmdb_writer_sx.clone(),
                     &searchable_fields,
                     &faceted_fields,
-                    primary_key_id,
+                    &primary_key_ids,
                     geo_fields_ids,
                     &stop_words,
+                    &stemming_languages,
+                    &localized_field_ids,
                     max_positions_per_attributes,
                 )
             })
@@ -277,9 +285,11 @@ fn send_and_extract_flattened_documents_data(
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: &Option<HashSet<FieldId>>,
     faceted_fields: &HashSet<FieldId>,
-    primary_key_id: FieldId,
+    primary_key_ids: &[FieldId],
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: &Option<fst::Set<&[u8]>>,
+    stemming_languages: &[StemmingLanguage],
+    localized_field_ids: &LocalizedFieldIds,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(
     grenad::Reader<CursorClonableMmap>,
@@ -297,9 +307,14 @@ fn send_and_extract_flattened_documents_data(
     if let Some(geo_fields_ids) = geo_fields_ids {
         let documents_chunk_cloned = flattened_documents_chunk.clone();
         let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        let primary_key_ids = primary_key_ids.to_vec();
         rayon::spawn(move || {
-            let result =
-                extract_geo_points(documents_chunk_cloned, indexer, primary_key_id, geo_fields_ids);
+            let result = extract_geo_points(
+                documents_chunk_cloned,
+                indexer,
+                &primary_key_ids,
+                geo_fields_ids,
+            );
             let _ = match result {
                 Ok(geo_points) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoPoints(geo_points))),
                 Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
@@ -316,6 +331,8 @@ fn send_and_extract_flattened_documents_data(
                         indexer,
                         searchable_fields,
                         stop_words.as_ref(),
+                        stemming_languages,
+                        localized_field_ids,
                         max_positions_per_attributes,
                     )?;
 
@@ -11,7 +11,8 @@ use std::num::NonZeroU32;
 use std::result::Result as StdResult;
 
 use crossbeam_channel::{Receiver, Sender};
-use heed::types::Str;
+use deserr::Deserr;
+use heed::types::{ByteSlice, Str};
 use heed::Database;
 use log::debug;
 use roaring::RoaringBitmap;
@@ -21,9 +22,11 @@ use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
 
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{
-    extract_finite_float_from_value, validate_document_id, validate_document_id_value,
-    validate_geo_from_json, DocumentId,
+    extract_finite_float_from_value, join_composite_document_id, primary_key_fields,
+    primary_key_roots, validate_document_id, validate_document_id_value, validate_geo_from_json,
+    DocumentId, COMPOSITE_DOCUMENT_ID_SEPARATOR,
 };
+pub(crate) use self::extract::json_to_string;
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
     fst_stream_into_vec, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps,
@@ -36,7 +39,7 @@ use crate::error::{Error, InternalError, UserError};
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
-    WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
+    WordPrefixDocids, WordPrefixIntegerDocids, WordsInfixesFst, WordsPrefixesFst,
 };
 use crate::{Index, Result, RoaringBitmapCodec};
 
@@ -70,6 +73,37 @@ impl Default for IndexDocumentsMethod {
     }
 }
 
+/// How the version of an added document is compared to the version of the
+/// document it replaces, when the index has a version field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum VersionCondition {
+    /// The document is written when its version is greater than the stored one,
+    /// the documents without a version are always written.
+    #[default]
+    IfNewerThan,
+    /// The document is written when its version is the stored one, the written
+    /// document gets the next version. The missing versions are `0`.
+    IfVersion,
+}
+
+/// The parts of the index that [`IndexDocuments::execute_raw`] writes from the documents
+/// it is given, a settings update only rebuilds the databases impacted by its changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ReindexScope {
+    /// Whether the documents themselves must be written in the documents database.
+    pub documents: bool,
+    /// Whether the word databases must be extracted from the searchable attributes.
+    pub words: bool,
+    /// Whether the facet databases must be extracted from the faceted attributes.
+    pub facets: bool,
+}
+
+impl ReindexScope {
+    pub const ALL: ReindexScope = ReindexScope { documents: true, words: true, facets: true };
+}
+
 pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     wtxn: &'t mut heed::RwTxn<'i, 'u>,
     index: &'i Index,
@@ -80,6 +114,7 @@ pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     should_abort: FA,
     added_documents: u64,
     deleted_documents: u64,
+    scope: ReindexScope,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -91,6 +126,9 @@ pub struct IndexDocumentsConfig {
     pub update_method: IndexDocumentsMethod,
     pub deletion_strategy: DeletionStrategy,
     pub autogenerate_docids: bool,
+    /// The condition on the versions of the documents, `None` to use the default one
+    /// of the index, an error is returned if it is set when the index has no version field.
+    pub version_condition: Option<VersionCondition>,
 }
 
 impl<'t, 'u, 'i, 'a, FP, FA> IndexDocuments<'t, 'u, 'i, 'a, FP, FA>
@@ -124,9 +162,21 @@ where
             index,
             added_documents: 0,
             deleted_documents: 0,
+            scope: ReindexScope::ALL,
         })
     }
 
+    /// Restricts the databases that [`Self::execute_raw`] extracts from the documents.
+    pub(crate) fn with_scope(mut self, scope: ReindexScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Sets the version condition of the documents added next to the builder.
+    pub fn set_version_condition(&mut self, version_condition: Option<VersionCondition>) {
+        self.config.version_condition = version_condition;
+    }
+
     /// Adds a batch of documents to the current builder.
     ///
     /// Since the documents are progressively added to the writer, a failure will cause only
@@ -156,12 +206,16 @@ where
         };
 
         let indexed_documents =
-            self.transform.as_mut().expect("Invalid document addition state").read_documents(
+            match self.transform.as_mut().expect("Invalid document addition state").read_documents(
                 enriched_documents_reader,
+                self.config.version_condition,
                 self.wtxn,
                 &self.progress,
                 &self.should_abort,
-            )? as u64;
+            )? {
+                Ok(indexed_documents) => indexed_documents as u64,
+                Err(user_error) => return Ok((self, Err(user_error))),
+            };
 
         self.added_documents += indexed_documents;
 
@@ -242,6 +296,7 @@ where
             documents_count,
             original_documents,
             flattened_documents,
+            stored_only_documents,
         } = output;
 
         // The fields_ids_map is put back to the store now so the rest of the transaction sees an
@@ -276,17 +331,26 @@ where
             Receiver<Result<TypedChunk>>,
         ) = crossbeam_channel::unbounded();
 
-        // get the primary key field id
-        let primary_key_id = fields_ids_map.id(&primary_key).unwrap();
+        // get the primary key fields ids, there are several of them for a composite primary key
+        let primary_key_ids: Vec<_> = primary_key_fields(&primary_key)
+            .map(|field| fields_ids_map.id(field).unwrap())
+            .collect();
 
         // get searchable fields for word databases
-        let searchable_fields =
-            self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+        let searchable_fields = if self.scope.words {
+            self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter)
+        } else {
+            Some(HashSet::new())
+        };
         // get filterable fields for facet databases
-        let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
+        let faceted_fields = if self.scope.facets {
+            self.index.faceted_fields_ids(self.wtxn)?
+        } else {
+            HashSet::new()
+        };
         // get the fid of the `_geo.lat` and `_geo.lng` fields.
         let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
-            Some(gfid) => {
+            Some(gfid) if self.scope.facets => {
                 let is_sortable = self.index.sortable_fields_ids(self.wtxn)?.contains(&gfid);
                 let is_filterable = self.index.filterable_fields_ids(self.wtxn)?.contains(&gfid);
                 // if `_geo` is faceted then we get the `lat` and `lng`
@@ -302,10 +366,12 @@ where
                     None
                 }
             }
-            None => None,
+            _ => None,
         };
 
         let stop_words = self.index.stop_words(self.wtxn)?;
+        let stemming_languages = self.index.stemming_languages(self.wtxn)?;
+        let localized_field_ids = self.index.localized_field_ids(self.wtxn)?;
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
 
         let pool_params = GrenadParameters {
@@ -317,6 +383,7 @@ where
         let documents_chunk_size =
             self.indexer_config.documents_chunk_size.unwrap_or(1024 * 1024 * 4); // 4MiB
         let max_positions_per_attributes = self.indexer_config.max_positions_per_attributes;
+        let scope = self.scope;
 
         // Run extraction pipeline in parallel.
         pool.install(|| {
@@ -336,11 +403,14 @@ where
                     flattened_chunk,
                     pool_params,
                     lmdb_writer_sx.clone(),
+                    scope.documents,
                     searchable_fields,
                     faceted_fields,
-                    primary_key_id,
+                    primary_key_ids,
                     geo_fields_ids,
                     stop_words,
+                    stemming_languages,
+                    localized_field_ids,
                     max_positions_per_attributes,
                     exact_attributes,
                 )
@@ -365,6 +435,14 @@ where
             debug!("{} documents actually deleted", deleted_documents_result.deleted_documents);
         }
 
+        // We overwrite the documents of which only the fields that are not indexed changed,
+        // the databases are still up to date with them.
+        let documents = self.index.documents.remap_types::<ByteSlice, ByteSlice>();
+        let mut cursor = grenad::Reader::new(stored_only_documents)?.into_cursor()?;
+        while let Some((key, value)) = cursor.move_on_next()? {
+            documents.put(self.wtxn, key, value)?;
+        }
+
         let index_documents_ids = self.index.documents_ids(self.wtxn)?;
         let index_is_empty = index_documents_ids.is_empty();
         let mut final_documents_ids = RoaringBitmap::new();
@@ -450,6 +528,16 @@ where
         let all_documents_ids = index_documents_ids | new_documents_ids;
         self.index.put_documents_ids(self.wtxn, &all_documents_ids)?;
 
+        // The word databases were left untouched, there is no prefix or infix to recompute.
+        if !self.scope.words {
+            return Ok(all_documents_ids.len());
+        }
+
+        // We add the infixes of the new words of the infix searchable attributes.
+        if let Some(word_fid_docids) = word_fid_docids.clone() {
+            WordsInfixesFst::new(self.wtxn, self.index).execute_with_new_words(word_fid_docids)?;
+        }
+
         self.execute_prefix_databases(
             word_docids,
             exact_word_docids,
@@ -1408,6 +1496,62 @@ mod tests {
         assert!(external_documents_ids.get("1").is_some());
     }
 
+    #[test]
+    fn index_documents_with_composite_primary_key() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("tenant_id,product.sku".to_owned());
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "product": { "sku": "KD-35" }, "title": "Acme shoe" },
+                { "tenant_id": "globex", "product": { "sku": "KD-35" }, "title": "Globex shoe" },
+                { "tenant_id": 12, "product": { "sku": 35 }, "title": "Initech shoe" },
+            ]))
+            .unwrap();
+
+        // The same sku in two tenants are two different documents.
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 3);
+        assert!(external_documents_ids.get("acme:KD-35").is_some());
+        assert!(external_documents_ids.get("globex:KD-35").is_some());
+        assert!(external_documents_ids.get("12:35").is_some());
+        drop(rtxn);
+
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "product": { "sku": "KD-35" }, "title": "New acme shoe" },
+            ]))
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 3);
+        drop(rtxn);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = DeleteDocuments::new(&mut wtxn, &index).unwrap();
+        builder.delete_external_id("globex:KD-35");
+        builder.execute().unwrap();
+        let external_documents_ids = index.external_documents_ids(&wtxn).unwrap();
+        assert!(external_documents_ids.get("globex:KD-35").is_none());
+        assert_eq!(index.number_of_documents(&wtxn).unwrap(), 2);
+        wtxn.commit().unwrap();
+
+        // All the fields of a composite primary key must be present.
+        let error = index
+            .add_documents(documents!([{ "tenant_id": "acme", "title": "Unknown shoe" }]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::MissingDocumentId { ref primary_key, .. })
+                if primary_key == "tenant_id,product.sku"
+        ));
+    }
+
     #[test]
     fn test_facets_generation() {
         let index = TempIndex::new();
@@ -2135,6 +2279,40 @@ mod tests {
         assert_eq!(cj_cmn_docs, expected_cj_cmn_docids);
     }
 
+    #[cfg(feature = "default")]
+    #[test]
+    fn store_declared_language_of_localized_attributes_during_indexing() {
+        use charabia::{Language, Script};
+
+        use crate::LocalizedAttributesRule;
+
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_localized_attributes_rules(vec![LocalizedAttributesRule::new(
+                    vec!["*_ja".to_string()],
+                    vec![Language::Jpn],
+                )]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "人人生而自由﹐在尊嚴和權利上一律平等。" },
+                { "id": 2, "title_ja": "人人生而自由﹐在尊嚴和權利上一律平等。" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let key_jpn = (Script::Cj, Language::Jpn);
+        let key_cmn = (Script::Cj, Language::Cmn);
+        let cj_jpn_docs = index.script_language_documents_ids(&rtxn, &key_jpn).unwrap().unwrap();
+        let cj_cmn_docs = index.script_language_documents_ids(&rtxn, &key_cmn).unwrap().unwrap();
+        let expected_cj_jpn_docids = [1].iter().collect();
+        assert_eq!(cj_jpn_docs, expected_cj_jpn_docids);
+        let expected_cj_cmn_docids = [0].iter().collect();
+        assert_eq!(cj_cmn_docs, expected_cj_cmn_docids);
+    }
+
     #[test]
     fn add_and_delete_documents_in_single_transform() {
         let mut index = TempIndex::new();
@@ -2459,6 +2637,108 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn unchanged_documents_are_not_reindexed() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "age": 20 },
+                { "id": 2, "name": "benoit", "age": 25 },
+            ]))
+            .unwrap();
+
+        // Merging these documents with the stored ones doesn't change them.
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin" },
+                { "id": 2, "age": 25 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.documents_ids(&rtxn).unwrap(), RoaringBitmap::from_iter([0, 1]));
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(external_documents_ids.get("1"), Some(0));
+        assert_eq!(external_documents_ids.get("2"), Some(1));
+        assert!(index.soft_deleted_documents_ids(&rtxn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unchanged_documents_do_not_use_a_new_id() {
+        let index = TempIndex::new();
+
+        index.add_documents(documents!([{ "id": 1, "name": "kevin" }])).unwrap();
+
+        // The identical document is skipped before being given a new document id.
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin" },
+                { "id": 2, "name": "benoit" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.documents_ids(&rtxn).unwrap(), RoaringBitmap::from_iter([0, 1]));
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(external_documents_ids.get("1"), Some(0));
+        assert_eq!(external_documents_ids.get("2"), Some(1));
+    }
+
+    #[test]
+    fn documents_with_only_stored_fields_changes_keep_their_id() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("name")]);
+                settings.set_filterable_fields(hashset! { S("age") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "age": 20, "price": 10 },
+                { "id": 2, "name": "benoit", "age": 25, "price": 12 },
+            ]))
+            .unwrap();
+
+        // Only the price, which is neither searchable nor filterable, changes.
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": 11 },
+                { "id": 2, "price": 13, "currency": "EUR" },
+            ]))
+            .unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"name":"kevin","id":1,"age":20,"price":11}
+        {"name":"benoit","id":2,"age":25,"price":13,"currency":"EUR"}
+        "###);
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.documents_ids(&rtxn).unwrap(), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(index.field_distribution(&rtxn).unwrap().get("currency"), Some(&1));
+        assert_eq!(index.field_distribution(&rtxn).unwrap().get("price"), Some(&2));
+        let crate::SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("kevin").execute().unwrap();
+        assert_eq!(documents_ids, vec![0]);
+        drop(rtxn);
+
+        // The age is filterable, the document is reindexed under a new id.
+        index.add_documents(documents!([{ "id": 1, "age": 21 }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        assert_ne!(docid, 0);
+        let mut search = index.search(&rtxn);
+        search.filter(crate::Filter::from_str("age = 21").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![docid]);
+    }
+
     #[test]
     fn test_word_fid_position() {
         let index = TempIndex::new();
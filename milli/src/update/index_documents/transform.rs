@@ -1,27 +1,40 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{Read, Seek};
+use std::result::Result as StdResult;
 
 use fxhash::FxHashMap;
 use heed::RoTxn;
 use itertools::Itertools;
 use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
-use serde_json::Value;
+use serde_json::{Number, Value};
 use smartstring::SmartString;
 
 use super::helpers::{
     create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_operations, MergeFn,
 };
-use super::{IndexDocumentsMethod, IndexerConfig};
-use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
+use super::{
+    primary_key_fields, primary_key_roots, IndexDocumentsMethod, IndexerConfig, ReindexScope,
+    VersionCondition,
+};
+use crate::documents::{
+    obkv_to_object, DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchCursor,
+    EnrichedDocumentsBatchReader,
+};
 use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
-use crate::update::{AvailableDocumentsIds, ClearDocuments, UpdateIndexingStep};
+use crate::update::clear_documents::{clear_facet_databases, clear_word_databases};
+use crate::update::{
+    AvailableDocumentsIds, ClearDocuments, DeleteDocuments, DeletionStrategy, UpdateIndexingStep,
+};
 use crate::{
-    FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result, BEU32,
+    all_obkv_to_json, is_faceted_by, DocumentId, DocumentSchema, ExternalDocumentsIds,
+    FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, IngestProcessor,
+    Object, Result, BEU32,
 };
 
 pub struct TransformOutput {
@@ -34,6 +47,9 @@ pub struct TransformOutput {
     pub documents_count: usize,
     pub original_documents: File,
     pub flattened_documents: File,
+    // The documents of which only the fields that are not indexed changed,
+    // they are written under their current document id without being extracted.
+    pub stored_only_documents: File,
 }
 
 /// Extract the external ids, deduplicate and compute the new internal documents ids
@@ -59,8 +75,15 @@ pub struct Transform<'a, 'i> {
 
     replaced_documents_ids: RoaringBitmap,
     new_documents_ids: RoaringBitmap,
+    // The id of the document in the database that each new document replaces.
+    original_documents_ids: FxHashMap<DocumentId, DocumentId>,
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
+    // The versions of the documents read by this transform, when the index has a version field.
+    documents_versions: FxHashMap<SmartString<smartstring::Compact>, Number>,
+    // The documents updated by this transform when they are rebuilt to be processed or
+    // validated, the next updates of the same documents are merged with them.
+    updated_documents: FxHashMap<SmartString<smartstring::Compact>, Object>,
     documents_count: usize,
 }
 
@@ -72,6 +95,16 @@ pub enum Operation {
     Deletion,
 }
 
+/// How a new version of a document differs from the one stored in the database.
+enum DocumentChange {
+    /// Both versions are identical.
+    None,
+    /// Only fields that are neither searchable nor faceted changed.
+    StoredFieldsOnly,
+    /// At least one searchable or faceted field changed.
+    IndexedFields,
+}
+
 /// Create a mapping between the field ids found in the document batch and the one that were
 /// already present in the index.
 ///
@@ -146,7 +179,10 @@ impl<'a, 'i> Transform<'a, 'i> {
             index_documents_method,
             replaced_documents_ids: RoaringBitmap::new(),
             new_documents_ids: RoaringBitmap::new(),
+            original_documents_ids: FxHashMap::default(),
             new_external_documents_ids_builder: FxHashMap::default(),
+            documents_versions: FxHashMap::default(),
+            updated_documents: FxHashMap::default(),
             documents_count: 0,
         })
     }
@@ -154,22 +190,72 @@ impl<'a, 'i> Transform<'a, 'i> {
     pub fn read_documents<R, FP, FA>(
         &mut self,
         reader: EnrichedDocumentsBatchReader<R>,
+        version_condition: Option<VersionCondition>,
         wtxn: &mut heed::RwTxn,
         progress_callback: FP,
         should_abort: FA,
-    ) -> Result<usize>
+    ) -> Result<StdResult<usize, UserError>>
     where
         R: Read + Seek,
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
     {
+        let version_field = self.index.version_field(wtxn)?.map(String::from);
+        let version_condition = match (&version_field, version_condition) {
+            (None, Some(_)) => return Ok(Err(UserError::MissingVersionField)),
+            (_, version_condition) => version_condition.unwrap_or_default(),
+        };
+
         let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+        let document_schema = self.index.document_schema(wtxn)?;
+        let ingest_pipeline = self.index.ingest_pipeline(wtxn)?;
+        let primary_key = cursor.primary_key().to_string();
+        let primary_key_roots: Vec<_> = primary_key_roots(&primary_key).collect();
+
+        if let Some(version_field) = &version_field {
+            if let Err(error) = check_documents_versions(
+                &mut cursor,
+                &fields_index,
+                version_field,
+                version_condition,
+            )? {
+                return Ok(Err(error));
+            }
+        }
+
+        // The documents are rebuilt from their JSON representation to be processed and
+        // validated, they are all checked before any of them is written.
+        let rebuild_documents = document_schema.is_some() || !ingest_pipeline.is_empty();
+        if rebuild_documents {
+            if let Err(error) = self.check_documents(
+                wtxn,
+                &mut cursor,
+                &fields_index,
+                &primary_key_roots,
+                &ingest_pipeline,
+                document_schema.as_ref(),
+            )? {
+                return Ok(Err(error));
+            }
+        }
+
         let external_documents_ids = self.index.external_documents_ids(wtxn)?;
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
+        let is_update = self.index_documents_method == IndexDocumentsMethod::UpdateDocuments;
+        let version_field_id = match &version_field {
+            Some(field) => {
+                Some(self.fields_ids_map.insert(field).ok_or(UserError::AttributeLimitReached)?)
+            }
+            None => None,
+        };
 
-        let primary_key = cursor.primary_key().to_string();
-        let primary_key_id =
-            self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        // The document ids are only generated for single field primary keys.
+        let mut primary_key_ids = Vec::new();
+        for field in primary_key_fields(&primary_key) {
+            let field_id =
+                self.fields_ids_map.insert(field).ok_or(UserError::AttributeLimitReached)?;
+            primary_key_ids.push(field_id);
+        }
 
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_buffer = Vec::new();
@@ -178,6 +264,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         let mut field_buffer: Vec<(u16, Cow<[u8]>)> = Vec::new();
         while let Some(enriched_document) = cursor.next_enriched_document()? {
             let EnrichedDocument { document, document_id } = enriched_document;
+            let mut updated_document = None;
 
             if should_abort() {
                 return Err(Error::InternalError(InternalError::AbortedIndexation));
@@ -198,13 +285,52 @@ impl<'a, 'i> Transform<'a, 'i> {
             if document_id.is_generated() {
                 serde_json::to_writer(&mut docid_buffer, external_id)
                     .map_err(InternalError::SerdeJson)?;
-                field_buffer_cache.push((primary_key_id, Cow::from(&docid_buffer)));
+                field_buffer_cache.push((primary_key_ids[0], Cow::from(&docid_buffer)));
             }
 
-            for (k, v) in document.iter() {
-                let mapped_id =
-                    *mapping.get(&k).ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
-                field_buffer_cache.push((mapped_id, Cow::from(v)));
+            // Whether the document is only checked against the version of the document it
+            // replaces. Updates merged with the stored document must send their own version.
+            let mut versioned = true;
+            if !rebuild_documents {
+                for (k, v) in document.iter() {
+                    let mapped_id = *mapping
+                        .get(&k)
+                        .ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
+                    field_buffer_cache.push((mapped_id, Cow::from(v)));
+                }
+            } else {
+                let object = obkv_to_object(&document, &fields_index)?;
+                let base = if is_update {
+                    self.base_document(wtxn, &external_documents_ids, external_id)?
+                } else {
+                    None
+                };
+                if base.is_some() {
+                    versioned = version_field.as_ref().map_or(false, |field| {
+                        object.get(field).map_or(false, |version| !version.is_null())
+                    });
+                }
+                // The documents were checked beforehand, they are valid.
+                let object = prepare_document(
+                    object,
+                    base,
+                    external_id,
+                    &primary_key_roots,
+                    &ingest_pipeline,
+                    document_schema.as_ref(),
+                )?;
+
+                for (name, value) in &object {
+                    let field_id =
+                        self.fields_ids_map.insert(name).ok_or(UserError::AttributeLimitReached)?;
+                    let value = serde_json::to_vec(value).map_err(InternalError::SerdeJson)?;
+                    field_buffer_cache.push((field_id, Cow::from(value)));
+                }
+
+                // The following updates of the document in this batch are merged with it.
+                if is_update {
+                    updated_document = Some(object);
+                }
             }
 
             // Insertion in a obkv need to be done with keys ordered. For now they are ordered
@@ -212,56 +338,162 @@ impl<'a, 'i> Transform<'a, 'i> {
             // fieldids map keys order.
             field_buffer_cache.sort_unstable_by(|(f1, _), (f2, _)| f1.cmp(f2));
 
+            // A document is skipped when its version does not respect the version condition.
+            if let Some(version_field_id) = version_field_id {
+                let document_id = || {
+                    serde_json::from_str(external_id).unwrap_or_else(|_| Value::from(external_id))
+                };
+                let version_position =
+                    field_buffer_cache.binary_search_by_key(&version_field_id, |(fid, _)| *fid);
+                let version = match version_position {
+                    Ok(position) if versioned => {
+                        let version = &field_buffer_cache[position].1;
+                        match serde_json::from_slice(version).map_err(InternalError::SerdeJson)? {
+                            Value::Null => None,
+                            Value::Number(version) => Some(version),
+                            version => {
+                                return Err(UserError::InvalidDocumentVersion {
+                                    document_id: document_id(),
+                                    version,
+                                    expected: "a number",
+                                }
+                                .into())
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+
+                let (is_stale, written_version) = match (version_condition, version) {
+                    (VersionCondition::IfNewerThan, None) => (false, None),
+                    (VersionCondition::IfNewerThan, Some(version)) => {
+                        let current_version = self.current_document_version(
+                            wtxn,
+                            &external_documents_ids,
+                            external_id,
+                            version_field_id,
+                        )?;
+                        let is_stale = current_version.map_or(false, |current_version| {
+                            compare_versions(&version, &current_version).is_le()
+                        });
+                        (is_stale, Some(version))
+                    }
+                    (VersionCondition::IfVersion, version) => {
+                        let version = match version {
+                            Some(version) => version.as_u64().ok_or_else(|| {
+                                UserError::InvalidDocumentVersion {
+                                    document_id: document_id(),
+                                    version: Value::Number(version),
+                                    expected: "a positive integer",
+                                }
+                            })?,
+                            None => 0,
+                        };
+                        let current_version = self.current_document_version(
+                            wtxn,
+                            &external_documents_ids,
+                            external_id,
+                            version_field_id,
+                        )?;
+                        let is_stale = current_version.map_or(Some(0), |current| current.as_u64())
+                            != Some(version);
+
+                        // The written document gets the next version.
+                        let next_version = match version.checked_add(1) {
+                            Some(next_version) => Number::from(next_version),
+                            None => {
+                                return Err(UserError::InvalidDocumentVersion {
+                                    document_id: document_id(),
+                                    version: Value::from(version),
+                                    expected: "a positive integer lower than 18446744073709551615",
+                                }
+                                .into())
+                            }
+                        };
+                        let bytes =
+                            serde_json::to_vec(&next_version).map_err(InternalError::SerdeJson)?;
+                        match version_position {
+                            Ok(position) => field_buffer_cache[position].1 = Cow::from(bytes),
+                            Err(position) => field_buffer_cache
+                                .insert(position, (version_field_id, Cow::from(bytes))),
+                        }
+                        if let (Some(object), Some(field)) = (&mut updated_document, &version_field)
+                        {
+                            object.insert(field.clone(), Value::Number(next_version.clone()));
+                        }
+                        (is_stale, Some(next_version))
+                    }
+                };
+
+                if is_stale {
+                    field_buffer = drop_and_reuse(field_buffer_cache);
+                    docid_buffer.clear();
+                    continue;
+                }
+                if let Some(version) = written_version {
+                    self.documents_versions.insert(external_id.into(), version);
+                }
+            }
+
             // Build the new obkv document.
             let mut writer = obkv::KvWriter::new(&mut obkv_buffer);
             for (k, v) in field_buffer_cache.iter() {
                 writer.insert(*k, v)?;
             }
 
-            let mut original_docid = None;
+            if let Some(object) = updated_document {
+                self.updated_documents.insert(external_id.into(), object);
+            }
+
+            // The document stored in the db under the same external id, when this one replaces it.
+            let mut original_document = None;
 
             let docid = match self.new_external_documents_ids_builder.entry((*external_id).into()) {
-                Entry::Occupied(entry) => *entry.get() as u32,
+                Entry::Occupied(entry) => Some(*entry.get() as u32),
                 Entry::Vacant(entry) => {
                     // If the document was already in the db we mark it as a replaced document.
                     // It'll be deleted later.
-                    if let Some(docid) = external_documents_ids.get(entry.key()) {
+                    if let Some(original_docid) = external_documents_ids.get(entry.key()) {
                         // If it was already in the list of replaced documents it means it was deleted
                         // by the remove_document method. We should starts as if it never existed.
-                        if self.replaced_documents_ids.insert(docid) {
-                            original_docid = Some(docid);
+                        if !self.replaced_documents_ids.contains(original_docid) {
+                            let original_key = BEU32::new(original_docid);
+                            let base_obkv = self
+                                .index
+                                .documents
+                                .remap_data_type::<heed::types::ByteSlice>()
+                                .get(wtxn, &original_key)?
+                                .ok_or(InternalError::DatabaseMissingEntry {
+                                    db_name: db_name::DOCUMENTS,
+                                    key: None,
+                                })?;
+                            original_document = Some((original_docid, base_obkv));
+                        }
+                    }
+
+                    // we check if the two documents are exactly equal. If it's the case we can skip
+                    // this document entirely, without using a new docid for it.
+                    match original_document {
+                        Some((_, base_obkv)) if base_obkv == obkv_buffer => None,
+                        _ => {
+                            if let Some((original_docid, _)) = original_document {
+                                self.replaced_documents_ids.insert(original_docid);
+                            }
+                            let docid = self
+                                .available_documents_ids
+                                .next()
+                                .ok_or(UserError::DocumentLimitReached)?;
+                            entry.insert(docid as u64);
+                            Some(docid)
                         }
                     }
-                    let docid = self
-                        .available_documents_ids
-                        .next()
-                        .ok_or(UserError::DocumentLimitReached)?;
-                    entry.insert(docid as u64);
-                    docid
                 }
             };
 
-            let mut skip_insertion = false;
-            if let Some(original_docid) = original_docid {
-                let original_key = BEU32::new(original_docid);
-                let base_obkv = self
-                    .index
-                    .documents
-                    .remap_data_type::<heed::types::ByteSlice>()
-                    .get(wtxn, &original_key)?
-                    .ok_or(InternalError::DatabaseMissingEntry {
-                        db_name: db_name::DOCUMENTS,
-                        key: None,
-                    })?;
-
-                // we check if the two documents are exactly equal. If it's the case we can skip this document entirely
-                if base_obkv == obkv_buffer {
-                    // we're not replacing anything
-                    self.replaced_documents_ids.remove(original_docid);
-                    // and we need to put back the original id as it was before
-                    self.new_external_documents_ids_builder.remove(external_id);
-                    skip_insertion = true;
-                } else {
+            if let Some(docid) = docid {
+                if let Some((original_docid, base_obkv)) = original_document {
+                    // we remember which document is replaced to compare them once merged.
+                    self.original_documents_ids.insert(docid, original_docid);
                     // we associate the base document with the new key, everything will get merged later.
                     document_sorter_buffer.clear();
                     document_sorter_buffer.push(Operation::Addition as u8);
@@ -281,9 +513,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                             .insert(docid.to_be_bytes(), &document_sorter_buffer)?,
                     }
                 }
-            }
 
-            if !skip_insertion {
                 self.new_documents_ids.insert(docid);
 
                 document_sorter_buffer.clear();
@@ -325,7 +555,68 @@ impl<'a, 'i> Transform<'a, 'i> {
         self.documents_count += documents_count;
         // Now that we have a valid sorter that contains the user id and the obkv we
         // give it to the last transforming function which returns the TransformOutput.
-        Ok(documents_count)
+        Ok(Ok(documents_count))
+    }
+
+    /// Checks that all the documents can be processed and respect the document schema,
+    /// an invalid document fails the addition before any document is written.
+    fn check_documents<R: Read + Seek>(
+        &self,
+        rtxn: &RoTxn,
+        cursor: &mut EnrichedDocumentsBatchCursor<R>,
+        fields_index: &DocumentsBatchIndex,
+        primary_key_roots: &[&str],
+        ingest_pipeline: &[IngestProcessor],
+        document_schema: Option<&DocumentSchema>,
+    ) -> Result<StdResult<(), UserError>> {
+        let external_documents_ids = self.index.external_documents_ids(rtxn)?;
+        let is_update = self.index_documents_method == IndexDocumentsMethod::UpdateDocuments;
+
+        // The documents updated multiple times by this addition are merged with their
+        // previous version of this addition.
+        let mut updated_documents: FxHashMap<String, Object> = FxHashMap::default();
+        let mut invalid_documents = Vec::new();
+        while let Some(EnrichedDocument { document, document_id }) =
+            cursor.next_enriched_document()?
+        {
+            let external_id = document_id.value();
+            let object = obkv_to_object(&document, fields_index)?;
+            let base = match updated_documents.remove(external_id) {
+                Some(base) => Some(base),
+                None if is_update => {
+                    self.base_document(rtxn, &external_documents_ids, external_id)?
+                }
+                None => None,
+            };
+
+            match prepare_document(
+                object,
+                base,
+                external_id,
+                primary_key_roots,
+                ingest_pipeline,
+                document_schema,
+            ) {
+                Ok(object) if is_update => {
+                    updated_documents.insert(external_id.to_string(), object);
+                }
+                Ok(_) => (),
+                Err(UserError::InvalidDocumentSchema { documents }) => {
+                    invalid_documents.extend(documents)
+                }
+                Err(user_error) => {
+                    cursor.reset();
+                    return Ok(Err(user_error));
+                }
+            }
+        }
+        cursor.reset();
+
+        if invalid_documents.is_empty() {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(UserError::InvalidDocumentSchema { documents: invalid_documents }))
+        }
     }
 
     /// The counter part of `read_documents` that removes documents either from the transform or the database.
@@ -374,6 +665,9 @@ impl<'a, 'i> Transform<'a, 'i> {
                     // 2. It wasn't in it because the document was created by a previous batch and since
                     //    we're removing it there is nothing to do.
                     self.new_documents_ids.remove(doc_id);
+                    self.original_documents_ids.remove(&doc_id);
+                    self.documents_versions.remove(entry.key());
+                    self.updated_documents.remove(entry.key());
                     entry.remove_entry();
                 }
                 Entry::Vacant(entry) => {
@@ -395,6 +689,85 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_deleted)
     }
 
+    /// Returns the current state of the document an update is merged with, either the one
+    /// previously updated by this transform or the one stored in the database.
+    fn base_document(
+        &self,
+        rtxn: &RoTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+    ) -> Result<Option<Object>> {
+        if let Some(object) = self.updated_documents.get(external_id) {
+            return Ok(Some(object.clone()));
+        }
+
+        match external_documents_ids.get(external_id) {
+            // The replaced documents were deleted by this transform, they start from scratch.
+            Some(docid) if !self.replaced_documents_ids.contains(docid) => {
+                let document = self
+                    .index
+                    .documents
+                    .remap_data_type::<heed::types::ByteSlice>()
+                    .get(rtxn, &BEU32::new(docid))?
+                    .ok_or(InternalError::DatabaseMissingEntry {
+                        db_name: db_name::DOCUMENTS,
+                        key: None,
+                    })?;
+                all_obkv_to_json(KvReader::new(document), &self.fields_ids_map).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the version of the document stored in the database, if it has a valid one.
+    fn stored_document_version(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+        version_field_id: FieldId,
+    ) -> Result<Option<Number>> {
+        let document = self
+            .index
+            .documents
+            .remap_data_type::<heed::types::ByteSlice>()
+            .get(rtxn, &BEU32::new(docid))?
+            .ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::DOCUMENTS,
+                key: None,
+            })?;
+
+        match KvReader::<FieldId>::new(document).get(version_field_id) {
+            Some(version) => {
+                match serde_json::from_slice(version).map_err(InternalError::SerdeJson)? {
+                    Value::Number(version) => Ok(Some(version)),
+                    _ => Ok(None),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the version of the document last read by this transform, or the version
+    /// of the document stored in the database when it was not read by this transform.
+    fn current_document_version(
+        &self,
+        rtxn: &RoTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+        version_field_id: FieldId,
+    ) -> Result<Option<Number>> {
+        if let Some(version) = self.documents_versions.get(external_id) {
+            return Ok(Some(version.clone()));
+        }
+
+        match external_documents_ids.get(external_id) {
+            Some(docid) if !self.replaced_documents_ids.contains(docid) => {
+                self.stored_document_version(rtxn, docid, version_field_id)
+            }
+            _ => Ok(None),
+        }
+    }
+
     // Flatten a document from the fields ids map contained in self and insert the new
     // created fields. Returns `None` if the document doesn't need to be flattened.
     fn flatten_from_fields_ids_map(&mut self, obkv: KvReader<FieldId>) -> Result<Option<Vec<u8>>> {
@@ -551,7 +924,7 @@ impl<'a, 'i> Transform<'a, 'i> {
     /// format like CSV, JSON or JSON stream. This sorter must contain a key that is the document
     /// id for the user side and the value must be an obkv where keys are valid fields ids.
     pub(crate) fn output_from_sorter<F>(
-        self,
+        mut self,
         wtxn: &mut heed::RwTxn,
         progress_callback: F,
     ) -> Result<TransformOutput>
@@ -581,6 +954,15 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         self.remove_deleted_documents_from_field_distribution(wtxn, &mut field_distribution)?;
 
+        // The replaced documents that don't need to be reindexed keep their document id.
+        let indexed_fields = self.indexed_fields(wtxn)?;
+        let mut kept_documents_ids = RoaringBitmap::new();
+        let mut stored_only_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
         // Here we are going to do the document count + field distribution + `write_into_stream_writer`
         let mut iter = self.original_sorter.into_stream_merger_iter()?;
         // used only for the callback
@@ -610,6 +992,35 @@ impl<'a, 'i> Transform<'a, 'i> {
                     })?;
                 *field_distribution.entry(name.to_string()).or_insert(0) += 1;
             }
+
+            let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+            if let Some(&original_docid) = self.original_documents_ids.get(&docid) {
+                let base_obkv =
+                    self.index.documents.get(wtxn, &BEU32::new(original_docid))?.ok_or(
+                        InternalError::DatabaseMissingEntry {
+                            db_name: db_name::DOCUMENTS,
+                            key: None,
+                        },
+                    )?;
+
+                let change = document_change(
+                    &self.fields_ids_map,
+                    base_obkv,
+                    KvReader::new(val),
+                    indexed_fields.as_ref(),
+                )?;
+                if let DocumentChange::StoredFieldsOnly = change {
+                    stored_only_writer.insert(original_docid.to_be_bytes(), val)?;
+                }
+                if let DocumentChange::None | DocumentChange::StoredFieldsOnly = change {
+                    // the document is neither replaced nor extracted again.
+                    kept_documents_ids.insert(docid);
+                    self.new_documents_ids.remove(docid);
+                    self.replaced_documents_ids.remove(original_docid);
+                    continue;
+                }
+            }
+
             writer.insert(key, val)?;
         }
 
@@ -617,6 +1028,9 @@ impl<'a, 'i> Transform<'a, 'i> {
         // We then extract the file and reset the seek to be able to read it again.
         original_documents.rewind()?;
 
+        let mut stored_only_documents = stored_only_writer.into_inner()?;
+        stored_only_documents.rewind()?;
+
         // We create a final writer to write the new documents in order from the sorter.
         let mut writer = create_writer(
             self.indexer_settings.chunk_compression_type,
@@ -632,14 +1046,22 @@ impl<'a, 'i> Transform<'a, 'i> {
             if val[0] == Operation::Deletion as u8 {
                 continue;
             }
+            let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+            if kept_documents_ids.contains(docid) {
+                continue;
+            }
             let val = &val[1..];
             writer.insert(key, val)?;
         }
         let mut flattened_documents = writer.into_inner()?;
         flattened_documents.rewind()?;
 
-        let mut new_external_documents_ids_builder: Vec<_> =
-            self.new_external_documents_ids_builder.into_iter().collect();
+        // The kept documents are still associated with their original document id.
+        let mut new_external_documents_ids_builder: Vec<_> = self
+            .new_external_documents_ids_builder
+            .into_iter()
+            .filter(|(_, docid)| !kept_documents_ids.contains(*docid as DocumentId))
+            .collect();
 
         new_external_documents_ids_builder
             .sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
@@ -659,9 +1081,23 @@ impl<'a, 'i> Transform<'a, 'i> {
             documents_count: self.documents_count,
             original_documents,
             flattened_documents,
+            stored_only_documents,
         })
     }
 
+    /// Returns the names of the fields that are written in the word and facet databases,
+    /// or `None` if all the fields are searchable.
+    fn indexed_fields(&self, rtxn: &RoTxn) -> Result<Option<HashSet<String>>> {
+        match self.index.searchable_fields(rtxn)? {
+            Some(searchable_fields) => {
+                let mut fields = self.index.user_defined_faceted_fields(rtxn)?;
+                fields.extend(searchable_fields.into_iter().map(String::from));
+                Ok(Some(fields))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Clear all databases. Returns a `TransformOutput` with a file that contains the documents
     /// of the index with the attributes reordered accordingly to the `FieldsIdsMap` given as argument.
     ///
@@ -727,38 +1163,8 @@ impl<'a, 'i> Transform<'a, 'i> {
 
             // Once we have the document. We're going to flatten it
             // and insert it in the flattened sorter.
-            let mut doc = serde_json::Map::new();
-
-            let reader = obkv::KvReader::new(buffer);
-            for (k, v) in reader.iter() {
-                let key = new_fields_ids_map.name(k).ok_or(FieldIdMapMissingEntry::FieldId {
-                    field_id: k,
-                    process: "Accessing field distribution in transform.",
-                })?;
-                let value = serde_json::from_slice::<serde_json::Value>(v)
-                    .map_err(InternalError::SerdeJson)?;
-                doc.insert(key.to_string(), value);
-            }
-
-            let flattened = flatten_serde_json::flatten(&doc);
-
-            // Once we have the flattened version we can convert it back to obkv and
-            // insert all the new generated fields_ids (if any) in the fields ids map.
-            let mut buffer: Vec<u8> = Vec::new();
-            let mut writer = KvWriter::new(&mut buffer);
-            let mut flattened: Vec<_> = flattened.into_iter().collect();
-            // we reorder the field to get all the known field first
-            flattened.sort_unstable_by_key(|(key, _)| {
-                new_fields_ids_map.id(key).unwrap_or(FieldId::MAX)
-            });
-
-            for (key, value) in flattened {
-                let fid =
-                    new_fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
-                let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
-                writer.insert(fid, &value)?;
-            }
-            flattened_writer.insert(docid.to_be_bytes(), &buffer)?;
+            let flattened = flatten_document(KvReader::new(buffer), &mut new_fields_ids_map)?;
+            flattened_writer.insert(docid.to_be_bytes(), &flattened)?;
         }
 
         // Once we have written all the documents, we extract
@@ -769,6 +1175,14 @@ impl<'a, 'i> Transform<'a, 'i> {
         let mut flattened_documents = flattened_writer.into_inner()?;
         flattened_documents.rewind()?;
 
+        let mut stored_only_documents = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        )
+        .into_inner()?;
+        stored_only_documents.rewind()?;
+
         let output = TransformOutput {
             primary_key,
             fields_ids_map: new_fields_ids_map,
@@ -779,6 +1193,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             documents_count,
             original_documents,
             flattened_documents,
+            stored_only_documents,
         };
 
         let new_facets = output.compute_real_facets(wtxn, self.index)?;
@@ -789,6 +1204,274 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         Ok(output)
     }
+
+    /// Clear the databases of the given scope. Returns a `TransformOutput` with a file that
+    /// contains the flattened documents of the index, the documents themselves are kept as is.
+    ///
+    /// The `FieldsIdsMap` must not have been remapped since the documents were indexed.
+    pub(crate) fn prepare_for_partial_reindexing(
+        self,
+        wtxn: &mut heed::RwTxn<'i, '_>,
+        scope: ReindexScope,
+    ) -> Result<TransformOutput> {
+        // The soft deleted documents are still referenced by the databases we keep,
+        // we permanently delete them before rebuilding the other ones.
+        if !self.index.soft_deleted_documents_ids(wtxn)?.is_empty() {
+            let mut deletion_builder = DeleteDocuments::new(wtxn, self.index)?;
+            deletion_builder.strategy(DeletionStrategy::AlwaysHard);
+            deletion_builder.execute_inner()?;
+        }
+
+        // There already has been a document addition, the primary key should be set by now.
+        let primary_key = self
+            .index
+            .primary_key(wtxn)?
+            .ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::MAIN,
+                key: Some(main_key::PRIMARY_KEY_KEY),
+            })?
+            .to_string();
+        let field_distribution = self.index.field_distribution(wtxn)?;
+        let mut fields_ids_map = self.index.fields_ids_map(wtxn)?;
+
+        let documents_ids = self.index.documents_ids(wtxn)?;
+        let documents_count = documents_ids.len() as usize;
+
+        // The documents are not written again, the original documents file stays empty.
+        let original_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        for result in self.index.all_documents(wtxn)? {
+            let (docid, obkv) = result?;
+            let flattened = flatten_document(obkv, &mut fields_ids_map)?;
+            flattened_writer.insert(docid.to_be_bytes(), &flattened)?;
+        }
+
+        let mut original_documents = original_writer.into_inner()?;
+        original_documents.rewind()?;
+
+        let mut flattened_documents = flattened_writer.into_inner()?;
+        flattened_documents.rewind()?;
+
+        let mut stored_only_documents = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        )
+        .into_inner()?;
+        stored_only_documents.rewind()?;
+
+        let output = TransformOutput {
+            primary_key,
+            fields_ids_map,
+            field_distribution,
+            new_external_documents_ids: fst::Map::default().map_data(Cow::Owned)?,
+            new_documents_ids: documents_ids,
+            replaced_documents_ids: RoaringBitmap::default(),
+            documents_count,
+            original_documents,
+            flattened_documents,
+            stored_only_documents,
+        };
+
+        if scope.words {
+            clear_word_databases(wtxn, self.index)?;
+        }
+
+        if scope.facets {
+            // The facet databases are cleared using the previous faceted fields.
+            clear_facet_databases(wtxn, self.index)?;
+            let new_facets = output.compute_real_facets(wtxn, self.index)?;
+            self.index.put_faceted_fields(wtxn, &new_facets)?;
+        }
+
+        Ok(output)
+    }
+}
+
+/// Computes the fields that differ between the stored and the new version of a document
+/// and returns whether they impact the word and facet databases.
+fn document_change(
+    fields_ids_map: &FieldsIdsMap,
+    base: KvReader<FieldId>,
+    new: KvReader<FieldId>,
+    indexed_fields: Option<&HashSet<String>>,
+) -> Result<DocumentChange> {
+    let changed_fields = base
+        .iter()
+        .map(|(fid, _)| fid)
+        .chain(new.iter().map(|(fid, _)| fid))
+        .unique()
+        .filter(|fid| base.get(*fid) != new.get(*fid));
+
+    let mut change = DocumentChange::None;
+    for fid in changed_fields {
+        let name = fields_ids_map.name(fid).ok_or(FieldIdMapMissingEntry::FieldId {
+            field_id: fid,
+            process: "Computing the document changes in transform.",
+        })?;
+        // a nested field is indexed as soon as its parent or one of its children is.
+        let is_indexed = indexed_fields.map_or(true, |fields| {
+            fields.iter().any(|field| is_faceted_by(name, field) || is_faceted_by(field, name))
+        });
+        if is_indexed {
+            return Ok(DocumentChange::IndexedFields);
+        }
+        change = DocumentChange::StoredFieldsOnly;
+    }
+
+    Ok(change)
+}
+
+/// Flattens an obkv document and returns it as an obkv, inserting all the new generated
+/// fields ids (if any) in the fields ids map.
+fn flatten_document(
+    document: KvReader<FieldId>,
+    fields_ids_map: &mut FieldsIdsMap,
+) -> Result<Vec<u8>> {
+    let mut doc = serde_json::Map::new();
+
+    for (k, v) in document.iter() {
+        let key = fields_ids_map.name(k).ok_or(FieldIdMapMissingEntry::FieldId {
+            field_id: k,
+            process: "Accessing field distribution in transform.",
+        })?;
+        let value =
+            serde_json::from_slice::<serde_json::Value>(v).map_err(InternalError::SerdeJson)?;
+        doc.insert(key.to_string(), value);
+    }
+
+    let flattened = flatten_serde_json::flatten(&doc);
+
+    // Once we have the flattened version we can convert it back to obkv and
+    // insert all the new generated fields_ids (if any) in the fields ids map.
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut writer = KvWriter::new(&mut buffer);
+    let mut flattened: Vec<_> = flattened.into_iter().collect();
+    // we reorder the field to get all the known field first
+    flattened.sort_unstable_by_key(|(key, _)| fields_ids_map.id(key).unwrap_or(FieldId::MAX));
+
+    for (key, value) in flattened {
+        let fid = fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
+        let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
+        writer.insert(fid, &value)?;
+    }
+    writer.finish()?;
+
+    Ok(buffer)
+}
+
+/// Checks that the versions of the documents can be compared according to the version condition.
+fn check_documents_versions<R: Read + Seek>(
+    cursor: &mut EnrichedDocumentsBatchCursor<R>,
+    fields_index: &DocumentsBatchIndex,
+    version_field: &str,
+    version_condition: VersionCondition,
+) -> Result<StdResult<(), UserError>> {
+    let version_field_id = match fields_index.id(version_field) {
+        Some(version_field_id) => version_field_id,
+        None => return Ok(Ok(())),
+    };
+
+    while let Some(EnrichedDocument { document, document_id }) = cursor.next_enriched_document()? {
+        let version = match document.get(version_field_id) {
+            Some(version) => serde_json::from_slice(version).map_err(InternalError::SerdeJson)?,
+            None => continue,
+        };
+        let expected = match (version_condition, &version) {
+            (_, Value::Null) => continue,
+            (VersionCondition::IfNewerThan, Value::Number(_)) => continue,
+            (VersionCondition::IfVersion, Value::Number(number)) if number.is_u64() => continue,
+            (VersionCondition::IfNewerThan, _) => "a number",
+            (VersionCondition::IfVersion, _) => "a positive integer",
+        };
+
+        cursor.reset();
+        let external_id = document_id.value();
+        let document_id =
+            serde_json::from_str(external_id).unwrap_or_else(|_| Value::from(external_id));
+        return Ok(Err(UserError::InvalidDocumentVersion { document_id, version, expected }));
+    }
+    cursor.reset();
+
+    Ok(Ok(()))
+}
+
+/// Compares two versions, as integers when they both are to keep their precision.
+fn compare_versions(left: &Number, right: &Number) -> Ordering {
+    let as_integer = |number: &Number| {
+        number.as_i64().map(i128::from).or_else(|| number.as_u64().map(i128::from))
+    };
+    match (as_integer(left), as_integer(right)) {
+        (Some(left), Some(right)) => left.cmp(&right),
+        _ => {
+            let (left, right) = (left.as_f64(), right.as_f64());
+            left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+        }
+    }
+}
+
+/// Returns the document an addition results in: merged with the document it updates,
+/// processed by the ingest pipeline and converted into the types of the document schema.
+fn prepare_document(
+    mut object: Object,
+    base: Option<Object>,
+    external_id: &str,
+    primary_key_roots: &[&str],
+    ingest_pipeline: &[IngestProcessor],
+    document_schema: Option<&DocumentSchema>,
+) -> StdResult<Object, UserError> {
+    let document_id =
+        || serde_json::from_str(external_id).unwrap_or_else(|_| Value::from(external_id));
+
+    // The processors and the schema apply to the whole document an update
+    // results in, not only to the fields it sends.
+    if let Some(mut base) = base {
+        base.extend(object);
+        object = base;
+    }
+
+    let original_primary_key: Vec<_> =
+        primary_key_roots.iter().map(|root| object.get(*root).cloned()).collect();
+    let pipeline_error =
+        |reason| UserError::InvalidIngestPipeline { document_id: document_id(), reason };
+    for processor in ingest_pipeline {
+        processor.process(&mut object).map_err(pipeline_error)?;
+    }
+    let modified_root = primary_key_roots
+        .iter()
+        .zip(&original_primary_key)
+        .find(|(root, original)| object.get(**root) != original.as_ref());
+    if let Some((primary_key_root, _)) = modified_root {
+        let reason = format!("the primary key `{primary_key_root}` cannot be modified");
+        return Err(pipeline_error(reason));
+    }
+
+    // The schema is checked once the document is processed, computed fields
+    // can be required and the processors cannot break the schema.
+    if let Some(schema) = document_schema {
+        for (name, value) in object.iter_mut() {
+            if let Some(coerced) = schema.coerce(name, value) {
+                *value = coerced;
+            }
+        }
+        if let Err(reason) = schema.validate(&object) {
+            return Err(UserError::InvalidDocumentSchema {
+                documents: vec![(document_id(), reason)],
+            });
+        }
+    }
+
+    Ok(object)
 }
 
 /// Drops all the value of type `U` in vec, and reuses the allocation to create a `Vec<T>`.
@@ -15,6 +15,7 @@ use super::helpers::{
 };
 use super::{ClonableMmap, MergeFn};
 use crate::facet::FacetType;
+use crate::stemming::is_stem;
 use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::as_cloneable_grenad;
 use crate::{lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, GeoPoint, Index, Result};
@@ -255,7 +256,11 @@ fn merge_word_docids_reader_into_fst(
     let mut builder = fst::SetBuilder::memory();
 
     while let Some((k, _)) = iter.next()? {
-        builder.insert(k)?;
+        // the stems are only searched from their original words, they must not be
+        // found by the typo and prefix derivations nor computed in the prefix databases.
+        if !is_stem(k) {
+            builder.insert(k)?;
+        }
     }
 
     Ok(builder.into_set())
@@ -1,10 +1,13 @@
 pub use self::available_documents_ids::AvailableDocumentsIds;
 pub use self::clear_documents::ClearDocuments;
 pub use self::delete_documents::{DeleteDocuments, DeletionStrategy, DocumentDeletionResult};
+pub use self::edit_documents::{EditDocuments, EditOperation};
 pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
+pub(crate) use self::index_documents::json_to_string;
 pub use self::index_documents::{
     DocumentAdditionResult, DocumentId, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod,
+    VersionCondition,
 };
 pub use self::indexer_config::IndexerConfig;
 pub use self::prefix_word_pairs::{
@@ -14,12 +17,14 @@ pub use self::prefix_word_pairs::{
 pub use self::settings::{Setting, Settings};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
+pub use self::words_infixes_fst::{WordsInfixesFst, INFIX_WORD_SEPARATOR, MIN_INFIX_LENGTH};
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
 pub use self::words_prefixes_fst::WordsPrefixesFst;
 
 mod available_documents_ids;
 mod clear_documents;
 mod delete_documents;
+mod edit_documents;
 pub(crate) mod facet;
 mod index_documents;
 mod indexer_config;
@@ -27,5 +32,6 @@ mod prefix_word_pairs;
 mod settings;
 mod update_step;
 mod word_prefix_docids;
+mod words_infixes_fst;
 mod words_prefix_integer_docids;
 mod words_prefixes_fst;
@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::result::Result as StdResult;
 
 use charabia::{Tokenizer, TokenizerBuilder};
@@ -12,9 +12,12 @@ use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
-use crate::update::index_documents::IndexDocumentsMethod;
-use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, Result};
+use crate::update::index_documents::{primary_key_fields, IndexDocumentsMethod, ReindexScope};
+use crate::update::{IndexDocuments, UpdateIndexingStep, WordsInfixesFst};
+use crate::{
+    DocumentSchema, FieldsIdsMap, Index, IngestProcessor, LocalizedAttributesRule, Result,
+    StemmingLanguage,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -114,6 +117,10 @@ pub struct Settings<'a, 't, 'u, 'i> {
     stop_words: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
     synonyms: Setting<HashMap<String, Vec<String>>>,
+    /// Named synonym sets to add, replace or remove, the sets not listed are kept.
+    synonym_sets: Setting<BTreeMap<String, Setting<HashMap<String, Vec<String>>>>>,
+    /// Whether all the existing synonym sets are removed before applying `synonym_sets`.
+    clear_synonym_sets: bool,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
@@ -121,6 +128,20 @@ pub struct Settings<'a, 't, 'u, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Attributes on which words can be found from any of their substrings.
+    infix_searchable_fields: Setting<HashSet<String>>,
+    /// Languages whose words are indexed and searched along with their stem.
+    stemming_languages: Setting<BTreeSet<StemmingLanguage>>,
+    /// Rules declaring the locales of the attributes instead of detecting them.
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    /// Constraints the documents must respect, only checked on the new documents.
+    document_schema: Setting<DocumentSchema>,
+    /// Processors applied in order on the new documents, before they are stored and indexed.
+    ingest_pipeline: Setting<Vec<IngestProcessor>>,
+    /// Field containing the timestamp after which a document is hidden and deleted.
+    expiry_field: Setting<String>,
+    /// Field containing the version of the documents, the stale documents are skipped.
+    version_field: Setting<String>,
     max_values_per_facet: Setting<usize>,
     pagination_max_total_hits: Setting<usize>,
 }
@@ -142,12 +163,21 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             stop_words: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
+            clear_synonym_sets: false,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             exact_words: Setting::NotSet,
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            infix_searchable_fields: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            expiry_field: Setting::NotSet,
+            version_field: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             indexer_config,
@@ -219,6 +249,24 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
     }
 
+    /// Removes all the synonym sets, including the ones previously set with this builder.
+    pub fn reset_synonym_sets(&mut self) {
+        self.synonym_sets = Setting::Reset;
+        self.clear_synonym_sets = true;
+    }
+
+    /// Adds or replaces the given synonym sets, or removes the ones that are reset.
+    /// The other synonym sets of the index are kept as is.
+    pub fn set_synonym_sets(
+        &mut self,
+        synonym_sets: BTreeMap<String, Setting<HashMap<String, Vec<String>>>>,
+    ) {
+        match self.synonym_sets {
+            Setting::Set(ref mut pending) => pending.extend(synonym_sets),
+            _ => self.synonym_sets = Setting::Set(synonym_sets),
+        }
+    }
+
     pub fn reset_primary_key(&mut self) {
         self.primary_key = Setting::Reset;
     }
@@ -267,6 +315,62 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_infix_searchable_fields(&mut self, attrs: HashSet<String>) {
+        self.infix_searchable_fields = Setting::Set(attrs);
+    }
+
+    pub fn reset_infix_searchable_fields(&mut self) {
+        self.infix_searchable_fields = Setting::Reset;
+    }
+
+    pub fn set_stemming_languages(&mut self, languages: BTreeSet<StemmingLanguage>) {
+        self.stemming_languages = Setting::Set(languages);
+    }
+
+    pub fn reset_stemming_languages(&mut self) {
+        self.stemming_languages = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, rules: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(rules);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_document_schema(&mut self, schema: DocumentSchema) {
+        self.document_schema = Setting::Set(schema);
+    }
+
+    pub fn reset_document_schema(&mut self) {
+        self.document_schema = Setting::Reset;
+    }
+
+    pub fn set_ingest_pipeline(&mut self, processors: Vec<IngestProcessor>) {
+        self.ingest_pipeline = Setting::Set(processors);
+    }
+
+    pub fn reset_ingest_pipeline(&mut self) {
+        self.ingest_pipeline = Setting::Reset;
+    }
+
+    pub fn set_expiry_field(&mut self, expiry_field: String) {
+        self.expiry_field = Setting::Set(expiry_field);
+    }
+
+    pub fn reset_expiry_field(&mut self) {
+        self.expiry_field = Setting::Reset;
+    }
+
+    pub fn set_version_field(&mut self, version_field: String) {
+        self.version_field = Setting::Set(version_field);
+    }
+
+    pub fn reset_version_field(&mut self) {
+        self.version_field = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -288,6 +392,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         progress_callback: &FP,
         should_abort: &FA,
         old_fields_ids_map: FieldsIdsMap,
+        scope: ReindexScope,
     ) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -308,12 +413,21 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             false,
         )?;
 
-        // We clear the databases and remap the documents fields based on the new `FieldsIdsMap`.
-        let output = transform.prepare_for_documents_reindexing(
-            self.wtxn,
-            old_fields_ids_map,
-            fields_ids_map,
-        )?;
+        // When the fields ids didn't move we only clear and rebuild the impacted databases,
+        // otherwise we clear the databases and remap the documents fields based on the new
+        // `FieldsIdsMap`.
+        let fields_ids_unchanged =
+            old_fields_ids_map.iter().all(|(id, name)| fields_ids_map.id(name) == Some(id));
+        let (output, scope) = if fields_ids_unchanged {
+            (transform.prepare_for_partial_reindexing(self.wtxn, scope)?, scope)
+        } else {
+            let output = transform.prepare_for_documents_reindexing(
+                self.wtxn,
+                old_fields_ids_map,
+                fields_ids_map,
+            )?;
+            (output, ReindexScope::ALL)
+        };
 
         // We index the generated `TransformOutput` which must contain
         // all the documents with fields in the newly defined searchable order.
@@ -325,7 +439,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             &progress_callback,
             &should_abort,
         )?;
-        indexing_builder.execute_raw(output)?;
+        indexing_builder.with_scope(scope).execute_raw(output)?;
 
         Ok(())
     }
@@ -358,6 +472,34 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(true)
     }
 
+    fn update_expiry_field(&mut self) -> Result<()> {
+        match self.expiry_field {
+            Setting::Set(ref field) => {
+                self.index.put_expiry_field(self.wtxn, field)?;
+            }
+            Setting::Reset => {
+                self.index.delete_expiry_field(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_version_field(&mut self) -> Result<()> {
+        match self.version_field {
+            Setting::Set(ref field) => {
+                self.index.put_version_field(self.wtxn, field)?;
+            }
+            Setting::Reset => {
+                self.index.delete_version_field(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     /// Updates the index's searchable attributes. This causes the field map to be recomputed to
     /// reflect the order of the searchable attributes.
     fn update_searchable(&mut self) -> Result<bool> {
@@ -433,47 +575,51 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
-    fn update_synonyms(&mut self) -> Result<bool> {
-        match self.synonyms {
-            Setting::Set(ref synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
-                        .filter_map(|token| {
-                            if token.is_word() {
-                                Some(token.lemma().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                }
+    /// Normalizes both the words and their synonyms like the words of the queries.
+    fn normalize_synonyms(
+        &self,
+        synonyms: &HashMap<String, Vec<String>>,
+    ) -> Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+        fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> Vec<String> {
+            tokenizer
+                .tokenize(text)
+                .filter(|token| token.is_word())
+                .map(|token| token.lemma().to_string())
+                .collect::<Vec<_>>()
+        }
 
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
-                let tokenizer = builder.build();
+        let mut builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.wtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            builder.stop_words(stop_words);
+        }
+        let tokenizer = builder.build();
+
+        let mut new_synonyms = HashMap::new();
+        for (word, synonyms) in synonyms {
+            // Normalize both the word and associated synonyms.
+            let normalized_word = normalize(&tokenizer, word);
+            let normalized_synonyms = synonyms.iter().map(|synonym| normalize(&tokenizer, synonym));
+
+            // Store the normalized synonyms under the normalized word,
+            // merging the possible duplicate words.
+            let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+            entry.extend(normalized_synonyms);
+        }
 
-                let mut new_synonyms = HashMap::new();
-                for (word, synonyms) in synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms =
-                        synonyms.iter().map(|synonym| normalize(&tokenizer, synonym));
-
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                    entry.extend(normalized_synonyms);
-                }
+        // Make sure that we don't have duplicate synonyms.
+        new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+            synonyms.sort_unstable();
+            synonyms.dedup();
+        });
 
-                // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+        Ok(new_synonyms)
+    }
+
+    fn update_synonyms(&mut self) -> Result<bool> {
+        match self.synonyms {
+            Setting::Set(ref synonyms) => {
+                let new_synonyms = self.normalize_synonyms(synonyms)?;
 
                 let old_synonyms = self.index.synonyms(self.wtxn)?;
 
@@ -489,6 +635,36 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_synonym_sets(&mut self) -> Result<bool> {
+        let old_synonym_sets = self.index.synonym_sets(self.wtxn)?;
+        let mut new_synonym_sets =
+            if self.clear_synonym_sets { BTreeMap::new() } else { old_synonym_sets.clone() };
+
+        if let Setting::Set(ref synonym_sets) = self.synonym_sets {
+            for (name, synonyms) in synonym_sets {
+                match synonyms {
+                    Setting::Set(synonyms) if !synonyms.is_empty() => {
+                        let synonyms = self.normalize_synonyms(synonyms)?;
+                        new_synonym_sets.insert(name.clone(), synonyms);
+                    }
+                    Setting::Set(_) | Setting::Reset => {
+                        new_synonym_sets.remove(name);
+                    }
+                    Setting::NotSet => (),
+                }
+            }
+        }
+
+        if new_synonym_sets == old_synonym_sets {
+            Ok(false)
+        } else if new_synonym_sets.is_empty() {
+            Ok(self.index.delete_synonym_sets(self.wtxn)?)
+        } else {
+            self.index.put_synonym_sets(self.wtxn, &new_synonym_sets)?;
+            Ok(true)
+        }
+    }
+
     fn update_exact_attributes(&mut self) -> Result<bool> {
         match self.exact_attributes {
             Setting::Set(ref attrs) => {
@@ -508,6 +684,60 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_infix_searchable_fields(&mut self) -> Result<bool> {
+        match self.infix_searchable_fields {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.infix_searchable_fields(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_infix_searchable_fields(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_infix_searchable_fields(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_stemming_languages(&mut self) -> Result<bool> {
+        match self.stemming_languages {
+            Setting::Set(ref languages) => {
+                let old_languages = self.index.stemming_languages(self.wtxn)?;
+                let old_languages = old_languages.into_iter().collect::<BTreeSet<_>>();
+
+                if languages != &old_languages {
+                    let languages = languages.iter().copied().collect::<Vec<_>>();
+                    self.index.put_stemming_languages(self.wtxn, &languages)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_stemming_languages(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                let old_rules = self.index.localized_attributes_rules(self.wtxn)?;
+                if old_rules.as_ref() != Some(rules) {
+                    self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -560,7 +790,9 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             Setting::Set(ref primary_key) => {
                 if self.index.number_of_documents(self.wtxn)? == 0 {
                     let mut fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
-                    fields_ids_map.insert(primary_key).ok_or(UserError::AttributeLimitReached)?;
+                    for field in primary_key_fields(primary_key) {
+                        fields_ids_map.insert(field).ok_or(UserError::AttributeLimitReached)?;
+                    }
                     self.index.put_fields_ids_map(self.wtxn, &fields_ids_map)?;
                     self.index.put_primary_key(self.wtxn, primary_key)?;
                     Ok(())
@@ -680,6 +912,34 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_document_schema(&mut self) -> Result<()> {
+        match self.document_schema {
+            Setting::Set(ref schema) => {
+                self.index.put_document_schema(self.wtxn, schema)?;
+            }
+            Setting::Reset => {
+                self.index.delete_document_schema(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_ingest_pipeline(&mut self) -> Result<()> {
+        match self.ingest_pipeline {
+            Setting::Set(ref processors) => {
+                self.index.put_ingest_pipeline(self.wtxn, processors)?;
+            }
+            Setting::Reset => {
+                self.index.delete_ingest_pipeline(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -708,6 +968,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_filterable()?;
         self.update_sortable()?;
         self.update_distinct_field()?;
+        self.update_expiry_field()?;
         self.update_criteria()?;
         self.update_primary_key()?;
         self.update_authorize_typos()?;
@@ -715,25 +976,49 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_pagination_max_total_hits()?;
+        // The schema is only enforced on the documents sent after it, the
+        // documents already in the index are neither validated nor reindexed.
+        self.update_document_schema()?;
+        // Like the schema, the pipeline only processes the documents sent after it.
+        self.update_ingest_pipeline()?;
+        // The versions are only compared when documents are sent, nothing is reindexed.
+        self.update_version_field()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
-        // index new fields as facets. It means that the distinct attribute,
-        // an Asc/Desc criterion or a filtered attribute as be added or removed.
+        // index new fields as facets. It means that the distinct attribute, the expiry
+        // field, an Asc/Desc criterion or a filtered attribute as be added or removed.
         let new_faceted_fields = self.index.user_defined_faceted_fields(self.wtxn)?;
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
         let stop_words_updated = self.update_stop_words()?;
-        let synonyms_updated = self.update_synonyms()?;
+        // The synonyms and synonym sets are only used at search time, the documents are not
+        // reindexed.
+        self.update_synonyms()?;
+        self.update_synonym_sets()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
-
-        if stop_words_updated
-            || faceted_updated
-            || synonyms_updated
+        let infix_searchable_updated = self.update_infix_searchable_fields()?;
+        let stemming_updated = self.update_stemming_languages()?;
+        let localized_attributes_updated = self.update_localized_attributes_rules()?;
+
+        // Only the databases impacted by the updated settings are rebuilt, the facet
+        // databases for the faceted fields and the word databases for everything
+        // changing the way the searchable attributes are tokenized.
+        let words_updated = stop_words_updated
             || searchable_updated
             || exact_attributes_updated
-        {
-            self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
+            || stemming_updated
+            || localized_attributes_updated;
+        let scope =
+            ReindexScope { documents: false, words: words_updated, facets: faceted_updated };
+        if words_updated || faceted_updated {
+            self.reindex(&progress_callback, &should_abort, old_fields_ids_map, scope)?;
+        }
+
+        // The words infixes dictionnary is rebuilt from the existing word databases,
+        // there is no need to reindex the documents when only this setting changed.
+        if infix_searchable_updated && !words_updated {
+            WordsInfixesFst::new(self.wtxn, self.index).execute()?;
         }
 
         Ok(())
@@ -749,7 +1034,7 @@ mod tests {
     use super::*;
     use crate::error::Error;
     use crate::index::tests::TempIndex;
-    use crate::update::{ClearDocuments, DeleteDocuments};
+    use crate::update::{ClearDocuments, DeleteDocuments, VersionCondition};
     use crate::{Criterion, Filter, SearchResult};
 
     #[test]
@@ -984,6 +1269,101 @@ mod tests {
         assert_eq!(count, 4);
     }
 
+    #[test]
+    fn set_filterable_fields_only_rebuilds_facets() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .add_documents(documents!([
+                { "name": "kevin", "age": 23 },
+                { "name": "kevina", "age": 21 },
+                { "name": "benoit", "age": 34 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let kevin_docids = index.word_docids.get(&rtxn, "kevin").unwrap().unwrap();
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("age") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // The documents and the word databases are kept as is.
+        assert_eq!(
+            index.fields_ids_map(&rtxn).unwrap().ids().collect::<Vec<_>>(),
+            fields_ids_map.ids().collect::<Vec<_>>()
+        );
+        assert_eq!(index.word_docids.get(&rtxn, "kevin").unwrap().unwrap(), kevin_docids);
+        assert_eq!(index.documents_ids(&rtxn).unwrap().len(), 3);
+
+        // The facet databases are built for the new filterable field.
+        let mut search = index.search(&rtxn);
+        search.filter(Filter::from_str("age = 23").unwrap().unwrap());
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_filterable_fields();
+            })
+            .unwrap();
+
+        // The facet databases are cleared but the words are still searchable.
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
+        assert_eq!(index.word_docids.get(&rtxn, "kevin").unwrap().unwrap(), kevin_docids);
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("benoit").execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+    }
+
+    #[test]
+    fn set_stop_words_only_rebuilds_words() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("age") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "name": "the kevin", "age": 23 },
+                { "name": "the kevina", "age": 21 },
+                { "name": "benoit", "age": 34 }
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_stop_words(btreeset! { S("the") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // The word databases are rebuilt without the stop words.
+        assert!(index.word_docids.get(&rtxn, "the").unwrap().is_none());
+        assert!(index.word_docids.get(&rtxn, "kevin").unwrap().is_some());
+        assert!(index.words_fst(&rtxn).unwrap().contains("kevina"));
+        assert!(!index.words_fst(&rtxn).unwrap().contains("the"));
+
+        // The facet databases are kept as is.
+        let mut search = index.search(&rtxn);
+        search.filter(Filter::from_str("age < 30").unwrap().unwrap());
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 2);
+    }
+
     #[test]
     fn set_asc_desc_field() {
         let mut index = TempIndex::new();
@@ -1489,6 +1869,347 @@ mod tests {
         }
     }
 
+    #[test]
+    fn document_schema_validates_and_coerces_documents() {
+        let index = TempIndex::new();
+
+        index.add_documents(documents!([{ "id": 1, "title": "kevin" }])).unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_document_schema(
+                    serde_json::from_value(serde_json::json!({
+                        "fields": {
+                            "title": { "type": "string", "required": true },
+                            "price": { "type": "number" },
+                        },
+                        "coerceTypes": true,
+                    }))
+                    .unwrap(),
+                );
+            })
+            .unwrap();
+
+        // The documents already indexed are not validated.
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+        drop(rtxn);
+
+        let error = index.add_documents(documents!([{ "id": 2, "price": "12.50" }])).unwrap_err();
+        insta::assert_display_snapshot!(error, @"Document `2` does not respect the document schema: the required field `title` is missing.");
+
+        let error = index
+            .add_documents(documents!([{ "id": 2, "title": "kevina", "price": "twelve" }]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"Document `2` does not respect the document schema: the field `price` must be a number but found `"twelve"`."###);
+
+        index
+            .add_documents(documents!([{ "id": 2, "title": "kevina", "price": "12.50" }]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"title":"kevin"}
+        {"id":2,"title":"kevina","price":12.5}
+        "###);
+
+        index.update_settings(|settings| settings.reset_document_schema()).unwrap();
+        index.add_documents(documents!([{ "id": 3, "price": "free" }])).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_schema(&rtxn).unwrap(), None);
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 3);
+    }
+
+    #[test]
+    fn document_schema_validates_merged_updates() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| {
+                settings.set_document_schema(
+                    serde_json::from_value(serde_json::json!({
+                        "fields": {
+                            "title": { "type": "string", "required": true },
+                            "price": { "type": "number" },
+                        },
+                    }))
+                    .unwrap(),
+                );
+            })
+            .unwrap();
+
+        index.add_documents(documents!([{ "id": 1, "title": "kevin", "price": 10 }])).unwrap();
+
+        // A partial update is validated once merged with the stored document.
+        index.add_documents(documents!([{ "id": 1, "price": 12 }])).unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"title":"kevin","price":12}
+        "###);
+
+        // All the invalid documents of the batch are reported.
+        let error = index
+            .add_documents(documents!([
+                { "id": 1, "price": "free" },
+                { "id": 2, "price": 3 },
+                { "id": 3, "title": "kevina" },
+            ]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"2 documents do not respect the document schema: document `1`: the field `price` must be a number but found `"free"`; document `2`: the required field `title` is missing."###);
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"title":"kevin","price":12}
+        "###);
+    }
+
+    #[test]
+    fn ingest_pipeline_processes_documents() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("category_path") });
+                settings.set_ingest_pipeline(
+                    serde_json::from_value(serde_json::json!([
+                        { "type": "concatenate", "fields": ["first", "last"], "target": "full_name" },
+                        { "type": "drop", "field": "first" },
+                        { "type": "split", "field": "category_path", "separator": "/" },
+                    ]))
+                    .unwrap(),
+                );
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "first": "kevin", "last": "durant", "category_path": "sports/shoes" },
+                { "id": 2, "first": "benoit", "category_path": "books" },
+            ]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"last":"durant","category_path":["sports","shoes"],"full_name":"kevin durant"}
+        {"id":2,"category_path":["books"],"full_name":"benoit"}
+        "###);
+
+        let rtxn = index.read_txn().unwrap();
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("kevin durant").execute().unwrap();
+        assert_eq!(documents_ids, vec![0]);
+        let filter = Filter::from_str("category_path = shoes").unwrap().unwrap();
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).filter(filter).execute().unwrap();
+        assert_eq!(documents_ids, vec![0]);
+        drop(rtxn);
+
+        // The primary key of the documents cannot be modified by the processors.
+        index
+            .update_settings(|settings| {
+                settings.set_ingest_pipeline(
+                    serde_json::from_value(serde_json::json!([
+                        { "type": "rename", "field": "id", "target": "uid" },
+                    ]))
+                    .unwrap(),
+                );
+            })
+            .unwrap();
+        let error = index.add_documents(documents!([{ "id": 3, "first": "kevina" }])).unwrap_err();
+        insta::assert_display_snapshot!(error, @"Could not process the document `3` with the ingest pipeline: the primary key `id` cannot be modified.");
+    }
+
+    #[test]
+    fn ingest_pipeline_processes_merged_updates() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| {
+                settings.set_ingest_pipeline(
+                    serde_json::from_value(serde_json::json!([
+                        { "type": "concatenate", "fields": ["first", "last"], "target": "full_name" },
+                        { "type": "default", "field": "stock", "value": 0 },
+                    ]))
+                    .unwrap(),
+                );
+                // The computed fields can be required, the schema is checked after the processors.
+                settings.set_document_schema(
+                    serde_json::from_value(serde_json::json!({
+                        "fields": {
+                            "full_name": { "type": "string", "required": true },
+                            "stock": { "type": "number" },
+                        },
+                    }))
+                    .unwrap(),
+                );
+            })
+            .unwrap();
+
+        index
+            .add_documents(
+                documents!([{ "id": 1, "first": "kevin", "last": "durant", "stock": 3 }]),
+            )
+            .unwrap();
+
+        // The partial updates are processed once merged with the stored document.
+        index.add_documents(documents!([{ "id": 1, "last": "bryant" }])).unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"first":"kevin","last":"bryant","stock":3,"full_name":"kevin bryant"}
+        "###);
+
+        let error = index.add_documents(documents!([{ "id": 2, "stock": 1 }])).unwrap_err();
+        insta::assert_display_snapshot!(error, @"Document `2` does not respect the document schema: the required field `full_name` is missing.");
+    }
+
+    #[test]
+    fn expiry_field_hides_expired_documents() {
+        let index = TempIndex::new();
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "expires_at": now - 60 },
+                { "id": 2, "name": "kevina", "expires_at": now + 3600 },
+                { "id": 3, "name": "benoit" },
+            ]))
+            .unwrap();
+
+        index.update_settings(|settings| settings.set_expiry_field(S("expires_at"))).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.user_defined_faceted_fields(&rtxn).unwrap().contains("expires_at"));
+        let expired = index.expired_documents_ids(&rtxn, OffsetDateTime::now_utc()).unwrap();
+        assert_eq!(expired.into_iter().collect::<Vec<_>>(), vec![0]);
+
+        // The expired documents are excluded from the search results, the expiry
+        // field cannot be filtered as it is not a filterable attribute.
+        let SearchResult { documents_ids, .. } = index.search(&rtxn).execute().unwrap();
+        assert_eq!(documents_ids, vec![1, 2]);
+        let filter = Filter::from_str("expires_at EXISTS").unwrap().unwrap();
+        let error = index.search(&rtxn).filter(filter).execute().unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidFilter(_))));
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.reset_expiry_field()).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let SearchResult { documents_ids, .. } = index.search(&rtxn).execute().unwrap();
+        assert_eq!(documents_ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn version_field_skips_stale_documents() {
+        let index = TempIndex::new();
+
+        index.update_settings(|settings| settings.set_version_field(S("version"))).unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin", "version": 3 },
+                { "id": 2, "name": "kevina", "version": 1 },
+            ]))
+            .unwrap();
+
+        // The older versions are skipped, even when they are in the same batch as a newer one.
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin v2", "version": 2 },
+                { "id": 1, "name": "kevin v3", "version": 3 },
+                { "id": 2, "name": "kevina v4", "version": 4 },
+                { "id": 2, "name": "kevina v2", "version": 2 },
+                { "id": 3, "name": "benoit", "version": 1 },
+            ]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"name":"kevin","version":3}
+        {"id":2,"name":"kevina v4","version":4}
+        {"id":3,"name":"benoit","version":1}
+        "###);
+
+        // The documents without a version are always applied.
+        index.add_documents(documents!([{ "id": 1, "name": "kevin v0" }])).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+        let document = crate::obkv_to_json(
+            &fields_ids_map.ids().collect::<Vec<_>>(),
+            &fields_ids_map,
+            document,
+        )
+        .unwrap();
+        assert_eq!(document.get("name"), Some(&serde_json::json!("kevin v0")));
+        assert_eq!(document.get("version"), None);
+        drop(rtxn);
+
+        // The integer versions are compared without losing their precision.
+        index
+            .add_documents(
+                documents!([{ "id": 4, "name": "tamo", "version": 9007199254740992u64 }]),
+            )
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 4, "name": "tamo v2", "version": 9007199254740993u64 },
+                { "id": 4, "name": "tamo v1", "version": 9007199254740992u64 },
+            ]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"name":"kevin v0"}
+        {"id":2,"name":"kevina v4","version":4}
+        {"id":3,"name":"benoit","version":1}
+        {"id":4,"name":"tamo v2","version":9007199254740993}
+        "###);
+
+        let error = index
+            .add_documents(documents!([{ "id": 3, "name": "benoit", "version": "two" }]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"Document `3` has an invalid version: expected a number, but found `"two"`."###);
+    }
+
+    #[test]
+    fn version_condition_if_version_increments_the_versions() {
+        let mut index = TempIndex::new();
+
+        index.add_documents(documents!([{ "id": 1, "name": "kevin" }])).unwrap();
+        index.index_documents_config.version_condition = Some(VersionCondition::IfVersion);
+        let error = index.add_documents(documents!([{ "id": 1, "name": "kevin" }])).unwrap_err();
+        insta::assert_display_snapshot!(error, @"The `versionCondition` parameter can only be used when the index has a version field.");
+
+        index.update_settings(|settings| settings.set_version_field(S("version"))).unwrap();
+
+        // The documents without a version are written when they are new, or their stored
+        // version is missing, and the written documents get the next version.
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin v1" },
+                { "id": 2, "name": "kevina v1" },
+                { "id": 2, "name": "kevina v2", "version": 1 },
+                { "id": 2, "name": "kevina stale", "version": 1 },
+            ]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"name":"kevin v1","version":1}
+        {"id":2,"name":"kevina v2","version":2}
+        "###);
+
+        // The documents are only written when they send the stored version.
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin stale", "version": 0 },
+                { "id": 2, "name": "kevina v3", "version": 2 },
+            ]))
+            .unwrap();
+        crate::db_snap!(index, documents, @r###"
+        {"id":1,"name":"kevin v1","version":1}
+        {"id":2,"name":"kevina v3","version":3}
+        "###);
+
+        let error = index
+            .add_documents(documents!([{ "id": 1, "name": "kevin", "version": 1.5 }]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(error, @"Document `1` has an invalid version: expected a positive integer, but found `1.5`.");
+
+        // The next version of the maximum version cannot be represented.
+        let error = index
+            .add_documents(documents!([{ "id": 1, "name": "kevin", "version": u64::MAX }]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(error, @"Document `1` has an invalid version: expected a positive integer lower than 18446744073709551615, but found `18446744073709551615`.");
+    }
+
     #[test]
     fn test_correct_settings_init() {
         let index = TempIndex::new();
@@ -1508,12 +2229,21 @@ mod tests {
                     stop_words,
                     distinct_field,
                     synonyms,
+                    synonym_sets,
+                    clear_synonym_sets,
                     primary_key,
                     authorize_typos,
                     min_word_len_two_typos,
                     min_word_len_one_typo,
                     exact_words,
                     exact_attributes,
+                    infix_searchable_fields,
+                    stemming_languages,
+                    localized_attributes_rules,
+                    document_schema,
+                    ingest_pipeline,
+                    expiry_field,
+                    version_field,
                     max_values_per_facet,
                     pagination_max_total_hits,
                 } = settings;
@@ -1525,12 +2255,21 @@ mod tests {
                 assert!(matches!(stop_words, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
+                assert!(matches!(synonym_sets, Setting::NotSet));
+                assert!(!clear_synonym_sets);
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_two_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(infix_searchable_fields, Setting::NotSet));
+                assert!(matches!(stemming_languages, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(document_schema, Setting::NotSet));
+                assert!(matches!(ingest_pipeline, Setting::NotSet));
+                assert!(matches!(expiry_field, Setting::NotSet));
+                assert!(matches!(version_field, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
             })
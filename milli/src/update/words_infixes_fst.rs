@@ -0,0 +1,111 @@
+use std::collections::{BTreeSet, HashSet};
+
+use heed::types::DecodeIgnore;
+use heed::BytesDecode;
+
+use crate::heed_codec::StrBEU16Codec;
+use crate::stemming::is_stem;
+use crate::update::index_documents::CursorClonableMmap;
+use crate::{FieldId, Index, Result};
+
+/// The minimum number of characters an infix must have to be part of the
+/// words infixes dictionnary.
+pub const MIN_INFIX_LENGTH: usize = 3;
+
+/// The byte used to separate the infix from the word it comes from in the
+/// words infixes dictionnary.
+pub const INFIX_WORD_SEPARATOR: char = '\0';
+
+/// Builds the words infixes dictionnary of the engine.
+///
+/// For every word that appears in one of the infix searchable attributes, every
+/// proper suffix of at least [`MIN_INFIX_LENGTH`] characters is stored followed by a `\0`
+/// and the original word. Doing a prefix search on this dictionnary with any substring
+/// of a word therefore returns the word.
+pub struct WordsInfixesFst<'t, 'u, 'i> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+}
+
+impl<'t, 'u, 'i> WordsInfixesFst<'t, 'u, 'i> {
+    pub fn new(wtxn: &'t mut heed::RwTxn<'i, 'u>, index: &'i Index) -> WordsInfixesFst<'t, 'u, 'i> {
+        WordsInfixesFst { wtxn, index }
+    }
+
+    #[logging_timer::time("WordsInfixesFst::{}")]
+    pub fn execute(self) -> Result<()> {
+        let infix_fields_ids = self.index.infix_searchable_fields_ids(self.wtxn)?;
+        if infix_fields_ids.is_empty() {
+            self.index.put_words_infixes_fst(self.wtxn, &fst::Set::default())?;
+            return Ok(());
+        }
+
+        let mut entries = BTreeSet::new();
+        let iter = self.index.word_fid_docids.remap_data_type::<DecodeIgnore>().iter(self.wtxn)?;
+        for result in iter {
+            let ((word, fid), ()) = result?;
+            insert_infix_entries(&mut entries, &infix_fields_ids, word, fid);
+        }
+
+        let infix_fst = fst::Set::from_iter(entries)?;
+        self.index.put_words_infixes_fst(self.wtxn, &infix_fst)?;
+
+        Ok(())
+    }
+
+    /// Adds the infixes of the words of a newly indexed `word_fid_docids` chunk
+    /// to the existing dictionnary, without scanning the whole database.
+    #[logging_timer::time("WordsInfixesFst::{}")]
+    pub fn execute_with_new_words(
+        self,
+        new_word_fid_docids: grenad::Reader<CursorClonableMmap>,
+    ) -> Result<()> {
+        let infix_fields_ids = self.index.infix_searchable_fields_ids(self.wtxn)?;
+        if infix_fields_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = BTreeSet::new();
+        let mut cursor = new_word_fid_docids.into_cursor()?;
+        while let Some((key, _)) = cursor.move_on_next()? {
+            let (word, fid) = StrBEU16Codec::bytes_decode(key).ok_or(heed::Error::Decoding)?;
+            insert_infix_entries(&mut entries, &infix_fields_ids, word, fid);
+        }
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        // merge the new infixes with the database fst
+        let fst = fst::Set::from_iter(entries)?;
+        let db_fst = self.index.words_infixes_fst(self.wtxn)?;
+        let union_stream = fst.op().add(db_fst.stream()).union();
+        let mut builder = fst::SetBuilder::memory();
+        builder.extend_stream(union_stream)?;
+        let fst = builder.into_set();
+        self.index.put_words_infixes_fst(self.wtxn, &fst)?;
+
+        Ok(())
+    }
+}
+
+fn insert_infix_entries(
+    entries: &mut BTreeSet<String>,
+    infix_fields_ids: &HashSet<FieldId>,
+    word: &str,
+    fid: FieldId,
+) {
+    // the stems are not written by the users, they are only found from the full words.
+    if !infix_fields_ids.contains(&fid) || is_stem(word) {
+        return;
+    }
+
+    // The whole word is skipped as it is already found by the words and prefixes
+    // dictionnaries, we only keep the suffixes starting at a valid character bound.
+    for (start, _) in word.char_indices().skip(1) {
+        let suffix = &word[start..];
+        if suffix.chars().count() < MIN_INFIX_LENGTH {
+            break;
+        }
+        entries.insert(format!("{suffix}{INFIX_WORD_SEPARATOR}{word}"));
+    }
+}